@@ -9,7 +9,7 @@ pub struct BasicBankDecisionModel;
 
 #[typetag::serde]
 impl DecisionModel for BasicBankDecisionModel {
-    fn decide(&self, agent: &dyn Any, state: &SimState, _rng: &mut dyn RngCore) -> Vec<SimAction> {
+    fn decide(&self, agent: &dyn Any, state: &SimState, rng: &mut dyn RngCore) -> Vec<SimAction> {
         let bank = match agent.downcast_ref::<Bank>() {
             Some(b) => b,
             None => return vec![],
@@ -17,55 +17,122 @@ impl DecisionModel for BasicBankDecisionModel {
         let mut actions = Vec::new();
         let fs = &state.financial_system;
 
-        self.manage_reserves(bank, fs, &mut actions);
-        self.market_make_treasuries(bank, fs, &mut actions);
+        self.manage_reserves(bank, state, &mut actions);
+        self.market_make_treasuries(bank, fs, state.current_date, &mut actions);
+        self.bid_in_auctions(bank, state, rng, &mut actions);
 
         actions
     }
 }
 
 impl BasicBankDecisionModel {
-    fn manage_reserves(&self, bank: &Bank, fs: &FinancialSystem, actions: &mut Vec<SimAction>) {
-        let total_deposits = fs.get_total_liabilities(&bank.id);
-        let required_reserves = total_deposits * fs.central_bank.reserve_requirement;
-        let desired_buffer = total_deposits * 0.02;
+    /// Keeps reserves at the requirement on its deposits plus a buffer. A shortfall is bid for in the SOFR
+    /// market, or borrowed from the lending facility against Treasuries when nobody offered overnight
+    /// funds yesterday. Most of a surplus is offered there, or placed at the deposit facility when nobody
+    /// bid for overnight funds yesterday, and the rest goes into bills. Quotes sit mid-corridor, between
+    /// the deposit and lending facility rates, since no bank lends below the one or borrows above the other.
+    fn manage_reserves(&self, bank: &Bank, state: &SimState, actions: &mut Vec<SimAction>) {
+        let (fs, date) = (&state.financial_system, state.current_date);
+        let required_reserves = fs.required_reserves(&bank.id);
+        let desired_buffer = fs.reservable_liabilities(&bank.id) * 0.02;
         let target_reserve_level = required_reserves + desired_buffer;
 
         let current_reserves = fs.get_bank_reserves(&bank.id).unwrap_or(0.0);
-        let reserve_surplus_or_shortfall = current_reserves - target_reserve_level;
+        // Lending-facility loans from last night's clearing fall due today and must be refinanced in the market.
+        let facility_borrowing = fs
+            .get_bs_by_id(&bank.id)
+            .map(|bs| {
+                bs.liabilities
+                    .values()
+                    .filter(|inst| {
+                        inst.details
+                            .as_any()
+                            .downcast_ref::<LoanDetails>()
+                            .is_some_and(|loan| loan.loan_type == LoanType::StandingFacility)
+                    })
+                    .map(|inst| inst.principal)
+                    .sum::<f64>()
+            })
+            .unwrap_or(0.0);
+        let reserve_surplus_or_shortfall = current_reserves - facility_borrowing - target_reserve_level;
 
         let overnight_market_id = FinancialMarketId::SecuredOvernightFinancing;
 
-        let floor_rate_bps = fs.central_bank.policy_rate * 10000.0;
-        let ceiling_rate_bps = floor_rate_bps + 25.0;
+        let floor_rate_bps = fs.central_bank.deposit_facility_rate() * 10000.0;
+        let ceiling_rate_bps = fs.central_bank.lending_facility_rate() * 10000.0;
         let target_rate_bps = (floor_rate_bps + ceiling_rate_bps) / 2.0;
 
         let daily_rate = overnight_market_id.annual_bps_to_daily_rate(target_rate_bps);
         let price = 1.0 / (1.0 + daily_rate);
+        let market_id = MarketId::Financial(overnight_market_id.clone());
+        let last_tick = state.history.market_ticks.get(&market_id).and_then(|ticks| ticks.back());
 
         if reserve_surplus_or_shortfall < -1.0 {
             let amount_needed = -reserve_surplus_or_shortfall;
-
-            actions.push(SimAction::Trading(TradingAction::PostBid {
-                agent_id: bank.id,
-                market_id: MarketId::Financial(overnight_market_id.clone()),
-                quantity: amount_needed,
-                price,
-            }));
+            let lenders_offered = last_tick.is_none_or(|tick| tick.best_ask.is_some());
+            let capacity = fs.facility_borrowing_capacity(&bank.id, date);
+            if !lenders_offered && capacity > 1.0 {
+                let amount = amount_needed.min(capacity);
+                actions.push(SimAction::Banking(BankingAction::BorrowFromFacility { bank: bank.id, amount }));
+            } else {
+                actions.push(SimAction::Trading(TradingAction::PostBid {
+                    agent_id: bank.id,
+                    market_id,
+                    quantity: amount_needed,
+                    price,
+                }));
+            }
         } else if reserve_surplus_or_shortfall > 1.0 {
             let amount_to_lend = reserve_surplus_or_shortfall * 0.75;
-            if amount_to_lend > 100.0 {
+            let borrowers_bid = last_tick.is_none_or(|tick| tick.best_bid.is_some());
+            if amount_to_lend > 100.0 && !borrowers_bid {
+                let amount = amount_to_lend;
+                actions.push(SimAction::Banking(BankingAction::DepositAtFacility { bank: bank.id, amount }));
+            } else if amount_to_lend > 100.0 {
                 actions.push(SimAction::Trading(TradingAction::PostAsk {
                     agent_id: bank.id,
-                    market_id: MarketId::Financial(overnight_market_id.clone()),
+                    market_id,
                     quantity: amount_to_lend,
                     price,
                 }));
             }
+            self.buy_bills(bank, fs, date, reserve_surplus_or_shortfall - amount_to_lend, actions);
         }
     }
 
-    fn market_make_treasuries(&self, bank: &Bank, fs: &FinancialSystem, actions: &mut Vec<SimAction>) {
+    /// Bills are the bank's liquidity buffer: surplus reserves not lent overnight are bid into the
+    /// shortest bill market at the policy rate.
+    fn buy_bills(
+        &self, bank: &Bank, fs: &FinancialSystem, date: chrono::NaiveDate, amount: f64, actions: &mut Vec<SimAction>,
+    ) {
+        let shortest_bill = fs
+            .exchange
+            .financial_markets
+            .keys()
+            .filter_map(|market_id| match market_id {
+                FinancialMarketId::Treasury { tenor } if tenor.is_bill() => Some(*tenor),
+                _ => None,
+            })
+            .min_by_key(|tenor| tenor.to_days());
+        let Some(tenor) = shortest_bill else {
+            return;
+        };
+        let bill = BondDetails::benchmark(tenor, date);
+        let price = bill.pricer(date).clean_price(fs.central_bank.policy_rate);
+        let quantity = (amount / price).floor();
+        if quantity >= 1.0 {
+            actions.push(SimAction::Trading(TradingAction::PostBid {
+                agent_id: bank.id,
+                market_id: MarketId::Financial(FinancialMarketId::Treasury { tenor }),
+                quantity,
+                price,
+            }));
+        }
+    }
+
+    fn market_make_treasuries(
+        &self, bank: &Bank, fs: &FinancialSystem, date: chrono::NaiveDate, actions: &mut Vec<SimAction>,
+    ) {
         let bs = fs.get_bs_by_id(&bank.id).expect("Bank must have BS");
 
         let mut holdings_by_tenor: HashMap<Tenor, u64> = HashMap::new();
@@ -78,9 +145,6 @@ impl BasicBankDecisionModel {
         }
 
         let quantity_to_quote = 5.0;
-        const FACE_VALUE: f64 = 1000.0;
-        let frequency = 2;
-
 
         for (market_id, _) in &fs.exchange.financial_markets {
             if let FinancialMarketId::Treasury { tenor } = market_id {
@@ -93,14 +157,11 @@ impl BasicBankDecisionModel {
                 let bid_yield = bid_yield_bps / 10000.0; // 435 -> 0.0435
                 let ask_yield = ask_yield_bps / 10000.0; // 425 -> 0.0425
 
-                let coupon_rate = 0.04;
-                let years_to_maturity = tenor.to_days() as f64 / 365.25;
-
-                let bid_price =
-                    self.calculate_bond_price(FACE_VALUE, coupon_rate, bid_yield, years_to_maturity, frequency);
-
-                let ask_price =
-                    self.calculate_bond_price(FACE_VALUE, coupon_rate, ask_yield, years_to_maturity, frequency);
+                // Quotes are clean prices for the tenor's benchmark issue
+                let benchmark = BondDetails::benchmark(*tenor, date);
+                let pricer = benchmark.pricer(date);
+                let bid_price = pricer.clean_price(bid_yield);
+                let ask_price = pricer.clean_price(ask_yield);
 
                 actions.push(SimAction::Trading(TradingAction::PostBid {
                     agent_id: bank.id,
@@ -122,28 +183,35 @@ impl BasicBankDecisionModel {
         }
     }
 
-    fn calculate_bond_price(
-        &self, face_value: f64, coupon_rate: f64, ytm: f64, years_to_maturity: f64, frequency: usize,
-    ) -> f64 {
-        let k = frequency as f64;
-        let n = (years_to_maturity * k).round() as usize;
-        let c = coupon_rate * face_value / k;
-        let y = ytm / k;
-
-        if n == 0 {
-            return face_value;
-        }
-
-        let mut price = 0.0;
-
-        if (y).abs() > 1e-9 {
-            price += c * (1.0 - (1.0 + y).powf(-(n as f64))) / y;
-        } else {
-            price += c * n as f64;
+    /// Bids once in each open Treasury auction as a primary dealer: competitively for 1.25 times its
+    /// share of the offering, at the curve's yield give or take a basis point or two, and
+    /// non-competitively for a twentieth of its share. Bids are limited to half of the reserves above
+    /// the requirement.
+    fn bid_in_auctions(&self, bank: &Bank, state: &SimState, rng: &mut dyn RngCore, actions: &mut Vec<SimAction>) {
+        let fs = &state.financial_system;
+        let dealers = state.agents.banks.len().max(1) as f64;
+        let excess_reserves = fs.get_bank_reserves(&bank.id).unwrap_or(0.0) - fs.required_reserves(&bank.id);
+        let mut budget = (excess_reserves * 0.5 / BENCHMARK_FACE_VALUE).floor().max(0.0) as u64;
+        let open = fs
+            .treasury_auctions
+            .iter()
+            .filter(|auction| auction.is_open(state.current_date) && !auction.has_bid_from(&bank.id));
+        for auction in open {
+            let share = auction.offering as f64 / dealers;
+            let tail = (rng.next_u32() as f64 / u32::MAX as f64 - 0.5) * 0.0004;
+            let yield_bid = (fs.risk_free_rate(&auction.tenor) + tail).max(0.0);
+            let bids = [((share * 1.25).ceil() as u64, Some(yield_bid)), ((share * 0.05).floor() as u64, None)];
+            for (quantity, yield_bid) in bids {
+                let quantity = quantity.min(budget);
+                if quantity == 0 {
+                    continue;
+                }
+                budget -= quantity;
+                actions.push(SimAction::Fiscal(FiscalAction::SubmitAuctionBid {
+                    auction_id: auction.id,
+                    bid: AuctionBid { bidder: bank.id, quantity, yield_bid },
+                }));
+            }
         }
-
-        price += face_value / (1.0 + y).powf(n as f64);
-
-        price
     }
 }
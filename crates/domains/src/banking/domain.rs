@@ -1,14 +1,18 @@
 use serde::{Deserialize, Serialize};
 use sim_core::*;
 use sim_macros::SimDomain;
+use crate::banking::LendingModel;
+use crate::fiscal::{Levy, pay_with_levy};
 
 // The SimDomain derive macro is added here. It will auto-generate the `impl Domain for BankingDomain` block.
 #[derive(Clone, Debug, Serialize, Deserialize, SimDomain)]
-pub struct BankingDomain {}
+pub struct BankingDomain {
+    lending: LendingModel,
+}
 
 impl BankingDomain {
     pub fn new() -> Self {
-        Self {}
+        Self { lending: LendingModel::default() }
     }
 
     pub fn execute(&self, action: &BankingAction, state: &SimState) -> BankingResult {
@@ -22,13 +26,15 @@ impl BankingDomain {
                 self.execute_withdraw(*agent_id, *bank, *amount, state)
             }
             BankingAction::Transfer { from, to, amount } => self.execute_transfer(*from, *to, *amount, state),
-            BankingAction::PayWages { agent_id, employee, amount } => {
-                self.execute_transfer(*agent_id, *employee, *amount, state)
+            BankingAction::PayPayroll { agent_id } => self.execute_pay_payroll(*agent_id, state),
+            BankingAction::BorrowFromFacility { bank, amount } => {
+                self.execute_borrow_from_facility(*bank, *amount, state)
             }
-            BankingAction::UpdateReserves { bank, amount_change } => {
-                self.execute_update_reserves(*bank, *amount_change, state)
+            BankingAction::DepositAtFacility { bank, amount } => {
+                self.execute_deposit_at_facility(*bank, *amount, state)
             }
             BankingAction::InjectLiquidity => self.execute_inject_liquidity(state),
+            BankingAction::RequestLoan { request } => self.execute_request_loan(request, state),
         }
     }
 
@@ -41,11 +47,45 @@ impl BankingDomain {
                 self.validate_withdraw(*agent_id, *bank, *amount, state)
             }
             BankingAction::Transfer { from, to, amount } => self.validate_transfer(*from, *to, *amount, state),
-            BankingAction::PayWages { agent_id, employee, amount } => {
-                self.validate_transfer(*agent_id, *employee, *amount, state)
+            BankingAction::PayPayroll { agent_id } => {
+                let firm = state.agents.firms.get(agent_id).ok_or_else(|| format!("Firm {} does not exist", agent_id))?;
+                let payroll: f64 = firm.weekly_wages().iter().map(|(_, wage)| wage).sum();
+                Validator::positive_amount(payroll)?;
+                self.validate_sufficient_liquid_assets(*agent_id, payroll, state)
+            }
+            BankingAction::BorrowFromFacility { bank, amount } => {
+                Validator::positive_amount(*amount)?;
+                self.validate_bank_exists(*bank, state)?;
+                let capacity = state.financial_system.facility_borrowing_capacity(bank, state.current_date);
+                if *amount <= capacity {
+                    Ok(())
+                } else {
+                    Err(format!(
+                        "Insufficient Treasury collateral for {}: can borrow ${:.2}, need ${:.2}",
+                        bank, capacity, amount
+                    ))
+                }
+            }
+            BankingAction::DepositAtFacility { bank, amount } => {
+                Validator::positive_amount(*amount)?;
+                self.validate_bank_exists(*bank, state)?;
+                let reserves = self.available_reserves(*bank, state);
+                if *amount <= reserves {
+                    Ok(())
+                } else {
+                    Err(format!("Insufficient reserves for {}: have ${:.2}, need ${:.2}", bank, reserves, amount))
+                }
             }
-            BankingAction::UpdateReserves { bank, .. } => self.validate_bank_exists(*bank, state),
             BankingAction::InjectLiquidity => Ok(()),
+            BankingAction::RequestLoan { request } => {
+                Validator::positive_amount(request.amount)?;
+                let borrower = request.borrower;
+                if state.agents.consumers.contains_key(&borrower) || state.agents.firms.contains_key(&borrower) {
+                    self.validate_agent_exists(borrower, state)
+                } else {
+                    Err(format!("Only firms and households borrow from banks, not {}", borrower))
+                }
+            }
         }
     }
 
@@ -114,7 +154,7 @@ impl BankingDomain {
     fn validate_sufficient_liquid_assets(
         &self, agent_id: AgentId, amount: f64, state: &SimState,
     ) -> Result<(), String> {
-        let liquid_assets = state.financial_system.get_liquid_assets(&agent_id);
+        let liquid_assets = self.available_funds(agent_id, state);
         if liquid_assets >= amount {
             Ok(())
         } else {
@@ -135,12 +175,22 @@ impl BankingDomain {
     pub fn execute_deposit(&self, depositor: AgentId, bank: AgentId, amount: f64, state: &SimState) -> BankingResult {
         let mut effects = vec![];
 
-        let deposit_rate = state.financial_system.central_bank.policy_rate - 0.02;
-        let deposit = deposit!(depositor, bank, amount, deposit_rate, state.current_date);
+        let deposit = deposit!(depositor, bank, amount, self.offered_deposit_rate(bank, state), state.current_date);
+        let deposit_id = state.financial_system.find_consolidatable_instrument(&deposit).unwrap_or(deposit.id);
         effects.push(StateEffect::Financial(FinancialEffect::CreateInstrument(deposit)));
 
-        let transfer_effects = self.create_transfer_effects(depositor, bank, amount, state);
-        effects.extend(transfer_effects);
+        match self.create_transfer_effects(depositor, &[(bank, amount)], state) {
+            Ok((transfer_effects, _)) => effects.extend(transfer_effects),
+            Err(error) => return BankingResult { success: false, effects: vec![], errors: vec![error] },
+        }
+        effects.push(self.record_transaction(
+            depositor,
+            bank,
+            amount,
+            TransactionType::Deposit { holder: depositor, bank, amount },
+            Some(deposit_id),
+            state,
+        ));
 
         BankingResult { success: !effects.is_empty(), effects, errors: vec![] }
     }
@@ -161,113 +211,453 @@ impl BankingDomain {
                     .push(StateEffect::Financial(FinancialEffect::UpdateInstrument { id: *deposit_id, new_principal }));
             }
 
-            let transfer_effects = self.create_transfer_effects(bank, account_holder, amount, state);
-            effects.extend(transfer_effects);
+            match self.create_transfer_effects(bank, &[(account_holder, amount)], state) {
+                Ok((transfer_effects, _)) => effects.extend(transfer_effects),
+                Err(error) => return BankingResult { success: false, effects: vec![], errors: vec![error] },
+            }
+            effects.push(self.record_transaction(
+                bank,
+                account_holder,
+                amount,
+                TransactionType::Withdrawal { holder: account_holder, bank, amount },
+                Some(*deposit_id),
+                state,
+            ));
         }
 
         BankingResult { success: !effects.is_empty(), effects, errors: vec![] }
     }
 
     pub fn execute_transfer(&self, from: AgentId, to: AgentId, amount: f64, state: &SimState) -> BankingResult {
-        let effects = self.create_transfer_effects(from, to, amount, state);
-        BankingResult { success: !effects.is_empty(), effects, errors: vec![] }
+        self.execute_payment(from, to, amount, TransactionType::Transfer { from, to, amount }, None, state)
+    }
+
+    /// Pays each of `firm`'s employees a week's wages net of the income tax and unemployment insurance
+    /// contribution withheld, which the firm remits to the government. The wages and the withholding
+    /// go out as one split payment, so the firm is debited once for the gross payroll.
+    pub fn execute_pay_payroll(&self, firm: AgentId, state: &SimState) -> BankingResult {
+        let wages = state.agents.firms.get(&firm).map(Firm::weekly_wages).unwrap_or_default();
+        let mut payments = vec![];
+        let mut levies = vec![];
+        for &(employee, amount) in &wages {
+            let withheld: Vec<Levy> =
+                [Levy::withholding(employee, amount, state), Levy::contribution(employee, amount, state)]
+                    .into_iter()
+                    .flatten()
+                    .collect();
+            let net = amount - withheld.iter().map(|levy| levy.amount).sum::<f64>();
+            payments.push((employee, net, TransactionType::Transfer { from: firm, to: employee, amount: net }));
+            levies.extend(withheld);
+        }
+        let mut result = pay_with_levy(self, firm, payments, None, &levies, state);
+        if result.success {
+            result.effects.push(StateEffect::Financial(FinancialEffect::RecordIncome {
+                agent_id: firm,
+                line: IncomeLine::OperatingExpense,
+                amount: wages.iter().map(|(_, wage)| wage).sum(),
+            }));
+        }
+        result
+    }
+
+    /// Moves `amount` from `from` to `to` and records the movement in the transaction ledger.
+    /// `instrument_id` names the instrument the payment relates to (e.g. the bond paying a coupon);
+    /// when absent, the payer's funding instrument is recorded instead.
+    ///
+    /// A payment the payer cannot fund moves nothing: the result is unsuccessful and its only effect
+    /// records the `PaymentFailure`.
+    pub fn execute_payment(
+        &self, from: AgentId, to: AgentId, amount: f64, tx_type: TransactionType, instrument_id: Option<InstrumentId>,
+        state: &SimState,
+    ) -> BankingResult {
+        self.execute_split_payment(from, &[(to, amount, tx_type)], instrument_id, state)
+    }
+
+    /// Makes several payments out of `from`'s funds at once, each recorded as its own transaction.
+    /// The payer's balances are drawn down once for the total. Either every payment is made or none
+    /// is.
+    pub fn execute_split_payment(
+        &self, from: AgentId, payments: &[(AgentId, f64, TransactionType)], instrument_id: Option<InstrumentId>,
+        state: &SimState,
+    ) -> BankingResult {
+        let payees: Vec<(AgentId, f64)> = payments.iter().map(|(to, amount, _)| (*to, *amount)).collect();
+        match self.create_transfer_effects(from, &payees, state) {
+            Ok((mut effects, funding_instrument)) => {
+                for (to, amount, tx_type) in payments {
+                    effects.push(self.record_transaction(
+                        from,
+                        *to,
+                        *amount,
+                        tx_type.clone(),
+                        instrument_id.or(funding_instrument),
+                        state,
+                    ));
+                }
+                BankingResult { success: true, effects, errors: vec![] }
+            }
+            Err(error) => {
+                let failure = PaymentFailure {
+                    tick: state.ticknum,
+                    date: state.current_date,
+                    payer: from,
+                    payee: payees.first().map_or(from, |(to, _)| *to),
+                    amount: payees.iter().map(|(_, amount)| amount).sum(),
+                    available: self.available_funds(from, state),
+                    instrument_id,
+                };
+                BankingResult {
+                    success: false,
+                    effects: vec![StateEffect::Financial(FinancialEffect::RecordPaymentFailure(failure))],
+                    errors: vec![error],
+                }
+            }
+        }
+    }
+
+    /// Everything `agent_id` can pay with: cash and demand deposits, plus reserves net of the day's
+    /// queued interbank payments for banks. The central bank can always pay.
+    pub fn available_funds(&self, agent_id: AgentId, state: &SimState) -> f64 {
+        let fs = &state.financial_system;
+        if agent_id == fs.central_bank.id {
+            return f64::INFINITY;
+        }
+        let mut funds = fs.get_liquid_assets(&agent_id);
+        if state.agents.banks.contains_key(&agent_id) {
+            funds += self.available_reserves(agent_id, state);
+        }
+        funds
+    }
+
+    fn available_reserves(&self, bank: AgentId, state: &SimState) -> f64 {
+        let fs = &state.financial_system;
+        let queued = fs.payment_system.net_positions().get(&bank).copied().unwrap_or(0.0);
+        (fs.get_bank_reserves(&bank).unwrap_or(0.0) + queued).max(0.0)
+    }
+
+    pub fn record_transaction(
+        &self, from: AgentId, to: AgentId, amount: f64, tx_type: TransactionType, instrument_id: Option<InstrumentId>,
+        state: &SimState,
+    ) -> StateEffect {
+        StateEffect::Financial(FinancialEffect::RecordTransaction(Transaction::new(
+            state.ticknum,
+            from,
+            to,
+            amount,
+            tx_type,
+            instrument_id,
+        )))
     }
 
-    pub fn execute_update_reserves(&self, _bank: AgentId, _amount_change: f64, _state: &SimState) -> BankingResult {
-        BankingResult {
-            success: false,
-            effects: vec![],
-            errors: vec!["Reserve update not yet implemented".to_string()],
+    /// Puts `request` to every bank and takes the cheapest offer. The loan is paid into the borrower's
+    /// account at its own bank and pays interest monthly. Every bank's decision is recorded; when all
+    /// of them refuse, the result is unsuccessful and gives each bank's reasons.
+    fn execute_request_loan(&self, request: &LoanRequest, state: &SimState) -> BankingResult {
+        let mut banks: Vec<&Bank> = state.agents.banks.values().collect();
+        banks.sort_by_key(|bank| bank.id.0);
+        let borrower_bank = self.settlement_bank(request.borrower, state);
+        let mut decisions: Vec<LoanDecision> = banks
+            .into_iter()
+            .map(|bank| {
+                let available_reserves = self.available_reserves(bank.id, state);
+                self.lending.decide(bank, request, borrower_bank, available_reserves, state)
+            })
+            .collect();
+        let best = decisions
+            .iter()
+            .enumerate()
+            .filter_map(|(i, decision)| decision.offered_rate().map(|rate| (i, rate)))
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(i, _)| i);
+
+        let Some(best) = best else {
+            let errors = decisions
+                .iter()
+                .flat_map(|decision| match &decision.outcome {
+                    LoanOutcome::Rejected { reasons } => reasons
+                        .iter()
+                        .map(|reason| format!("Bank {} refused {}: {}", decision.bank, request.borrower, reason))
+                        .collect(),
+                    LoanOutcome::Offered { .. } => vec![],
+                })
+                .collect();
+            let effects = decisions.into_iter().map(record_decision).collect();
+            return BankingResult { success: false, effects, errors };
+        };
+        decisions[best].accepted = true;
+        let (bank, rate) = (decisions[best].bank, decisions[best].offered_rate().unwrap_or_default());
+
+        let (borrower, amount, date) = (request.borrower, request.amount, state.current_date);
+        let maturity = request.tenor.add_to_date(date);
+        let calendar = &state.financial_system.calendar;
+        let mut loan = loan!(bank, borrower, amount, request.loan_type.clone(), rate, maturity, date, calendar);
+        if let (LoanType::Commercial, Some(reset)) = (&request.loan_type, &self.lending.commercial_reset) {
+            let spread = rate - state.rate_fixing(RateIndex::PolicyRate);
+            loan = loan.with_floating_rate(RateIndex::PolicyRate, spread, reset.clone());
         }
+        let loan_id = loan.id;
+
+        let mut effects: Vec<StateEffect> = decisions.into_iter().map(record_decision).collect();
+        effects.push(StateEffect::Financial(FinancialEffect::CreateInstrument(loan)));
+        effects.extend(self.credit_bank_money(bank, borrower, amount, state));
+        let tx_type = TransactionType::Transfer { from: bank, to: borrower, amount };
+        effects.push(self.record_transaction(bank, borrower, amount, tx_type, Some(loan_id), state));
+        BankingResult { success: true, effects, errors: vec![] }
+    }
+
+    /// Lends `bank` reserves overnight against its Treasuries. The reserves move through the payment
+    /// queue like any other interbank payment, so the bank can use them straight away.
+    pub fn execute_borrow_from_facility(&self, bank: AgentId, amount: f64, state: &SimState) -> BankingResult {
+        let (central_bank, calendar) = (&state.financial_system.central_bank, &state.financial_system.calendar);
+        let loan = central_bank.lending_facility_loan(bank, amount, amount, state.current_date, calendar);
+        let tx_type = TransactionType::FacilityLoan { bank, amount };
+        let effects = self.facility_effects(central_bank.id, bank, amount, loan, tx_type, state);
+        BankingResult { success: true, effects, errors: vec![] }
+    }
+
+    /// Moves `amount` of `bank`'s reserves overnight to the deposit facility, where they earn the
+    /// deposit facility rate rather than the lower rate on excess reserves.
+    pub fn execute_deposit_at_facility(&self, bank: AgentId, amount: f64, state: &SimState) -> BankingResult {
+        let (central_bank, calendar) = (&state.financial_system.central_bank, &state.financial_system.calendar);
+        let placement = central_bank.deposit_facility_placement(bank, amount, state.current_date, calendar);
+        let tx_type = TransactionType::FacilityDeposit { bank, amount };
+        let effects = self.facility_effects(bank, central_bank.id, amount, placement, tx_type, state);
+        BankingResult { success: true, effects, errors: vec![] }
+    }
+
+    fn facility_effects(
+        &self, from: AgentId, to: AgentId, amount: f64, instrument: FinancialInstrument, tx_type: TransactionType,
+        state: &SimState,
+    ) -> Vec<StateEffect> {
+        let obligation = InterbankObligation { from_bank: from, to_bank: to, amount, date: state.current_date };
+        let record = self.record_transaction(from, to, amount, tx_type, Some(instrument.id), state);
+        vec![
+            StateEffect::Financial(FinancialEffect::CreateInstrument(instrument)),
+            StateEffect::Financial(FinancialEffect::QueueInterbankObligation(obligation)),
+            record,
+        ]
     }
 
     pub fn execute_inject_liquidity(&self, state: &SimState) -> BankingResult {
+        let cb_id = state.financial_system.central_bank.id;
         let effects: Vec<StateEffect> = state
             .agents
             .consumers
-            .iter()
-            .map(|consumer| {
-                let cash = cash!(*consumer.0, 1000.0, state.financial_system.central_bank.id, state.current_date);
-                StateEffect::Financial(FinancialEffect::CreateInstrument(cash))
+            .keys()
+            .flat_map(|consumer_id| {
+                let cash = cash!(*consumer_id, 1000.0, cb_id, state.current_date);
+                let record = self.record_transaction(
+                    cb_id,
+                    *consumer_id,
+                    1000.0,
+                    TransactionType::Transfer { from: cb_id, to: *consumer_id, amount: 1000.0 },
+                    Some(cash.id),
+                    state,
+                );
+                [StateEffect::Financial(FinancialEffect::CreateInstrument(cash)), record]
             })
             .collect();
 
         BankingResult { success: true, effects, errors: vec![] }
     }
 
-    fn create_transfer_effects(&self, from: AgentId, to: AgentId, amount: f64, state: &SimState) -> Vec<StateEffect> {
-        let mut effects = vec![];
+    /// The bank whose books hold an agent's money: households and firms bank at `bank_id`, banks settle
+    /// on their own reserve account, and the government and central bank hold money at the central bank.
+    pub fn settlement_bank(&self, agent_id: AgentId, state: &SimState) -> Option<AgentId> {
         let cb_id = state.financial_system.central_bank.id;
-        let from_bs = match state.financial_system.get_bs_by_id(&from) {
-            Some(bs) => bs,
-            None => return vec![], // Should be caught by validation
-        };
+        if let Some(consumer) = state.agents.get_consumer(&agent_id) {
+            Some(consumer.bank_id)
+        } else if let Some(firm) = state.agents.get_firm(&agent_id) {
+            Some(firm.bank_id)
+        } else if state.agents.banks.contains_key(&agent_id) || agent_id == cb_id {
+            Some(agent_id)
+        } else if agent_id == state.financial_system.government.id {
+            Some(cb_id)
+        } else {
+            None
+        }
+    }
 
-        let (cash_id, cash_on_hand) = from_bs
-            .assets
-            .iter()
-            .find(|(_, inst)| inst.details.as_any().is::<CashDetails>())
-            .map(|(id, inst)| (Some(*id), inst.principal))
-            .unwrap_or((None, 0.0));
-
-        let amount_from_cash = cash_on_hand.min(amount);
-        let amount_remaining_for_deposit = amount - amount_from_cash;
-
-        if amount_from_cash > 1e-6 {
-            if let Some(id) = cash_id {
-                let new_principal = cash_on_hand - amount_from_cash;
-                if new_principal < 1e-6 {
-                    effects.push(StateEffect::Financial(FinancialEffect::RemoveInstrument(id)));
-                } else {
-                    effects.push(StateEffect::Financial(FinancialEffect::UpdateInstrument { id, new_principal }));
-                }
+    fn deposit_rate(&self, holder: AgentId, bank: AgentId, state: &SimState) -> f64 {
+        state
+            .financial_system
+            .get_bs_by_id(&holder)
+            .and_then(|bs| {
+                bs.assets
+                    .values()
+                    .filter(|inst| inst.debtor == bank)
+                    .find_map(|inst| inst.details.as_any().downcast_ref::<DemandDepositDetails>())
+            })
+            .map(|details| details.interest_rate)
+            .unwrap_or_else(|| self.offered_deposit_rate(bank, state))
+    }
+
+    /// The rate `bank` pays on a new deposit, under its `deposit_spread`.
+    fn offered_deposit_rate(&self, bank: AgentId, state: &SimState) -> f64 {
+        let policy_rate = state.financial_system.central_bank.policy_rate;
+        state.agents.banks.get(&bank).map_or(policy_rate, |bank| bank.deposit_rate(policy_rate))
+    }
+
+    fn debit_effect(&self, id: InstrumentId, current: f64, amount: f64) -> StateEffect {
+        let new_principal = current - amount;
+        if new_principal.abs() < 1e-6 {
+            StateEffect::Financial(FinancialEffect::RemoveInstrument(id))
+        } else {
+            StateEffect::Financial(FinancialEffect::UpdateInstrument { id, new_principal })
+        }
+    }
+
+    /// Queues the reserve payment that backs a movement of bank money from `from_bank`'s books to
+    /// `to_bank`'s. Reserves only change hands when the payment system settles net at end of day.
+    pub fn queue_reserve_payment(
+        &self, from_bank: AgentId, to_bank: AgentId, amount: f64, state: &SimState,
+    ) -> Vec<StateEffect> {
+        if from_bank == to_bank || amount <= 1e-6 {
+            return vec![];
+        }
+        vec![StateEffect::Financial(FinancialEffect::QueueInterbankObligation(InterbankObligation {
+            from_bank,
+            to_bank,
+            amount,
+            date: state.current_date,
+        }))]
+    }
+
+    /// Credits `to` with bank money paid out of `paying_bank`'s books. Payees with an account get a deposit
+    /// at their own bank; banks and the central bank are paid in reserves. Agents with no known bank are
+    /// paid in cash, which the paying bank buys from the central bank with reserves.
+    fn credit_bank_money(&self, paying_bank: AgentId, to: AgentId, amount: f64, state: &SimState) -> Vec<StateEffect> {
+        let cb_id = state.financial_system.central_bank.id;
+        match self.settlement_bank(to, state) {
+            Some(bank) if bank == to => self.queue_reserve_payment(paying_bank, bank, amount, state),
+            Some(bank) => {
+                let rate = self.deposit_rate(to, bank, state);
+                let mut effects = vec![StateEffect::Financial(FinancialEffect::CreateInstrument(deposit!(
+                    to,
+                    bank,
+                    amount,
+                    rate,
+                    state.current_date
+                )))];
+                effects.extend(self.queue_reserve_payment(paying_bank, bank, amount, state));
+                effects
+            }
+            None => {
+                let mut effects = self.queue_reserve_payment(paying_bank, cb_id, amount, state);
                 effects.push(StateEffect::Financial(FinancialEffect::CreateInstrument(cash!(
                     to,
-                    amount_from_cash,
+                    amount,
                     cb_id,
                     state.current_date
                 ))));
+                effects
             }
         }
+    }
 
-        if amount_remaining_for_deposit > 1e-6 {
-            if let Some((dep_id, dep_inst)) =
-                from_bs.assets.iter().find(|(_, inst)| inst.details.as_any().is::<DemandDepositDetails>())
-            {
-                let payer_bank_id = dep_inst.debtor;
-                let new_deposit_principal = dep_inst.principal - amount_remaining_for_deposit;
+    /// Builds the effects of paying each of `payees` its amount from `from`. Funds are drawn in order: cash
+    /// on hand, handed over as cash; demand deposits, settled as bank money; and for banks, reserves. Each
+    /// drawing goes to the payees in turn. Returns the effects together with the first instrument the
+    /// payments were funded from, or an error if the payer cannot cover the total.
+    fn create_transfer_effects(
+        &self, from: AgentId, payees: &[(AgentId, f64)], state: &SimState,
+    ) -> Result<(Vec<StateEffect>, Option<InstrumentId>), String> {
+        let mut effects = vec![];
+        let mut funding_instrument = None;
+        let cb_id = state.financial_system.central_bank.id;
+        let from_bs =
+            state.financial_system.get_bs_by_id(&from).ok_or(format!("Payer {} has no balance sheet", from))?;
+        let amount: f64 = payees.iter().map(|(_, amount)| amount).sum();
+        let available = self.available_funds(from, state);
+        if available + 1e-6 < amount {
+            return Err(format!(
+                "Insufficient funds for payment: agent {} needs ${:.2}, has ${:.2}",
+                from, amount, available
+            ));
+        }
 
-                if new_deposit_principal < 1e-6 {
-                    effects.push(StateEffect::Financial(FinancialEffect::RemoveInstrument(*dep_id)));
-                } else {
-                    effects.push(StateEffect::Financial(FinancialEffect::UpdateInstrument {
-                        id: *dep_id,
-                        new_principal: new_deposit_principal,
-                    }));
-                }
+        let mut owed = payees.to_vec();
+        let mut remaining = amount;
+        let mut cash_paid: Vec<(AgentId, f64)> = vec![];
+        for (id, inst) in from_bs.assets.iter().filter(|(_, inst)| inst.details.as_any().is::<CashDetails>()) {
+            if remaining <= 1e-6 {
+                break;
+            }
+            let drawn = inst.principal.min(remaining);
+            if drawn <= 1e-6 {
+                continue;
+            }
+            funding_instrument = funding_instrument.or(Some(*id));
+            effects.push(self.debit_effect(*id, inst.principal, drawn));
+            for (to, paid) in share_out(&mut owed, drawn) {
+                add_to(&mut cash_paid, to, paid);
+            }
+            remaining -= drawn;
+        }
+        for (to, paid) in cash_paid {
+            effects.push(StateEffect::Financial(FinancialEffect::CreateInstrument(cash!(
+                to,
+                paid,
+                cb_id,
+                state.current_date
+            ))));
+        }
 
-                if let Some((res_id, res_inst)) = state.financial_system.get_bs_by_id(&payer_bank_id).and_then(|bs| {
-                    bs.assets.iter().find(|(_, i)| i.details.as_any().is::<CentralBankReservesDetails>())
-                }) {
-                    let new_reserves = res_inst.principal - amount_remaining_for_deposit;
-                    if new_reserves < 1e-6 {
-                        effects.push(StateEffect::Financial(FinancialEffect::RemoveInstrument(*res_id)));
-                    } else {
-                        effects.push(StateEffect::Financial(FinancialEffect::UpdateInstrument {
-                            id: *res_id,
-                            new_principal: new_reserves,
-                        }));
-                    }
-                }
-                effects.push(StateEffect::Financial(FinancialEffect::CreateInstrument(cash!(
-                    to,
-                    amount_remaining_for_deposit,
-                    cb_id,
-                    state.current_date
-                ))));
+        let mut paid_by_bank: Vec<((AgentId, AgentId), f64)> = vec![];
+        for (id, inst) in from_bs.assets.iter().filter(|(_, inst)| inst.details.as_any().is::<DemandDepositDetails>()) {
+            if remaining <= 1e-6 {
+                break;
+            }
+            let drawn = inst.principal.min(remaining);
+            if drawn <= 1e-6 {
+                continue;
+            }
+            funding_instrument = funding_instrument.or(Some(*id));
+            effects.push(self.debit_effect(*id, inst.principal, drawn));
+            for (to, paid) in share_out(&mut owed, drawn) {
+                add_to(&mut paid_by_bank, (inst.debtor, to), paid);
+            }
+            remaining -= drawn;
+        }
+        for ((paying_bank, to), paid) in paid_by_bank {
+            effects.extend(self.credit_bank_money(paying_bank, to, paid, state));
+        }
+
+        if remaining > 1e-6 {
+            // Only banks and the central bank get here: they pay out of their own reserve account.
+            for (to, paid) in share_out(&mut owed, remaining) {
+                effects.extend(self.credit_bank_money(from, to, paid, state));
             }
         }
-        effects
+
+        Ok((effects, funding_instrument))
+    }
+}
+
+fn record_decision(decision: LoanDecision) -> StateEffect {
+    StateEffect::Financial(FinancialEffect::RecordLoanDecision(decision))
+}
+
+/// Splits `drawn` between the payees still owed, in order, reducing what each is owed.
+fn share_out(owed: &mut [(AgentId, f64)], mut drawn: f64) -> Vec<(AgentId, f64)> {
+    let mut shares = vec![];
+    for (to, outstanding) in owed.iter_mut() {
+        if drawn <= 1e-9 {
+            break;
+        }
+        let share = outstanding.min(drawn);
+        if share > 1e-9 {
+            shares.push((*to, share));
+            *outstanding -= share;
+            drawn -= share;
+        }
+    }
+    shares
+}
+
+fn add_to<K: PartialEq>(totals: &mut Vec<(K, f64)>, key: K, amount: f64) {
+    match totals.iter_mut().find(|(k, _)| *k == key) {
+        Some((_, total)) => *total += amount,
+        None => totals.push((key, amount)),
     }
 }
 
@@ -0,0 +1,151 @@
+use serde::{Deserialize, Serialize};
+use sim_core::*;
+
+/// How a bank decides on a loan request. The borrower is scored from its income, leverage and
+/// payment history; a loan is offered at the reference rate for its tenor plus the bank's
+/// `lending_spread` plus a risk premium that grows as the score falls. Credit is rationed by the
+/// bank's capital and reserves: a loan it cannot carry is refused however good the borrower. Loans to
+/// firms float: they are refixed every `commercial_reset` at the policy rate plus the spread they
+/// were priced at.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LendingModel {
+    pub income_weight: f64,
+    pub leverage_weight: f64,
+    pub history_weight: f64,
+    /// Debt over annual income at which the income score reaches zero.
+    pub max_debt_to_income: f64,
+    /// Debt over total assets at which the leverage score reaches zero.
+    pub max_leverage: f64,
+    /// Lowest score the bank lends to.
+    pub cutoff: f64,
+    /// Premium charged on a score of zero; a perfect score pays none.
+    pub max_risk_premium: f64,
+    /// Lowest ratio of net worth to total assets the bank will lend down to.
+    pub min_capital_ratio: f64,
+    /// How often commercial loans are refixed; `None` lends to firms at a fixed rate.
+    pub commercial_reset: Option<TimePeriod>,
+}
+
+impl Default for LendingModel {
+    fn default() -> Self {
+        Self {
+            income_weight: 0.4,
+            leverage_weight: 0.3,
+            history_weight: 0.3,
+            max_debt_to_income: 4.0,
+            max_leverage: 0.9,
+            cutoff: 0.5,
+            max_risk_premium: 0.08,
+            min_capital_ratio: 0.08,
+            commercial_reset: Some(TimePeriod::Monthly),
+        }
+    }
+}
+
+impl LendingModel {
+    /// Scores `borrower` for a new loan of `amount`. Each missed payment on record halves the
+    /// payment history score, and a default takes it to zero.
+    pub fn score(&self, borrower: AgentId, amount: f64, state: &SimState) -> CreditScore {
+        let fs = &state.financial_system;
+        let (debt, total_assets) =
+            fs.get_bs_by_id(&borrower).map_or((0.0, 0.0), |bs| (bs.total_debt(), bs.total_assets()));
+
+        let income = annual_income(borrower, state);
+        let income_score = if income > 1e-9 {
+            (1.0 - (debt + amount) / income / self.max_debt_to_income).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        let leverage = if total_assets > 1e-9 {
+            debt / total_assets
+        } else if debt > 1e-9 {
+            f64::MAX
+        } else {
+            0.0
+        };
+        let leverage_score = (1.0 - leverage / self.max_leverage).clamp(0.0, 1.0);
+
+        let record = fs.credit.events.iter().filter(|event| event.debtor == borrower);
+        let (missed, defaulted) = record.fold((0, false), |(missed, defaulted), event| match event.kind {
+            CreditEventKind::MissedPayment { .. } => (missed + 1, defaulted),
+            CreditEventKind::Default { .. } => (missed, true),
+            _ => (missed, defaulted),
+        });
+        let history_score = if defaulted { 0.0 } else { 0.5f64.powi(missed) };
+
+        let total_weight = self.income_weight + self.leverage_weight + self.history_weight;
+        let score = (self.income_weight * income_score
+            + self.leverage_weight * leverage_score
+            + self.history_weight * history_score)
+            / total_weight;
+        CreditScore { score, income: income_score, leverage: leverage_score, payment_history: history_score }
+    }
+
+    /// `bank`'s decision on `request`, made against `available_reserves`, the bank's reserves net of
+    /// the day's queued payments. `borrower_bank` is where the loan will be paid out.
+    pub fn decide(
+        &self, bank: &Bank, request: &LoanRequest, borrower_bank: Option<AgentId>, available_reserves: f64,
+        state: &SimState,
+    ) -> LoanDecision {
+        let fs = &state.financial_system;
+        let LoanRequest { borrower, amount, .. } = *request;
+        let score = self.score(borrower, amount, state);
+        let mut reasons = vec![];
+
+        let rating = fs.credit.rating_or_unrated(&borrower);
+        if !bank.lends_to(&rating) {
+            reasons.push(RejectionReason::RatedBelowMinimum { rating });
+        }
+        if score.score < self.cutoff {
+            reasons.push(RejectionReason::ScoreBelowCutoff { score: score.score, cutoff: self.cutoff });
+        }
+
+        // The loan adds the same to the bank's assets and deposits, leaving its net worth unchanged
+        let (net_worth, total_assets) =
+            fs.get_bs_by_id(&bank.id).map_or((0.0, 0.0), |bs| (bs.net_worth(), bs.total_assets()));
+        let capital_ratio = net_worth / (total_assets + amount);
+        if capital_ratio < self.min_capital_ratio {
+            reasons.push(RejectionReason::InsufficientCapital { capital_ratio, minimum: self.min_capital_ratio });
+        }
+
+        // The new deposit needs reserves held against it, and all of them if it is paid to another bank
+        let drawn = if borrower_bank == Some(bank.id) { amount * fs.central_bank.reserve_requirement } else { amount };
+        let excess_reserves = available_reserves - fs.required_reserves(&bank.id);
+        if excess_reserves < drawn {
+            reasons.push(RejectionReason::InsufficientLiquidity { excess_reserves, needed: drawn });
+        }
+
+        let outcome = if reasons.is_empty() {
+            let reference_rate = fs.risk_free_rate(&request.tenor);
+            let risk_premium = self.max_risk_premium * (1.0 - score.score);
+            LoanOutcome::Offered { rate: reference_rate + bank.lending_spread / 10_000.0 + risk_premium }
+        } else {
+            LoanOutcome::Rejected { reasons }
+        };
+        LoanDecision {
+            tick: state.ticknum,
+            date: state.current_date,
+            borrower,
+            bank: bank.id,
+            amount,
+            loan_type: request.loan_type.clone(),
+            score,
+            outcome,
+            accepted: false,
+        }
+    }
+}
+
+/// A consumer's income, or a firm's revenue over its last reported quarter annualized. A firm that has
+/// not reported yet is judged on the revenue of its current period.
+pub fn annual_income(borrower: AgentId, state: &SimState) -> f64 {
+    if let Some(consumer) = state.agents.consumers.get(&borrower) {
+        return consumer.income;
+    }
+    let reported = state.history.earnings_reports.iter().rev().find(|report| report.firm == borrower);
+    let quarterly = match reported {
+        Some(report) => report.revenues,
+        None => state.financial_system.get_bs_by_id(&borrower).map_or(0.0, |bs| bs.income_statement.revenue),
+    };
+    quarterly * 4.0
+}
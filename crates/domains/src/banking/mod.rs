@@ -19,23 +19,40 @@
 //! - **`behavior.rs`**: Contains the `BasicBankDecisionModel`, an implementation of the
 //!   `DecisionModel` trait. This model defines the "AI" for bank agents. In each simulation tick,
 //!   it decides which actions a bank should take, such as managing its reserves in the overnight
-//!   market or market-making for government bonds.
+//!   market, parking surplus reserves in Treasury bills or at the deposit facility, market-making
+//!   for government bonds, or bidding for new issues as a primary dealer in Treasury auctions.
+//!
+//! - **`lending.rs`**: Contains the `LendingModel` banks use to decide on a `RequestLoan`. The
+//!   borrower is scored from its income, leverage and payment history, and priced at the reference
+//!   rate plus the bank's lending spread plus a risk premium for its score. A bank short of capital
+//!   or reserves refuses however good the borrower. Every bank is asked, the borrower takes the
+//!   cheapest offer, and each bank's decision, with its reasons for refusing, is kept in the history.
+//!
+//! Banks reach the central bank's standing facilities through `BorrowFromFacility`, an overnight loan
+//! at the lending facility rate secured on their Treasuries, and `DepositAtFacility`, an overnight
+//! placement earning the deposit facility rate. The two rates bound the SOFR market's corridor. A bank
+//! short of reserves turns to the lending facility when nobody offered overnight funds the day before.
+//! Deposits pay the policy rate plus the bank's deposit spread, which is negative in practice.
 //!
 //! ## Key Components
 //!
 //! - **`BankingDomain`**: The public-facing service that processes all banking-related actions.
 //! - **`BasicBankDecisionModel`**: The default logic controller for bank agents.
+//! - **`LendingModel`**: Credit scoring, loan pricing and credit rationing.
 //! - **`BankingResult`**: A struct that wraps the output of an execution, containing the resulting
 //!   effects or a list of validation errors.
 pub mod behavior;
 pub mod domain;
+pub mod lending;
 
 pub use behavior::*;
 pub use domain::*;
+pub use lending::*;
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rand::SeedableRng;
     use sim_core::*;
     use uuid::Uuid;
     fn setup_banking_test_state() -> (SimState, AgentId, AgentId, AgentId, AgentId) {
@@ -55,6 +72,15 @@ mod tests {
         state.financial_system.create_instrument(reserves!(bank_id, cb_id, 500.0, state.current_date)).unwrap();
         (state, payer_id, recipient_id, bank_id, cb_id)
     }
+
+    fn hold_treasury(state: &mut SimState, bank_id: AgentId) {
+        let government_id = state.financial_system.government.id;
+        let date = state.current_date;
+        let maturity = date + chrono::Duration::days(365 * 2);
+        let treasury =
+            bond!(bank_id, government_id, 1000.0, 0.043, maturity, 1000.0, BondType::Government, 2, Tenor::T2Y, date);
+        state.financial_system.create_instrument(treasury).unwrap();
+    }
     #[test]
     fn test_transfer_uses_cash_when_sufficient() {
         let (mut state, payer_id, recipient_id, _, _) = setup_banking_test_state();
@@ -85,16 +111,41 @@ mod tests {
         let deposit = payer_bs.assets.values().find(|i| i.details.as_any().is::<DemandDepositDetails>()).unwrap();
         assert!((deposit.principal - 100.0).abs() < 1e-6, "Deposit should be 200 - 100");
         assert!(
-            (state.financial_system.get_bank_reserves(&bank_id).unwrap() - 400.0).abs() < 1e-6,
-            "Reserves should be 500 - 100"
+            (state.financial_system.get_bank_reserves(&bank_id).unwrap() - 500.0).abs() < 1e-6,
+            "Both parties bank at the same bank, so reserves should not move"
+        );
+        assert!((state.financial_system.get_cash_assets(&recipient_id) - 50.0).abs() < 1e-6);
+        assert!((state.financial_system.get_deposits_at_bank(&recipient_id, &bank_id) - 100.0).abs() < 1e-6);
+        assert!(
+            state.financial_system.get_total_liabilities(&cb_id) == 550.0,
+            "Central bank liabilities should be unchanged, has {}",
+            state.financial_system.get_total_liabilities(&cb_id)
         );
-        assert!((state.financial_system.get_cash_assets(&recipient_id) - transfer_amount).abs() < 1e-6);
-        assert!(state.financial_system.get_total_liabilities(&cb_id) == 550.0, "Central bank should have liabilities after transfer, has {}", state.financial_system.get_total_liabilities(&cb_id));
         println!("Final Consumer BS: {:?}", state.financial_system.get_bs_by_id(&payer_id));
         println!("Final Bank BS: {:?}", state.financial_system.get_bs_by_id(&bank_id));
         println!("Final Central Bank BS: {:?}", state.financial_system.get_bs_by_id(&cb_id));
     }
     #[test]
+    fn test_deposit_transfer_queues_interbank_obligation() {
+        let (mut state, payer_id, recipient_id, bank_id, cb_id) = setup_banking_test_state();
+        let other_bank_id = AgentId(Uuid::new_v4());
+        state.agents.banks.insert(other_bank_id, Bank::new("Other Bank".to_string(), 0.0, 0.0));
+        state.financial_system.balance_sheets.insert(other_bank_id, BalanceSheet::new(other_bank_id));
+        state.agents.consumers.get_mut(&recipient_id).unwrap().bank_id = other_bank_id;
+        let domain = BankingDomain::new();
+        let result = domain.execute_transfer(payer_id, recipient_id, 150.0, &state);
+        assert!(result.success);
+        state.apply_effects(&result.effects).unwrap();
+        assert!((state.financial_system.get_deposits_at_bank(&payer_id, &bank_id) - 100.0).abs() < 1e-6);
+        assert!((state.financial_system.get_deposits_at_bank(&recipient_id, &other_bank_id) - 100.0).abs() < 1e-6);
+        assert!((state.financial_system.get_bank_reserves(&bank_id).unwrap() - 500.0).abs() < 1e-6);
+        assert!((state.financial_system.get_total_liabilities(&cb_id) - 550.0).abs() < 1e-6);
+        let queue = &state.financial_system.payment_system.queue;
+        assert_eq!(queue.len(), 1);
+        assert_eq!((queue[0].from_bank, queue[0].to_bank), (bank_id, other_bank_id));
+        assert!((queue[0].amount - 100.0).abs() < 1e-6);
+    }
+    #[test]
     fn test_transfer_fails_when_all_funds_insufficient() {
         let (state, payer_id, recipient_id, _, _) = setup_banking_test_state();
         let domain = BankingDomain::new();
@@ -105,4 +156,228 @@ mod tests {
         assert!(execution_result.effects.is_empty(), "No effects should be generated on failure");
         assert!(execution_result.errors.iter().any(|e| e.contains("Insufficient liquid assets")));
     }
-}
\ No newline at end of file
+    #[test]
+    fn test_bank_pays_from_reserves_when_out_of_cash() {
+        let (mut state, _, recipient_id, bank_id, _) = setup_banking_test_state();
+        let other_bank_id = AgentId(Uuid::new_v4());
+        state.agents.banks.insert(other_bank_id, Bank::new("Other Bank".to_string(), 0.0, 0.0));
+        state.financial_system.balance_sheets.insert(other_bank_id, BalanceSheet::new(other_bank_id));
+        state.agents.consumers.get_mut(&recipient_id).unwrap().bank_id = other_bank_id;
+        let domain = BankingDomain::new();
+        let result = domain.execute_transfer(bank_id, recipient_id, 300.0, &state);
+        assert!(result.success, "{:?}", result.errors);
+        state.apply_effects(&result.effects).unwrap();
+        assert!((state.financial_system.get_deposits_at_bank(&recipient_id, &other_bank_id) - 300.0).abs() < 1e-6);
+        let queue = &state.financial_system.payment_system.queue;
+        assert_eq!((queue[0].from_bank, queue[0].to_bank), (bank_id, other_bank_id));
+        assert!((domain.available_funds(bank_id, &state) - 200.0).abs() < 1e-6);
+    }
+    #[test]
+    fn test_transfer_records_ledger_entry() {
+        let (mut state, payer_id, recipient_id, _, _) = setup_banking_test_state();
+        let domain = BankingDomain::new();
+        let result = domain.execute_transfer(payer_id, recipient_id, 40.0, &state);
+        state.apply_effects(&result.effects).unwrap();
+
+        let page = state.history.ledger_page(&recipient_id, 0, 10);
+        assert_eq!(page.total, 1);
+        let tx = &page.transactions[0];
+        assert_eq!((tx.from, tx.to, tx.qty), (payer_id, recipient_id, 40.0));
+        assert!(matches!(tx.tx_type, TransactionType::Transfer { .. }));
+        assert!(tx.instrument_id.is_some());
+    }
+    #[test]
+    fn test_facility_lending_is_limited_by_treasury_collateral() {
+        let (mut state, _, _, bank_id, _) = setup_banking_test_state();
+        let domain = BankingDomain::new();
+        let borrow = |amount| BankingAction::BorrowFromFacility { bank: bank_id, amount };
+        assert!(!domain.execute(&borrow(100.0), &state).success, "No Treasuries to pledge");
+
+        hold_treasury(&mut state, bank_id);
+        let capacity = state.financial_system.facility_borrowing_capacity(&bank_id, state.current_date);
+        assert!(capacity > 900.0 && capacity < 1000.0, "Haircut applies, capacity {}", capacity);
+        assert!(!domain.execute(&borrow(capacity * 1.1), &state).success);
+
+        let result = domain.execute(&borrow(capacity / 2.0), &state);
+        assert!(result.success, "{:?}", result.errors);
+        state.apply_effects(&result.effects).unwrap();
+        let liabilities = &state.financial_system.get_bs_by_id(&bank_id).unwrap().liabilities;
+        let loan = liabilities.values().find(|inst| inst.details.as_any().is::<LoanDetails>()).unwrap();
+        let details = loan.details.as_any().downcast_ref::<LoanDetails>().unwrap();
+        assert_eq!(details.interest_rate, state.financial_system.central_bank.lending_facility_rate());
+        let tx = state.history.transactions.last().unwrap();
+        assert!(matches!(tx.tx_type, TransactionType::FacilityLoan { bank, .. } if bank == bank_id));
+        assert_eq!(tx.instrument_id, Some(loan.id));
+        assert!((details.collateral.as_ref().unwrap().value - capacity / 2.0).abs() < 1e-9);
+        let remaining = state.financial_system.facility_borrowing_capacity(&bank_id, state.current_date);
+        assert!((remaining - capacity / 2.0).abs() < 1e-6, "Pledged collateral cannot be reused");
+        assert!((domain.available_funds(bank_id, &state) - (500.0 + capacity / 2.0)).abs() < 1e-6);
+    }
+    #[test]
+    fn test_deposit_facility_takes_reserves_overnight() {
+        let (mut state, _, _, bank_id, cb_id) = setup_banking_test_state();
+        let domain = BankingDomain::new();
+        assert!(!domain.execute(&BankingAction::DepositAtFacility { bank: bank_id, amount: 600.0 }, &state).success);
+
+        let result = domain.execute(&BankingAction::DepositAtFacility { bank: bank_id, amount: 400.0 }, &state);
+        assert!(result.success, "{:?}", result.errors);
+        state.apply_effects(&result.effects).unwrap();
+        let queue = &state.financial_system.payment_system.queue;
+        assert_eq!((queue[0].from_bank, queue[0].to_bank, queue[0].amount), (bank_id, cb_id, 400.0));
+        let cb_liabilities = &state.financial_system.get_bs_by_id(&cb_id).unwrap().liabilities;
+        let placement = cb_liabilities.values().find(|inst| inst.details.as_any().is::<LoanDetails>()).unwrap();
+        let details = placement.details.as_any().downcast_ref::<LoanDetails>().unwrap();
+        assert_eq!(placement.creditor, bank_id);
+        assert_eq!(details.loan_type, LoanType::DepositFacility);
+        assert_eq!(details.interest_rate, state.financial_system.central_bank.deposit_facility_rate());
+        assert!((domain.available_funds(bank_id, &state) - 100.0).abs() < 1e-6);
+        let tx = state.history.transactions.last().unwrap();
+        assert!(matches!(tx.tx_type, TransactionType::FacilityDeposit { bank, .. } if bank == bank_id));
+    }
+
+    #[test]
+    fn test_loan_request_takes_the_cheapest_offer() {
+        let (mut state, payer_id, _, bank_id, cb_id) = setup_banking_test_state();
+        let cheap_bank_id = AgentId(Uuid::new_v4());
+        state.agents.banks.insert(bank_id, Bank { id: bank_id, ..Bank::new("Test Bank".to_string(), 300.0, 0.0) });
+        let cheap_bank = Bank { id: cheap_bank_id, ..Bank::new("Cheap Bank".to_string(), 150.0, 0.0) };
+        state.agents.banks.insert(cheap_bank_id, cheap_bank);
+        state.financial_system.balance_sheets.insert(cheap_bank_id, BalanceSheet::new(cheap_bank_id));
+        state.financial_system.create_instrument(reserves!(cheap_bank_id, cb_id, 500.0, state.current_date)).unwrap();
+        state.agents.consumers.get_mut(&payer_id).unwrap().income = 52_000.0;
+
+        let domain = BankingDomain::new();
+        let request =
+            LoanRequest { borrower: payer_id, amount: 100.0, loan_type: LoanType::Personal, tenor: Tenor::T52W };
+        let score = LendingModel::default().score(payer_id, 100.0, &state);
+        let result = domain.execute(&BankingAction::RequestLoan { request }, &state);
+        assert!(result.success, "{:?}", result.errors);
+        state.apply_effects(&result.effects).unwrap();
+
+        let decisions = state.history.loan_decisions_for(&payer_id).collect::<Vec<_>>();
+        assert_eq!(decisions.len(), 2);
+        let accepted: Vec<_> = decisions.iter().filter(|decision| decision.accepted).collect();
+        assert_eq!(accepted.len(), 1);
+        assert_eq!(accepted[0].bank, cheap_bank_id);
+        let expected_rate = state.financial_system.risk_free_rate(&Tenor::T52W)
+            + 0.015
+            + LendingModel::default().max_risk_premium * (1.0 - score.score);
+        assert!((accepted[0].offered_rate().unwrap() - expected_rate).abs() < 1e-12);
+
+        let loan = state.financial_system.get_bs_by_id(&payer_id).unwrap().liabilities.values().next().unwrap();
+        assert_eq!((loan.creditor, loan.principal), (cheap_bank_id, 100.0));
+        assert!(!loan.payment_schedule.is_empty(), "Term loans pay interest monthly");
+        assert!((state.financial_system.get_deposits_at_bank(&payer_id, &bank_id) - 300.0).abs() < 1e-6);
+        let queue = &state.financial_system.payment_system.queue;
+        assert_eq!((queue[0].from_bank, queue[0].to_bank, queue[0].amount), (cheap_bank_id, bank_id, 100.0));
+    }
+
+    #[test]
+    fn test_loan_request_is_rationed_by_capital_and_reserves() {
+        let (mut state, payer_id, _, bank_id, _) = setup_banking_test_state();
+        state.agents.banks.insert(bank_id, Bank { id: bank_id, ..Bank::new("Test Bank".to_string(), 200.0, 0.0) });
+        state.agents.consumers.get_mut(&payer_id).unwrap().income = 52_000.0;
+        let domain = BankingDomain::new();
+        let request =
+            LoanRequest { borrower: payer_id, amount: 10_000.0, loan_type: LoanType::Personal, tenor: Tenor::T52W };
+        let result = domain.execute(&BankingAction::RequestLoan { request }, &state);
+        assert!(!result.success);
+        assert!(result.errors.iter().any(|e| e.contains("capital ratio")), "{:?}", result.errors);
+        assert!(result.errors.iter().any(|e| e.contains("excess reserves")), "{:?}", result.errors);
+        state.apply_effects(&result.effects).unwrap();
+
+        let decisions = state.history.loan_decisions_for(&payer_id).collect::<Vec<_>>();
+        assert_eq!(decisions.len(), 1, "Refusals are recorded too");
+        let LoanOutcome::Rejected { reasons } = &decisions[0].outcome else { panic!("Loan should be refused") };
+        assert!(matches!(
+            reasons[..],
+            [RejectionReason::InsufficientCapital { .. }, RejectionReason::InsufficientLiquidity { .. }]
+        ));
+        assert!(decisions[0].score.score >= LendingModel::default().cutoff, "A good borrower is still rationed");
+        assert!(state.financial_system.get_bs_by_id(&payer_id).unwrap().liabilities.is_empty());
+    }
+
+    #[test]
+    fn test_deposits_pay_the_policy_rate_plus_the_bank_spread() {
+        let (mut state, _, recipient_id, bank_id, cb_id) = setup_banking_test_state();
+        state.agents.banks.insert(bank_id, Bank { id: bank_id, ..Bank::new("Test Bank".to_string(), 200.0, -70.0) });
+        state.financial_system.create_instrument(cash!(recipient_id, 50.0, cb_id, state.current_date)).unwrap();
+        let result = BankingDomain::new().execute_deposit(recipient_id, bank_id, 50.0, &state);
+        assert!(result.success, "{:?}", result.errors);
+        state.apply_effects(&result.effects).unwrap();
+
+        let bs = state.financial_system.get_bs_by_id(&recipient_id).unwrap();
+        let deposit = bs.assets.values().find_map(|inst| inst.details.as_any().downcast_ref::<DemandDepositDetails>());
+        let policy_rate = state.financial_system.central_bank.policy_rate;
+        assert!((deposit.unwrap().interest_rate - (policy_rate - 0.007)).abs() < 1e-12);
+        assert_eq!(state.agents.banks[&bank_id].deposit_rate(0.005), 0.0, "Deposit rates stop at zero");
+    }
+
+    #[test]
+    fn test_commercial_loans_float_over_the_policy_rate() {
+        let (mut state, _, _, bank_id, _) = setup_banking_test_state();
+        state.agents.banks.insert(bank_id, Bank { id: bank_id, ..Bank::new("Test Bank".to_string(), 300.0, 0.0) });
+        let firm = Firm::new(bank_id, "Borrower".to_string(), None, 25.0);
+        let firm_id = firm.id;
+        state.agents.firms.insert(firm_id, firm);
+        let mut bs = BalanceSheet::new(firm_id);
+        bs.income_statement.record(IncomeLine::Revenue, 10_000.0);
+        state.financial_system.balance_sheets.insert(firm_id, bs);
+
+        let request =
+            LoanRequest { borrower: firm_id, amount: 100.0, loan_type: LoanType::Commercial, tenor: Tenor::T52W };
+        let result = BankingDomain::new().execute(&BankingAction::RequestLoan { request }, &state);
+        assert!(result.success, "{:?}", result.errors);
+        state.apply_effects(&result.effects).unwrap();
+
+        let rate = state.history.loan_decisions_for(&firm_id).next().unwrap().offered_rate().unwrap();
+        let loan = state.financial_system.get_bs_by_id(&firm_id).unwrap().liabilities.values().next().unwrap();
+        let floating = loan.floating_rate().expect("Commercial loans float");
+        assert_eq!(floating.index, RateIndex::PolicyRate);
+        assert!((floating.rate(state.financial_system.central_bank.policy_rate) - rate).abs() < 1e-12);
+        assert_eq!(floating.next_reset, TimePeriod::Monthly.add_to_date(state.current_date));
+        assert_eq!(loan.payment_schedule.len(), 12, "A floating loan pays its interest at each reset");
+    }
+
+    #[test]
+    fn test_short_bank_borrows_from_facility_when_nobody_lends_overnight() {
+        let (mut state, _, _, bank_id, _) = setup_banking_test_state();
+        hold_treasury(&mut state, bank_id);
+        let date = state.current_date;
+        let fs = &state.financial_system;
+        let last_night = fs.central_bank.lending_facility_loan(bank_id, 600.0, 600.0, date, &fs.calendar);
+        state.financial_system.create_instrument(last_night).unwrap();
+        let bank = Bank { id: bank_id, ..Bank::new("Test Bank".to_string(), 0.0, 0.0) };
+        let model = BasicBankDecisionModel;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+        let decide = |state: &SimState, rng: &mut rand::rngs::StdRng| model.decide(&bank, state, rng);
+
+        // Yesterday nobody offered overnight funds, so the bank refinances at the facility
+        let market_id = MarketId::Financial(FinancialMarketId::SecuredOvernightFinancing);
+        let tick = MarketTick { date: state.current_date, ..Default::default() };
+        state.history.market_ticks.entry(market_id.clone()).or_default().push_back(tick);
+        let borrowing: Vec<f64> = decide(&state, &mut rng)
+            .into_iter()
+            .filter_map(|action| match action {
+                SimAction::Banking(BankingAction::BorrowFromFacility { amount, .. }) => Some(amount),
+                _ => None,
+            })
+            .collect();
+        let target = state.financial_system.required_reserves(&bank_id)
+            + state.financial_system.reservable_liabilities(&bank_id) * 0.02;
+        assert_eq!(borrowing.len(), 1);
+        assert!((borrowing[0] - (100.0 + target)).abs() < 1e-6);
+
+        // With lenders in the market it bids for the reserves instead
+        let tick = MarketTick { date: state.current_date, best_ask: Some(0.9999), ..Default::default() };
+        state.history.market_ticks.get_mut(&market_id).unwrap().push_back(tick);
+        let actions = decide(&state, &mut rng);
+        let borrows =
+            |action: &SimAction| matches!(action, SimAction::Banking(BankingAction::BorrowFromFacility { .. }));
+        assert!(!actions.iter().any(borrows));
+        assert!(actions.iter().any(|action| matches!(
+            action,
+            SimAction::Trading(TradingAction::PostBid { market_id: bid_market, .. }) if *bid_market == market_id
+        )));
+    }
+}
@@ -0,0 +1,180 @@
+use serde::{Deserialize, Serialize};
+use sim_core::*;
+use sim_macros::SimDomain;
+use std::collections::{HashMap, HashSet};
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default, SimDomain)]
+pub struct ClearingDomain {}
+
+#[derive(Debug, Clone)]
+pub struct ClearingResult {
+    pub success: bool,
+    pub effects: Vec<StateEffect>,
+    pub errors: Vec<String>,
+}
+
+impl ClearingDomain {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    pub fn can_handle(&self, action: &ClearingAction) -> bool {
+        matches!(action, ClearingAction::SettleNetPositions)
+    }
+
+    pub fn execute(&self, action: &ClearingAction, state: &SimState) -> ClearingResult {
+        match action {
+            ClearingAction::SettleNetPositions => self.execute_settle_net_positions(state),
+        }
+    }
+
+    /// Nets the day's interbank obligations multilaterally and settles each bank's net position in
+    /// reserves. A bank whose reserves cannot cover its net debit is lent the difference overnight by
+    /// the central bank's lending facility against its Treasuries, which it has to refinance in the
+    /// SOFR market the next day. A bank that could not cover its net debit even then settles none of
+    /// its payments: they stay in the queue for the next day, and the rest is netted again without them.
+    fn execute_settle_net_positions(&self, state: &SimState) -> ClearingResult {
+        let fs = &state.financial_system;
+        let cb_id = fs.central_bank.id;
+        let (settled, deferred) = self.defer_unfunded_payments(state);
+        let queue = &settled.queue;
+        let net_positions = settled.net_positions();
+
+        let mut gross: HashMap<AgentId, (f64, f64)> = HashMap::new();
+        for obligation in queue {
+            gross.entry(obligation.from_bank).or_default().0 += obligation.amount;
+            gross.entry(obligation.to_bank).or_default().1 += obligation.amount;
+        }
+        let mut deferred_sent: HashMap<AgentId, f64> = HashMap::new();
+        for obligation in &deferred {
+            *deferred_sent.entry(obligation.from_bank).or_default() += obligation.amount;
+        }
+
+        let mut banks: Vec<AgentId> = state
+            .agents
+            .banks
+            .keys()
+            .chain(net_positions.keys())
+            .filter(|id| **id != cb_id)
+            .copied()
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        banks.sort_by_key(|id| id.0);
+
+        let mut effects = vec![];
+        let mut positions = vec![];
+        let mut total_borrowing = 0.0;
+        for bank_id in banks {
+            let net_position = net_positions.get(&bank_id).copied().unwrap_or(0.0);
+            let (gross_sent, gross_received) = gross.get(&bank_id).copied().unwrap_or((0.0, 0.0));
+            let reserves_before = fs.get_bank_reserves(&bank_id).unwrap_or(0.0);
+            let end_of_day = reserves_before + net_position;
+            let facility_borrowing = (-end_of_day).max(0.0);
+            let reserves_after = end_of_day.max(0.0);
+
+            if (reserves_after - reserves_before).abs() > 1e-9 {
+                effects.extend(self.set_reserves(bank_id, reserves_after, state));
+            }
+            if facility_borrowing > 1e-6 {
+                effects.extend(self.borrow_from_facility(bank_id, facility_borrowing, state));
+                total_borrowing += facility_borrowing;
+            }
+            positions.push(BankClearingPosition {
+                bank_id,
+                gross_sent,
+                gross_received,
+                net_position,
+                reserves_after,
+                required_reserves: fs.required_reserves(&bank_id),
+                facility_borrowing,
+                deferred: deferred_sent.get(&bank_id).copied().unwrap_or(0.0),
+            });
+        }
+
+        let report = ClearingReport {
+            tick: state.ticknum,
+            date: state.current_date,
+            obligations: queue.len(),
+            gross_flows: settled.gross_flows(),
+            net_flows: net_positions.values().filter(|p| **p > 0.0).fold(0.0, |total, p| total + p),
+            facility_borrowing: total_borrowing,
+            positions,
+        };
+        effects.push(StateEffect::Financial(FinancialEffect::ClearPaymentQueue));
+        effects.extend(
+            deferred
+                .into_iter()
+                .map(|obligation| StateEffect::Financial(FinancialEffect::QueueInterbankObligation(obligation))),
+        );
+        effects.push(StateEffect::Financial(FinancialEffect::RecordClearingReport(report)));
+
+        ClearingResult { success: true, effects, errors: vec![] }
+    }
+
+    fn set_reserves(&self, bank_id: AgentId, new_principal: f64, state: &SimState) -> Vec<StateEffect> {
+        let cb_id = state.financial_system.central_bank.id;
+        let reserves = state
+            .financial_system
+            .get_bs_by_id(&bank_id)
+            .and_then(|bs| bs.assets.iter().find(|(_, inst)| inst.details.as_any().is::<CentralBankReservesDetails>()));
+        match reserves {
+            Some((id, _)) if new_principal < 1e-6 => {
+                vec![StateEffect::Financial(FinancialEffect::RemoveInstrument(*id))]
+            }
+            Some((id, _)) => {
+                vec![StateEffect::Financial(FinancialEffect::UpdateInstrument { id: *id, new_principal })]
+            }
+            None if new_principal >= 1e-6 => vec![StateEffect::Financial(FinancialEffect::CreateInstrument(
+                reserves!(bank_id, cb_id, new_principal, state.current_date),
+            ))],
+            None => vec![],
+        }
+    }
+
+    /// Splits the queue into the payments that settle today and those deferred. Every payment of a bank
+    /// whose net debit is more than its reserves and facility borrowing capacity together is deferred,
+    /// and netting is repeated until every remaining bank can settle, since a deferred payment is one
+    /// less receipt for its payee.
+    fn defer_unfunded_payments(&self, state: &SimState) -> (PaymentSystem, Vec<InterbankObligation>) {
+        let fs = &state.financial_system;
+        let cb_id = fs.central_bank.id;
+        let mut settled = fs.payment_system.clone();
+        let mut deferred = vec![];
+        loop {
+            let short: HashSet<AgentId> = settled
+                .net_positions()
+                .into_iter()
+                .filter(|(bank_id, net_position)| {
+                    let reserves = fs.get_bank_reserves(bank_id).unwrap_or(0.0);
+                    let capacity = fs.facility_borrowing_capacity(bank_id, state.current_date);
+                    *bank_id != cb_id && reserves + capacity + net_position < -1e-6
+                })
+                .map(|(bank_id, _)| bank_id)
+                .collect();
+            if short.is_empty() {
+                return (settled, deferred);
+            }
+            let (unfunded, funded): (Vec<_>, Vec<_>) =
+                settled.queue.into_iter().partition(|obligation| short.contains(&obligation.from_bank));
+            settled.queue = funded;
+            deferred.extend(unfunded);
+        }
+    }
+
+    /// Lends `bank_id` its shortfall overnight against its Treasuries.
+    fn borrow_from_facility(&self, bank_id: AgentId, amount: f64, state: &SimState) -> Vec<StateEffect> {
+        let fs = &state.financial_system;
+        let loan = fs.central_bank.lending_facility_loan(bank_id, amount, amount, state.current_date, &fs.calendar);
+        let cb_id = fs.central_bank.id;
+        let record = StateEffect::Financial(FinancialEffect::RecordTransaction(Transaction::new(
+            state.ticknum,
+            cb_id,
+            bank_id,
+            amount,
+            TransactionType::FacilityLoan { bank: bank_id, amount },
+            Some(loan.id),
+        )));
+        vec![StateEffect::Financial(FinancialEffect::CreateInstrument(loan)), record]
+    }
+}
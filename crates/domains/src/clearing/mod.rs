@@ -0,0 +1,147 @@
+//! # Clearing Domain Crate
+//!
+//! This crate implements the interbank payment system's end-of-day settlement. Whenever bank
+//! money moves between two banks during a tick, the banking domain queues an
+//! `InterbankObligation` rather than moving reserves immediately. The clearing domain settles the
+//! whole queue at once.
+//!
+//! ## Crate Structure and Purpose
+//!
+//! Like settlement, clearing has no `behavior.rs`: the simulation engine issues
+//! `ClearingAction::SettleNetPositions` once per tick, after markets have cleared and trades have
+//! settled.
+//!
+//! - **`domain.rs`**: Contains the `ClearingDomain` struct. Settling the queue:
+//!   - nets all obligations multilaterally into one position per bank,
+//!   - moves each bank's net position in central bank reserves,
+//!   - lends any bank still short of reserves the difference through the central bank's overnight
+//!     lending facility, secured on its Treasuries after a haircut, which the bank repays (and
+//!     refinances in the SOFR market) the next day,
+//!   - leaves every payment of a bank that could not cover its net debit even with the facility in
+//!     the queue for the next day, netting the rest again without them,
+//!   - records a `ClearingReport` of gross against net flows in the simulation history.
+//!
+//! ## Key Components
+//!
+//! - **`ClearingDomain`**: The handler for end-of-day net settlement.
+//! - **`ClearingResult`**: A struct wrapping the outcome, containing effects or errors.
+pub mod domain;
+
+pub use domain::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sim_core::*;
+    use uuid::Uuid;
+
+    fn setup_clearing_state(reserves: &[f64]) -> (SimState, Vec<AgentId>) {
+        let mut state = SimState::default();
+        let cb_id = state.financial_system.central_bank.id;
+        let mut banks = vec![];
+        for (i, amount) in reserves.iter().enumerate() {
+            let bank_id = AgentId(Uuid::new_v4());
+            state.agents.banks.insert(bank_id, Bank::new(format!("Bank {}", i), 0.0, 0.0));
+            state.financial_system.balance_sheets.insert(bank_id, BalanceSheet::new(bank_id));
+            if *amount > 0.0 {
+                state
+                    .financial_system
+                    .create_instrument(reserves!(bank_id, cb_id, *amount, state.current_date))
+                    .unwrap();
+            }
+            banks.push(bank_id);
+        }
+        (state, banks)
+    }
+
+    fn queue(state: &mut SimState, from_bank: AgentId, to_bank: AgentId, amount: f64) {
+        let date = state.current_date;
+        state.financial_system.payment_system.queue.push(InterbankObligation { from_bank, to_bank, amount, date });
+    }
+
+    #[test]
+    fn test_multilateral_netting_settles_only_net_positions() {
+        let (mut state, banks) = setup_clearing_state(&[100.0, 100.0, 100.0]);
+        queue(&mut state, banks[0], banks[1], 500.0);
+        queue(&mut state, banks[1], banks[2], 500.0);
+        queue(&mut state, banks[2], banks[0], 450.0);
+
+        let result = ClearingDomain::new().execute(&ClearingAction::SettleNetPositions, &state);
+        assert!(result.success);
+        state.apply_effects(&result.effects).unwrap();
+
+        let fs = &state.financial_system;
+        assert!((fs.get_bank_reserves(&banks[0]).unwrap() - 50.0).abs() < 1e-6);
+        assert!((fs.get_bank_reserves(&banks[1]).unwrap() - 100.0).abs() < 1e-6);
+        assert!((fs.get_bank_reserves(&banks[2]).unwrap() - 150.0).abs() < 1e-6);
+        assert!(fs.payment_system.queue.is_empty());
+
+        let report = state.history.clearing_reports.last().unwrap();
+        assert_eq!(report.obligations, 3);
+        assert!((report.gross_flows - 1450.0).abs() < 1e-6);
+        assert!((report.net_flows - 50.0).abs() < 1e-6);
+        assert_eq!(report.facility_borrowing, 0.0);
+    }
+
+    fn hold_treasury(state: &mut SimState, bank_id: AgentId) {
+        let government_id = state.financial_system.government.id;
+        let date = state.current_date;
+        let maturity = date + chrono::Duration::days(365 * 2);
+        let treasury =
+            bond!(bank_id, government_id, 1000.0, 0.04, maturity, 1000.0, BondType::Government, 2, Tenor::T2Y, date);
+        state.financial_system.create_instrument(treasury).unwrap();
+    }
+
+    #[test]
+    fn test_short_bank_borrows_from_lending_facility() {
+        let (mut state, banks) = setup_clearing_state(&[100.0, 0.0]);
+        let cb_id = state.financial_system.central_bank.id;
+        hold_treasury(&mut state, banks[0]);
+        queue(&mut state, banks[0], banks[1], 250.0);
+
+        let result = ClearingDomain::new().execute(&ClearingAction::SettleNetPositions, &state);
+        state.apply_effects(&result.effects).unwrap();
+
+        let fs = &state.financial_system;
+        assert_eq!(fs.get_bank_reserves(&banks[0]).unwrap(), 0.0);
+        assert!((fs.get_bank_reserves(&banks[1]).unwrap() - 250.0).abs() < 1e-6);
+        let loan = fs.get_bs_by_id(&banks[0]).unwrap().liabilities.values().next().unwrap();
+        let details = loan.details.as_any().downcast_ref::<LoanDetails>().unwrap();
+        assert_eq!(loan.creditor, cb_id);
+        assert!((loan.principal - 150.0).abs() < 1e-6);
+        assert_eq!(details.loan_type, LoanType::StandingFacility);
+        assert!(state.history.transactions.iter().any(|tx| {
+            matches!(tx.tx_type, TransactionType::FacilityLoan { bank, .. } if bank == banks[0])
+                && tx.instrument_id == Some(loan.id)
+        }));
+        let collateral = details.collateral.as_ref().unwrap();
+        assert!((collateral.value - 150.0).abs() < 1e-6, "The loan is secured on the bank's Treasury");
+        assert!((state.history.clearing_reports[0].facility_borrowing - 150.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_payments_a_bank_cannot_secure_wait_in_the_queue() {
+        let (mut state, banks) = setup_clearing_state(&[100.0, 0.0, 50.0]);
+        queue(&mut state, banks[0], banks[1], 250.0);
+        queue(&mut state, banks[1], banks[2], 200.0);
+        queue(&mut state, banks[2], banks[0], 20.0);
+
+        let result = ClearingDomain::new().execute(&ClearingAction::SettleNetPositions, &state);
+        state.apply_effects(&result.effects).unwrap();
+
+        // Without Treasuries the first bank cannot cover its payment, which leaves the second bank short
+        // too once it goes unpaid; only the third bank's payment settles
+        let fs = &state.financial_system;
+        assert!((fs.get_bank_reserves(&banks[0]).unwrap() - 120.0).abs() < 1e-6);
+        assert_eq!(fs.get_bank_reserves(&banks[1]).unwrap_or(0.0), 0.0);
+        assert!((fs.get_bank_reserves(&banks[2]).unwrap() - 30.0).abs() < 1e-6);
+        assert!(fs.get_bs_by_id(&banks[0]).unwrap().liabilities.is_empty(), "Nothing is lent unsecured");
+        let waiting: Vec<_> = fs.payment_system.queue.iter().map(|o| (o.from_bank, o.amount)).collect();
+        assert_eq!(waiting, vec![(banks[0], 250.0), (banks[1], 200.0)]);
+
+        let report = &state.history.clearing_reports[0];
+        assert_eq!((report.obligations, report.facility_borrowing), (1, 0.0));
+        let deferred = |bank| report.positions.iter().find(|p| p.bank_id == bank).unwrap().deferred;
+        assert_eq!((deferred(banks[0]), deferred(banks[1]), deferred(banks[2])), (250.0, 200.0, 0.0));
+    }
+}
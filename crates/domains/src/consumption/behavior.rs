@@ -85,6 +85,7 @@ impl DecisionModel for CESConsumerDecisionModel {
         let mut actions = Vec::new();
 
         self.handle_employment(consumer, state, &mut actions);
+        self.handle_borrowing(consumer, state, &mut actions);
 
         let nominal_rate = state.financial_system.central_bank.policy_rate;
         let expected_inflation = consumer.expectations.expected_inflation;
@@ -164,6 +165,22 @@ impl CESConsumerDecisionModel {
         }
     }
 
+    /// A consumer in work whose liquid assets fall below a week's income asks the banks for a year's
+    /// personal loan of a month's income, at most once a month.
+    fn handle_borrowing(&self, consumer: &Consumer, state: &SimState, actions: &mut Vec<SimAction>) {
+        let weekly_income = consumer.income / 52.0;
+        let since = state.current_date - chrono::Duration::days(30);
+        if consumer.income <= 0.0
+            || state.financial_system.get_liquid_assets(&consumer.id) >= weekly_income
+            || state.history.requested_loan_since(&consumer.id, since)
+        {
+            return;
+        }
+        let amount = consumer.income / 12.0;
+        let request = LoanRequest { borrower: consumer.id, amount, loan_type: LoanType::Personal, tenor: Tenor::T52W };
+        actions.push(SimAction::Banking(BankingAction::RequestLoan { request }));
+    }
+
     fn handle_employment(&self, consumer: &Consumer, _state: &SimState, actions: &mut Vec<SimAction>) {
         if consumer.employed_by.is_none() {
             let expected_hourly_wage = match consumer.personality {
@@ -209,7 +226,7 @@ impl DecisionModel for ParametricMPC {
         let weekly_income = consumer.income / 52.0;
         let liquid_assets = fs.get_liquid_assets(&consumer.id);
         let total = weekly_income + liquid_assets;
-        let wealth_ratio = fs.get_total_assets(&consumer.id) / consumer.income.max(1.0);
+        let wealth_ratio = state.household_wealth(&consumer.id) / consumer.income.max(1.0);
 
         let mpc = self.mpc_min
             + (self.mpc_max - self.mpc_min)
@@ -2,6 +2,7 @@ use serde::{Deserialize, Serialize};
 use sim_core::*;
 use sim_macros::SimDomain;
 use crate::banking::BankingDomain;
+use crate::fiscal::{Levy, consumption_tax_rate, pay_with_levy};
 
 #[derive(Clone, Debug, Serialize, Deserialize, SimDomain)]
 pub struct ConsumptionDomain {
@@ -70,7 +71,7 @@ impl ConsumptionDomain {
 
         let price =
             state.financial_system.exchange.goods_market(&good_id).and_then(|m| m.best_ask()).map_or(1.0, |ask| ask.price);
-        let total_cost = amount * price;
+        let total_cost = amount * price * (1.0 + consumption_tax_rate(buyer, state));
         let available_funds = state.financial_system.get_liquid_assets(&buyer);
         if available_funds < total_cost {
             return Err(format!("Buyer has insufficient funds: needs ${:.2}, has ${:.2}", total_cost, available_funds));
@@ -143,7 +144,10 @@ impl ConsumptionDomain {
 
         let total_cost = amount * price;
 
-        let payment_result = self.payment_router.execute_transfer(buyer, seller, total_cost, state);
+        let levy = Levy::on_purchase(buyer, total_cost, state);
+        let tx_type = TransactionType::Transfer { from: buyer, to: seller, amount: total_cost };
+        let payment = (seller, total_cost, tx_type);
+        let payment_result = pay_with_levy(&self.payment_router, buyer, vec![payment], None, levy.as_slice(), state);
 
         if !payment_result.success {
             return ConsumptionResult {
@@ -182,7 +186,8 @@ impl ConsumptionDomain {
             None => return ConsumptionResult { success: true, effects: vec![], errors: vec![] }, // Market doesn't exist
         };
 
-        let mut remaining_notional = max_notional;
+        // The notional covers the consumption tax due on settlement as well as the goods
+        let mut remaining_notional = max_notional / (1.0 + consumption_tax_rate(buyer, state));
         let mut effects = vec![];
 
         let mut asks = market.order_book.asks.clone();
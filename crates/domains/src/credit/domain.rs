@@ -0,0 +1,279 @@
+use serde::{Deserialize, Serialize};
+use sim_core::*;
+use sim_macros::SimDomain;
+use std::collections::HashSet;
+use crate::banking::BankingDomain;
+use crate::credit::RatingAgency;
+
+#[derive(Clone, Debug, Serialize, Deserialize, SimDomain)]
+pub struct CreditDomain {
+    agency: RatingAgency,
+    payment_router: BankingDomain,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreditResult {
+    pub success: bool,
+    pub effects: Vec<StateEffect>,
+    pub errors: Vec<String>,
+}
+
+/// One creditor's claim in a default, with what it recovers.
+#[derive(Debug, Clone)]
+pub struct DefaultClaim {
+    pub instrument_id: InstrumentId,
+    pub creditor: AgentId,
+    pub exposure: f64,
+    pub secured: f64,
+    /// Whether the secured part is settled by handing over the debtor's Treasuries rather than in funds.
+    pub treasury_collateral: bool,
+    pub recovered: f64,
+}
+
+impl Default for CreditDomain {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CreditDomain {
+    pub fn new() -> Self {
+        Self { agency: RatingAgency::default(), payment_router: BankingDomain::new() }
+    }
+
+    pub fn can_handle(&self, action: &CreditAction) -> bool {
+        matches!(action, CreditAction::ReviewDelinquencies | CreditAction::RateBorrowers)
+    }
+
+    pub fn execute(&self, action: &CreditAction, state: &SimState) -> CreditResult {
+        match action {
+            CreditAction::ReviewDelinquencies => self.execute_review_delinquencies(state),
+            CreditAction::RateBorrowers => self.execute_rate_borrowers(state),
+        }
+    }
+
+    /// Has the rating agency review every firm. Each review is recorded in the registry's rating
+    /// history; a changed rating also replaces the current one and is recorded as a `RatingChange`.
+    /// Firms in default stay rated D.
+    fn execute_rate_borrowers(&self, state: &SimState) -> CreditResult {
+        let credit = &state.financial_system.credit;
+        let mut firms: Vec<AgentId> = state.agents.firms.keys().copied().collect();
+        firms.sort_by_key(|id| id.0);
+
+        let mut effects = vec![];
+        for firm_id in firms {
+            let previous = credit.rating(&firm_id).cloned();
+            if previous == Some(CreditRating::D) {
+                continue;
+            }
+            let Some(assessment) = self.agency.assess(firm_id, state) else {
+                continue;
+            };
+            if previous.as_ref() != Some(&assessment.rating) {
+                effects.push(StateEffect::Credit(CreditEffect::SetRating {
+                    agent_id: firm_id,
+                    rating: assessment.rating.clone(),
+                }));
+                effects.push(StateEffect::Credit(CreditEffect::RecordEvent(CreditEvent::new(
+                    state.ticknum,
+                    state.current_date,
+                    firm_id,
+                    None,
+                    CreditEventKind::RatingChange { from: previous, to: assessment.rating.clone() },
+                ))));
+            }
+            effects.push(StateEffect::Credit(CreditEffect::RecordAssessment(assessment)));
+        }
+        CreditResult { success: true, effects, errors: vec![] }
+    }
+
+    /// Defaults every debtor holding an instrument that is still delinquent after the grace period.
+    fn execute_review_delinquencies(&self, state: &SimState) -> CreditResult {
+        let fs = &state.financial_system;
+        let mut debtors: Vec<AgentId> = fs
+            .credit
+            .past_grace_period(state.current_date)
+            .iter()
+            .filter_map(|id| fs.instruments.get(id).map(|inst| inst.debtor))
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        debtors.sort_by_key(|id| id.0);
+
+        let effects = debtors.into_iter().flat_map(|debtor| self.default_debtor(debtor, state)).collect();
+        CreditResult { success: true, effects, errors: vec![] }
+    }
+
+    /// Works out what each of a defaulting debtor's creditors recovers. Collateralised claims are repaid
+    /// from their collateral first: pledged Treasuries out of the debtor's holdings, anything else out
+    /// of its funds. Everything else shares the debtor's remaining funds pro rata. Assets the debtor
+    /// cannot pay with, such as inventory or securities it has not pledged, are not realised.
+    pub fn recovery_claims(&self, debtor: AgentId, state: &SimState) -> Vec<DefaultClaim> {
+        let fs = &state.financial_system;
+        let Some(bs) = fs.get_bs_by_id(&debtor) else {
+            return vec![];
+        };
+
+        let mut claims: Vec<DefaultClaim> = bs
+            .liabilities
+            .iter()
+            .filter(|(id, inst)| {
+                !fs.credit.is_defaulted(id)
+                    && !inst.details.as_any().is::<CashDetails>()
+                    && !inst.details.as_any().is::<CentralBankReservesDetails>()
+                    && !inst.details.as_any().is::<EquityDetails>()
+            })
+            .map(|(id, inst)| {
+                let exposure = inst.principal + inst.accrued_interest;
+                let collateral =
+                    inst.details.as_any().downcast_ref::<LoanDetails>().and_then(|loan| loan.collateral.as_ref());
+                DefaultClaim {
+                    instrument_id: *id,
+                    creditor: inst.creditor,
+                    exposure,
+                    secured: collateral.map_or(0.0, |collateral| collateral.value.min(exposure)),
+                    treasury_collateral: collateral
+                        .is_some_and(|collateral| collateral.collateral_type == CollateralType::Treasury),
+                    recovered: 0.0,
+                }
+            })
+            .collect();
+        claims.sort_by_key(|claim| claim.instrument_id.0);
+
+        let holdings = treasury_holdings(debtor, state);
+        let mut treasuries: f64 = holdings.iter().map(|(_, units, price)| *units as f64 * price).sum();
+        let mut funds = self.payment_router.available_funds(debtor, state);
+        for claim in &mut claims {
+            let pool = if claim.treasury_collateral { &mut treasuries } else { &mut funds };
+            claim.secured = claim.secured.min(*pool);
+            *pool -= claim.secured;
+        }
+        let unsecured_total: f64 = claims.iter().map(|c| c.exposure - c.secured).sum();
+        let share = if unsecured_total > 1e-9 { (funds / unsecured_total).min(1.0) } else { 1.0 };
+        for claim in &mut claims {
+            claim.recovered = claim.secured + share * (claim.exposure - claim.secured);
+        }
+        claims
+    }
+
+    /// Puts `debtor` into default and settles its claims: each creditor is paid what it recovers, and
+    /// the claim is then closed. If the recoveries cannot be paid the claims stay on the books, written
+    /// down to their recovery value.
+    fn default_debtor(&self, debtor: AgentId, state: &SimState) -> Vec<StateEffect> {
+        let mut effects = vec![];
+        let event = |instrument_id: Option<InstrumentId>, kind: CreditEventKind| {
+            StateEffect::Credit(CreditEffect::RecordEvent(CreditEvent::new(
+                state.ticknum,
+                state.current_date,
+                debtor,
+                instrument_id,
+                kind,
+            )))
+        };
+
+        let claims = self.recovery_claims(debtor, state);
+        let mut holdings = treasury_holdings(debtor, state);
+        let mut payments = vec![];
+        for claim in &claims {
+            let written_off = claim.exposure - claim.recovered;
+            let recovery_rate = if claim.exposure > 1e-9 { claim.recovered / claim.exposure } else { 1.0 };
+            effects.push(event(Some(claim.instrument_id), CreditEventKind::Default { exposure: claim.exposure }));
+            effects.push(StateEffect::Credit(CreditEffect::MarkDefaulted {
+                instrument_id: claim.instrument_id,
+                date: state.current_date,
+                recovery_rate,
+            }));
+            effects.push(StateEffect::Credit(CreditEffect::WriteDown {
+                instrument_id: claim.instrument_id,
+                new_principal: claim.recovered,
+                loss: written_off,
+            }));
+            effects.push(event(
+                Some(claim.instrument_id),
+                CreditEventKind::Recovery {
+                    creditor: claim.creditor,
+                    exposure: claim.exposure,
+                    recovered: claim.recovered,
+                    written_off,
+                },
+            ));
+
+            let mut paid = claim.recovered;
+            if claim.treasury_collateral {
+                effects.extend(seize_treasuries(claim.creditor, claim.secured, &mut holdings));
+                paid -= claim.secured;
+            }
+            if paid > 1e-6 {
+                let tx_type = TransactionType::DefaultRecovery { debtor, creditor: claim.creditor, amount: paid };
+                payments.push((claim.creditor, paid, tx_type));
+            }
+        }
+
+        let settled = payments.is_empty() || {
+            let result = self.payment_router.execute_split_payment(debtor, &payments, None, state);
+            effects.extend(result.effects);
+            result.success
+        };
+        if settled {
+            // A claim written down to nothing has already been removed
+            for claim in claims.iter().filter(|claim| claim.recovered >= 1e-6) {
+                effects.push(StateEffect::Financial(FinancialEffect::RemoveInstrument(claim.instrument_id)));
+            }
+        }
+
+        let previous = state.financial_system.credit.rating(&debtor).cloned();
+        if previous != Some(CreditRating::D) {
+            effects.push(StateEffect::Credit(CreditEffect::SetRating { agent_id: debtor, rating: CreditRating::D }));
+            effects.push(event(None, CreditEventKind::RatingChange { from: previous, to: CreditRating::D }));
+        }
+        effects
+    }
+}
+
+/// `debtor`'s Treasuries as `(holding, units, price per unit)`, in a stable order. Holdings are valued
+/// at market, or at book value when the curve has no rate for them.
+fn treasury_holdings(debtor: AgentId, state: &SimState) -> Vec<(InstrumentId, u64, f64)> {
+    let fs = &state.financial_system;
+    let Some(bs) = fs.get_bs_by_id(&debtor) else {
+        return vec![];
+    };
+    let mut holdings: Vec<(InstrumentId, u64, f64)> = bs
+        .assets
+        .values()
+        .filter_map(|inst| {
+            let bond = inst.details.as_any().downcast_ref::<BondDetails>()?;
+            if bond.bond_type != BondType::Government || bond.quantity == 0 {
+                return None;
+            }
+            let value =
+                fs.bond_market_value(inst, state.current_date).filter(|v| v.is_finite()).unwrap_or(inst.principal);
+            Some((inst.id, bond.quantity, value / bond.quantity as f64))
+        })
+        .collect();
+    holdings.sort_by_key(|(id, _, _)| id.0);
+    holdings
+}
+
+/// Hands `creditor` enough of the debtor's Treasuries to cover `value`, taking whole bonds in order.
+fn seize_treasuries(creditor: AgentId, value: f64, holdings: &mut [(InstrumentId, u64, f64)]) -> Vec<StateEffect> {
+    let mut effects = vec![];
+    let mut remaining = value;
+    for (id, units, price) in holdings.iter_mut() {
+        if remaining < 1e-6 {
+            break;
+        }
+        if *units == 0 || *price <= 0.0 {
+            continue;
+        }
+        let taken = ((remaining / *price).ceil() as u64).min(*units);
+        let effect = if taken == *units {
+            FinancialEffect::TransferInstrument { id: *id, new_creditor: creditor }
+        } else {
+            FinancialEffect::SplitAndTransferInstrument { id: *id, buyer: creditor, quantity: taken }
+        };
+        effects.push(StateEffect::Financial(effect));
+        *units -= taken;
+        remaining -= taken as f64 * *price;
+    }
+    effects
+}
@@ -0,0 +1,238 @@
+//! # Credit Domain Crate
+//!
+//! This crate turns missed payments into credit events. It works off the `CreditRegistry` held in
+//! the `FinancialSystem`:
+//!
+//! - When the settlement domain cannot fund an interest or coupon payment, it marks the instrument
+//!   **delinquent**. A later successful payment cures it.
+//! - Once a day the engine issues `CreditAction::ReviewDelinquencies`. Any instrument still
+//!   delinquent after the registry's grace period puts its debtor into **default**.
+//!
+//! ## Default and Recovery
+//!
+//! A default covers every outstanding debt of the debtor; shareholders are not creditors and get
+//! no claim. Each claim recovers first from its
+//! `CollateralInfo`, if any; unsecured amounts share the debtor's remaining funds pro rata. Each
+//! creditor's instrument is written down to its recovery value and the loss is booked to the
+//! creditor's income statement as `credit_losses`. The recovery is then settled: pledged Treasuries
+//! are handed to the creditor, the rest is paid through the banking domain, and the claim is closed.
+//! The debtor is rated `CreditRating::D`, and every step is recorded as a `CreditEvent` that can be
+//! queried from the registry.
+//!
+//! ## Ratings
+//!
+//! At each month end the engine issues `CreditAction::RateBorrowers`, and the `RatingAgency` in
+//! `rating.rs` rates every firm from its leverage, interest coverage and liquidity. Every review is
+//! kept in the registry's `rating_history`, so decision models and the API can follow a firm's
+//! ratios over time. The current rating sets the spread on the firm's new bonds and the bucket its
+//! bonds trade in, and banks will not underwrite for firms rated below `Bank::MIN_LENDING_RATING`.
+//!
+//! ## Key Components
+//!
+//! - **`CreditDomain`**: The handler that reviews delinquencies, processes defaults and rates firms.
+//! - **`RatingAgency`**: The ratio thresholds and weights behind each rating.
+//! - **`DefaultClaim`**: A creditor's exposure in a default and the amount it recovers.
+//! - **`CreditResult`**: A struct wrapping the outcome, containing effects or errors.
+pub mod domain;
+pub mod rating;
+
+pub use domain::*;
+pub use rating::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sim_core::*;
+    use uuid::Uuid;
+
+    fn loan(
+        lender: AgentId, borrower: AgentId, principal: f64, collateral: Option<f64>, date: chrono::NaiveDate,
+    ) -> FinancialInstrument {
+        let maturity = date + chrono::Duration::days(365);
+        let mut loan = loan!(lender, borrower, principal, LoanType::Personal, 0.05, maturity, date);
+        if let Some(value) = collateral {
+            let details = loan.details.as_any_mut().downcast_mut::<LoanDetails>().unwrap();
+            details.collateral = Some(CollateralInfo { collateral_type: CollateralType::Vehicle, value });
+        }
+        loan
+    }
+
+    fn setup_default_state() -> (SimState, AgentId, AgentId, AgentId, InstrumentId, InstrumentId) {
+        let mut state = SimState::default();
+        let cb_id = state.financial_system.central_bank.id;
+        let debtor = AgentId(Uuid::new_v4());
+        let secured_lender = AgentId(Uuid::new_v4());
+        let unsecured_lender = AgentId(Uuid::new_v4());
+        for id in [debtor, secured_lender, unsecured_lender] {
+            state.financial_system.balance_sheets.insert(id, BalanceSheet::new(id));
+        }
+        let date = state.current_date;
+        state.financial_system.create_instrument(cash!(debtor, 550.0, cb_id, date)).unwrap();
+        let secured = loan(secured_lender, debtor, 500.0, Some(200.0), date);
+        let unsecured = loan(unsecured_lender, debtor, 400.0, None, date);
+        let (secured_id, unsecured_id) = (secured.id, unsecured.id);
+        state.financial_system.create_instrument(secured).unwrap();
+        state.financial_system.create_instrument(unsecured).unwrap();
+        (state, debtor, secured_lender, unsecured_lender, secured_id, unsecured_id)
+    }
+
+    #[test]
+    fn test_delinquency_defaults_after_grace_period() {
+        let (mut state, debtor, secured_lender, unsecured_lender, secured_id, unsecured_id) = setup_default_state();
+        let missed =
+            CreditEffect::RecordMissedPayment { instrument_id: unsecured_id, amount: 10.0, date: state.current_date };
+        state.apply_effect(&StateEffect::Credit(missed)).unwrap();
+        let domain = CreditDomain::new();
+
+        state.current_date += chrono::Duration::days(state.financial_system.credit.grace_period_days - 1);
+        let result = domain.execute(&CreditAction::ReviewDelinquencies, &state);
+        assert!(result.effects.is_empty(), "Still within the grace period");
+
+        state.current_date += chrono::Duration::days(1);
+        let result = domain.execute(&CreditAction::ReviewDelinquencies, &state);
+        state.apply_effects(&result.effects).unwrap();
+
+        let fs = &state.financial_system;
+        assert_eq!(fs.credit.rating(&debtor), Some(&CreditRating::D));
+        assert!(fs.credit.is_defaulted(&secured_id) && fs.credit.is_defaulted(&unsecured_id));
+        // The secured claim takes its 200 of collateral out of the 550 of funds; the remaining 350
+        // covers half of the 700 of unsecured claims. Both are paid and closed.
+        assert!(!fs.instruments.contains_key(&secured_id) && !fs.instruments.contains_key(&unsecured_id));
+        assert!((fs.get_cash_assets(&secured_lender) - 350.0).abs() < 1e-6);
+        assert!((fs.get_cash_assets(&unsecured_lender) - 200.0).abs() < 1e-6);
+        assert!(fs.get_cash_assets(&debtor).abs() < 1e-6);
+        let secured_losses = fs.get_bs_by_id(&secured_lender).unwrap().income_statement.credit_losses;
+        let unsecured_losses = fs.get_bs_by_id(&unsecured_lender).unwrap().income_statement.credit_losses;
+        assert!((secured_losses - 150.0).abs() < 1e-6);
+        assert!((unsecured_losses - 200.0).abs() < 1e-6);
+        assert_eq!(fs.credit.defaults().count(), 2);
+        assert!(fs.credit.events_for(&unsecured_lender).any(|e| matches!(e.kind, CreditEventKind::Recovery { .. })));
+    }
+
+    #[test]
+    fn test_cured_instrument_does_not_default() {
+        let (mut state, debtor, _, _, _, unsecured_id) = setup_default_state();
+        state
+            .apply_effects(&[
+                StateEffect::Credit(CreditEffect::RecordMissedPayment {
+                    instrument_id: unsecured_id,
+                    amount: 10.0,
+                    date: state.current_date,
+                }),
+                StateEffect::Credit(CreditEffect::Cure { instrument_id: unsecured_id }),
+            ])
+            .unwrap();
+        state.current_date += chrono::Duration::days(60);
+        let result = CreditDomain::new().execute(&CreditAction::ReviewDelinquencies, &state);
+        assert!(result.effects.is_empty());
+        assert_eq!(state.financial_system.credit.rating(&debtor), None);
+    }
+
+    #[test]
+    fn test_agency_rates_firms_from_ratios_and_records_history() {
+        let mut state = SimState::default();
+        let cb_id = state.financial_system.central_bank.id;
+        let date = state.current_date;
+        let lender = AgentId(Uuid::new_v4());
+        let levered = Firm::new(lender, "Levered".to_string(), None, 25.0);
+        let unlevered = Firm::new(lender, "Unlevered".to_string(), None, 25.0);
+        let (levered_id, unlevered_id) = (levered.id, unlevered.id);
+        state.agents.firms.insert(levered_id, levered);
+        state.agents.firms.insert(unlevered_id, unlevered);
+        for id in [lender, levered_id, unlevered_id] {
+            state.financial_system.balance_sheets.insert(id, BalanceSheet::new(id));
+        }
+        for id in [levered_id, unlevered_id] {
+            state.financial_system.create_instrument(cash!(id, 1_000.0, cb_id, date)).unwrap();
+        }
+        state.financial_system.create_instrument(loan(lender, levered_id, 800.0, None, date)).unwrap();
+        let statement = &mut state.financial_system.balance_sheets.get_mut(&levered_id).unwrap().income_statement;
+        statement.record(IncomeLine::Revenue, 50.0);
+        statement.record(IncomeLine::InterestExpense, 100.0);
+
+        let domain = CreditDomain::new();
+        let result = domain.execute(&CreditAction::RateBorrowers, &state);
+        state.apply_effects(&result.effects).unwrap();
+
+        // Leverage 0.8 rates CC, coverage 0.5 rates C and liquidity 1.25 rates AAA: CCC on the weights
+        let credit = &state.financial_system.credit;
+        assert_eq!(credit.rating(&levered_id), Some(&CreditRating::CCC));
+        assert_eq!(credit.rating(&unlevered_id), Some(&CreditRating::AAA));
+        let assessment = credit.latest_assessment(&levered_id).unwrap();
+        assert_eq!(assessment.leverage, 0.8);
+        assert_eq!(assessment.interest_coverage, Some(0.5));
+        assert_eq!(assessment.liquidity, Some(1.25));
+        assert!(matches!(
+            credit.events_for(&levered_id).next_back().map(|e| &e.kind),
+            Some(CreditEventKind::RatingChange { from: None, to: CreditRating::CCC })
+        ));
+
+        let result = domain.execute(&CreditAction::RateBorrowers, &state);
+        state.apply_effects(&result.effects).unwrap();
+        let credit = &state.financial_system.credit;
+        assert_eq!(credit.rating_history_for(&levered_id).count(), 2);
+        assert_eq!(credit.events_for(&levered_id).count(), 1, "An unchanged rating is not a rating change");
+
+        // Once a period closes the agency rates on the report rather than the statement that reset with it
+        state.history.earnings_reports.push(EarningsReport {
+            tick: state.ticknum,
+            date,
+            firm: levered_id,
+            revenues: 1_000.0,
+            costs: 100.0,
+            interest_expense: 100.0,
+            net_profit: 720.0,
+            dividends: 0.0,
+            shares_outstanding: 0,
+        });
+        let statement = &mut state.financial_system.balance_sheets.get_mut(&levered_id).unwrap().income_statement;
+        *statement = IncomeStatement::default();
+        let result = domain.execute(&CreditAction::RateBorrowers, &state);
+        state.apply_effects(&result.effects).unwrap();
+        // Coverage 10 rates AA, which with the same leverage and liquidity lifts the firm to BBB
+        let credit = &state.financial_system.credit;
+        assert_eq!(credit.latest_assessment(&levered_id).unwrap().interest_coverage, Some(10.0));
+        assert_eq!(credit.rating(&levered_id), Some(&CreditRating::BBB));
+    }
+
+    #[test]
+    fn test_facility_default_hands_pledged_treasuries_to_the_central_bank() {
+        let mut state = SimState::default();
+        let cb_id = state.financial_system.central_bank.id;
+        let bank = AgentId(Uuid::new_v4());
+        state.financial_system.balance_sheets.insert(bank, BalanceSheet::new(bank));
+        let date = state.current_date;
+        let government_id = state.financial_system.government.id;
+        let maturity = date + chrono::Duration::days(365 * 2);
+        let mut treasury =
+            bond!(bank, government_id, 10_000.0, 0.043, maturity, 1000.0, BondType::Government, 2, Tenor::T2Y, date);
+        treasury.set_quantity(10);
+        let treasury_id = treasury.id;
+        state.financial_system.create_instrument(treasury).unwrap();
+        let capacity = state.financial_system.facility_borrowing_capacity(&bank, date);
+        let fs = &state.financial_system;
+        let loan = fs.central_bank.lending_facility_loan(bank, 3_000.0, 3_000.0, date, &fs.calendar);
+        let loan_id = loan.id;
+        state.financial_system.create_instrument(loan).unwrap();
+        assert!(capacity > 3_000.0);
+
+        let missed = CreditEffect::RecordMissedPayment { instrument_id: loan_id, amount: 3_000.0, date };
+        state.apply_effect(&StateEffect::Credit(missed)).unwrap();
+        state.current_date += chrono::Duration::days(state.financial_system.credit.grace_period_days);
+        let result = CreditDomain::new().execute(&CreditAction::ReviewDelinquencies, &state);
+        state.apply_effects(&result.effects).unwrap();
+
+        let fs = &state.financial_system;
+        assert!(!fs.instruments.contains_key(&loan_id), "The claim is settled and closed");
+        let seized: u64 = fs
+            .get_bs_by_id(&cb_id)
+            .unwrap()
+            .assets
+            .values()
+            .filter_map(|inst| inst.details.as_any().downcast_ref::<BondDetails>())
+            .map(|bond| bond.quantity)
+            .sum();
+        assert!((3..10).contains(&seized), "Only enough bonds to cover the loan are taken, took {}", seized);
+        assert_eq!(fs.instruments[&treasury_id].quantity(), Some(10 - seized));
+    }
+}
@@ -0,0 +1,86 @@
+use serde::{Deserialize, Serialize};
+use sim_core::*;
+
+/// Rates borrowers from three ratios. Each ratio places the borrower at a notch on the
+/// `CreditRating::PERFORMING` scale through its threshold table, and the rating is the weighted
+/// average notch. Thresholds are listed best first, one per notch from AAA to CC; a ratio that
+/// clears none of them rates C.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RatingAgency {
+    /// Debt over total assets; lower is better.
+    pub leverage_thresholds: [f64; 8],
+    /// Operating profit over interest expense; higher is better.
+    pub coverage_thresholds: [f64; 8],
+    /// Liquid assets over debt; higher is better.
+    pub liquidity_thresholds: [f64; 8],
+    pub leverage_weight: f64,
+    pub coverage_weight: f64,
+    pub liquidity_weight: f64,
+}
+
+impl Default for RatingAgency {
+    fn default() -> Self {
+        Self {
+            leverage_thresholds: [0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.75, 0.9],
+            coverage_thresholds: [12.0, 8.0, 6.0, 4.0, 3.0, 2.0, 1.5, 1.0],
+            liquidity_thresholds: [1.0, 0.75, 0.5, 0.35, 0.25, 0.15, 0.1, 0.05],
+            leverage_weight: 0.4,
+            coverage_weight: 0.4,
+            liquidity_weight: 0.2,
+        }
+    }
+}
+
+impl RatingAgency {
+    /// Reviews `agent_id` from its balance sheet and its last closed reporting period. The income
+    /// statement resets every period, so the period to date is only used until a first period closes.
+    pub fn assess(&self, agent_id: AgentId, state: &SimState) -> Option<RatingAssessment> {
+        let bs = state.financial_system.get_bs_by_id(&agent_id)?;
+        let debt = bs.total_debt();
+        let total_assets = bs.total_assets();
+        let leverage = if total_assets > 1e-9 {
+            debt / total_assets
+        } else if debt > 1e-9 {
+            f64::MAX
+        } else {
+            0.0
+        };
+
+        let (operating_profit, interest_expense) = match state.latest_earnings(&agent_id) {
+            Some(report) => (report.revenues - report.costs + report.interest_expense, report.interest_expense),
+            None => {
+                let statement = &bs.income_statement;
+                (statement.net_income + statement.interest_expense, statement.interest_expense)
+            }
+        };
+        let interest_coverage = (interest_expense > 1e-9).then(|| operating_profit / interest_expense);
+        let liquidity = (debt > 1e-9).then(|| bs.liquid_assets() / debt);
+
+        Some(RatingAssessment {
+            tick: state.ticknum,
+            date: state.current_date,
+            agent_id,
+            rating: self.rating_for(leverage, interest_coverage, liquidity),
+            leverage,
+            interest_coverage,
+            liquidity,
+        })
+    }
+
+    /// A missing coverage or liquidity ratio means there is no interest or debt to cover, which is the
+    /// best case.
+    pub fn rating_for(&self, leverage: f64, interest_coverage: Option<f64>, liquidity: Option<f64>) -> CreditRating {
+        let leverage_notch = self.leverage_thresholds.iter().position(|t| leverage <= *t);
+        let coverage_notch = interest_coverage.map(|c| self.coverage_thresholds.iter().position(|t| c >= *t));
+        let liquidity_notch = liquidity.map(|l| self.liquidity_thresholds.iter().position(|t| l >= *t));
+
+        let worst = CreditRating::PERFORMING.len() - 1;
+        let notch = |position: Option<usize>| position.unwrap_or(worst) as f64;
+        let weighted = self.leverage_weight * notch(leverage_notch)
+            + self.coverage_weight * coverage_notch.map_or(0.0, notch)
+            + self.liquidity_weight * liquidity_notch.map_or(0.0, notch);
+        let total_weight = self.leverage_weight + self.coverage_weight + self.liquidity_weight;
+        let index = (weighted / total_weight).round() as usize;
+        CreditRating::PERFORMING[index.min(worst)].clone()
+    }
+}
@@ -2,10 +2,54 @@ use sim_core::*;
 use std::any::Any;
 use rand::RngCore;
 use serde::{Deserialize, Serialize};
-use chrono::Datelike;
+use chrono::{Datelike, Weekday};
 
-#[derive(Clone, Debug, Serialize, Default, Deserialize)]
-pub struct BasicGovernmentDecisionModel;
+/// Spends its targets every Monday, a week's transfers and purchases at a time, and a month's public
+/// investment on the first Monday of the month. Transfers are shared out under its `TransferRules`,
+/// purchases go into the goods markets at the best asks, and investment goes to firms in equal grants.
+/// The week's unemployment insurance claims are paid alongside. Spending is cut back pro rata to the
+/// cash on hand.
+///
+/// Closes the books on the first of each month, setting the debt service target to a year's
+/// coupons on the debt outstanding. Unless its `FiscalPolicy` is `Automatic`, it then closes
+/// `adjustment_speed` of the gap between the month's deficit, annualized and net of the insurance
+/// scheme, and the policy's target: half through purchases, half through the income tax rate. It
+/// borrows at the auctions on its `AuctionCalendar` whenever its cash falls short of a month's
+/// spending plus the debt falling due within the month, and holds each auction on its date. Linked
+/// auctions are only announced once there is a CPI to index the bonds to.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct BasicGovernmentDecisionModel {
+    pub adjustment_speed: f64,
+    pub auctions: AuctionCalendar,
+    pub transfers: TransferRules,
+}
+
+impl Default for BasicGovernmentDecisionModel {
+    fn default() -> Self {
+        Self { adjustment_speed: 0.25, auctions: AuctionCalendar::default(), transfers: TransferRules::default() }
+    }
+}
+
+/// How the `transfers` budget is shared out between the programmes.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct TransferRules {
+    /// Share of the budget paid equally to every consumer. The rest is means-tested.
+    pub universal_share: f64,
+    /// Income at which the means-tested benefit runs out. Below it, a consumer's claim grows in
+    /// proportion to the shortfall.
+    pub income_threshold: f64,
+}
+
+impl Default for TransferRules {
+    fn default() -> Self {
+        Self { universal_share: 0.25, income_threshold: 30_000.0 }
+    }
+}
+
+/// The highest income tax rate the government will steer to.
+const MAX_INCOME_TAX_RATE: f64 = 0.6;
 
 #[typetag::serde]
 impl DecisionModel for BasicGovernmentDecisionModel {
@@ -16,20 +60,266 @@ impl DecisionModel for BasicGovernmentDecisionModel {
         };
 
         let mut actions = Vec::new();
-        if state.current_date.ordinal() % 30 == 0{ // Every 30 days, collect taxes 
-            let tax_rate = government.tax_rates.income_tax;
-
-            for consumer in state.agents.consumers.values() {
-                let tax_liability = (consumer.income/12.0) * tax_rate; // Monthly income tax
-                if tax_liability > 0.0 {
-                    actions.push(SimAction::Banking(BankingAction::Transfer {
-                        from: consumer.id,
-                        to: government.id,
-                        amount: tax_liability,
-                    }));
+        if state.current_date.weekday() == Weekday::Mon {
+            actions.extend(self.spend(government, state));
+        }
+        if state.current_date.day() == 1 {
+            actions.extend(self.steer_budget(government, state).into_iter().map(SimAction::Fiscal));
+            actions.extend(self.plan_debt_service(government, state).map(SimAction::Fiscal));
+            actions.push(SimAction::Fiscal(FiscalAction::CloseFiscalPeriod { government_id: government.id }));
+        }
+        actions.extend(self.auctions(government, state).into_iter().map(SimAction::Fiscal));
+        actions
+    }
+}
+
+impl BasicGovernmentDecisionModel {
+    /// The week's transfers, insurance claims and purchases, and on the first Monday of the month its
+    /// public investment, scaled down together if they come to more than the government's cash.
+    pub fn spend(&self, government: &Government, state: &SimState) -> Vec<SimAction> {
+        let fs = &state.financial_system;
+        let targets = &government.spending_targets;
+        let mut payments = self.benefits(targets.transfers / 52.0, state);
+        payments.extend(self.unemployment_benefits(government, state));
+        if state.current_date.day() <= 7 {
+            payments.extend(self.investment_grants(targets.investment / 12.0, state));
+        }
+        let mut purchases: Vec<(GoodId, f64)> = {
+            let markets: Vec<GoodId> = fs
+                .exchange
+                .goods_markets
+                .iter()
+                .filter(|(_, market)| market.best_ask().is_some())
+                .map(|(good_id, _)| *good_id)
+                .collect();
+            let share = targets.purchases / 52.0 / markets.len().max(1) as f64;
+            markets.into_iter().map(|good_id| (good_id, share)).collect()
+        };
+
+        let planned = payments.iter().map(|payment| payment.amount).sum::<f64>()
+            + purchases.iter().map(|(_, amount)| amount).sum::<f64>();
+        if planned <= 1e-6 {
+            return vec![];
+        }
+        let scale = (fs.get_liquid_assets(&government.id) / planned).min(1.0);
+        payments.iter_mut().for_each(|payment| payment.amount *= scale);
+        payments.retain(|payment| payment.amount >= 0.01);
+        purchases.iter_mut().for_each(|(_, amount)| *amount *= scale);
+
+        let mut actions = vec![];
+        if !payments.is_empty() {
+            actions.push(SimAction::Fiscal(FiscalAction::Disburse { government_id: government.id, payments }));
+        }
+        for (good_id, max_notional) in purchases.into_iter().filter(|(_, amount)| *amount >= 0.01) {
+            actions.push(SimAction::Consumption(ConsumptionAction::PurchaseAtBest {
+                agent_id: government.id,
+                good_id,
+                max_notional,
+            }));
+        }
+        actions
+    }
+
+    /// Shares `budget` out between consumers: the universal share equally, the rest in proportion to
+    /// how far each consumer's income falls short of the means test's threshold.
+    pub fn benefits(&self, budget: f64, state: &SimState) -> Vec<Disbursement> {
+        let consumers = &state.agents.consumers;
+        if budget <= 0.0 || consumers.is_empty() {
+            return vec![];
+        }
+        let rules = &self.transfers;
+        let universal = budget * rules.universal_share / consumers.len() as f64;
+        let need = |consumer: &Consumer| (1.0 - consumer.income / rules.income_threshold).clamp(0.0, 1.0);
+        let total_need: f64 = consumers.values().map(need).sum();
+        let means_tested = budget * (1.0 - rules.universal_share);
+
+        let mut payments = vec![];
+        for consumer in consumers.values() {
+            let mut pay = |amount: f64, program| {
+                if amount > 0.0 {
+                    payments.push(Disbursement {
+                        recipient: consumer.id,
+                        amount,
+                        purpose: SpendingPurpose::Benefit(program),
+                    });
                 }
+            };
+            pay(universal, TransferProgram::Universal);
+            if total_need > 0.0 {
+                pay(means_tested * need(consumer) / total_need, TransferProgram::MeansTested);
+            }
+        }
+        payments
+    }
+
+    /// The week's benefit for every consumer the unemployment insurance scheme covers.
+    pub fn unemployment_benefits(&self, government: &Government, state: &SimState) -> Vec<Disbursement> {
+        let scheme = &government.unemployment_insurance;
+        state
+            .agents
+            .consumers
+            .values()
+            .filter_map(|consumer| {
+                let amount = scheme.weekly_benefit(consumer, state.current_date)?;
+                Some(Disbursement {
+                    recipient: consumer.id,
+                    amount,
+                    purpose: SpendingPurpose::Benefit(TransferProgram::UnemploymentInsurance),
+                })
+            })
+            .collect()
+    }
+
+    /// The insurance scheme's annual deficit at this week's claims and payroll: benefits paid less the
+    /// contributions withheld from wages.
+    pub fn insurance_deficit(&self, government: &Government, state: &SimState) -> f64 {
+        let claims: f64 = self.unemployment_benefits(government, state).iter().map(|claim| claim.amount).sum();
+        let payroll: f64 = state
+            .agents
+            .firms
+            .values()
+            .flat_map(|firm| firm.employees.values())
+            .map(|contract| contract.wage_rate * contract.hours)
+            .sum();
+        (claims - payroll * government.tax_rates.payroll_contribution) * 52.0
+    }
+
+    /// Equal grants from `budget` to every firm.
+    pub fn investment_grants(&self, budget: f64, state: &SimState) -> Vec<Disbursement> {
+        let firms = &state.agents.firms;
+        if budget <= 0.0 || firms.is_empty() {
+            return vec![];
+        }
+        let grant = budget / firms.len() as f64;
+        firms
+            .keys()
+            .map(|firm| Disbursement { recipient: *firm, amount: grant, purpose: SpendingPurpose::PublicInvestment })
+            .collect()
+    }
+
+    /// Sets the debt service target to a year's coupons on the Treasuries outstanding, which the
+    /// bonds pay as they fall due.
+    pub fn plan_debt_service(&self, government: &Government, state: &SimState) -> Option<FiscalAction> {
+        let coupons: f64 = state
+            .financial_system
+            .instruments
+            .values()
+            .filter(|inst| inst.debtor == government.id)
+            .filter_map(|inst| inst.details.as_any().downcast_ref::<BondDetails>())
+            .map(|bond| bond.coupon_rate * bond.face_value * bond.quantity as f64)
+            .sum();
+        let current = government.spending_targets.level(&SpendingCategory::DebtService);
+        ((coupons - current).abs() > 1e-6).then_some(FiscalAction::SetSpendingTarget {
+            government_id: government.id,
+            category: SpendingCategory::DebtService,
+            new_level: coupons,
+        })
+    }
+
+    /// Changes to the purchases target and income tax rate that move the budget towards the policy's
+    /// target deficit, judged on the month just ended. The insurance scheme's deficit is left out, so
+    /// that the rest of the budget is not cut back to make up for the claims in a downturn.
+    pub fn steer_budget(&self, government: &Government, state: &SimState) -> Vec<FiscalAction> {
+        let Some(target) = government.fiscal_policy.target_deficit() else {
+            return vec![];
+        };
+        let Some(statement) = state.financial_system.get_bs_by_id(&government.id).map(|bs| &bs.income_statement) else {
+            return vec![];
+        };
+        let monthly_deficit =
+            statement.operating_expenses + statement.interest_expense - statement.interest_income - statement.revenue;
+        let structural_deficit = monthly_deficit * 12.0 - self.insurance_deficit(government, state);
+        let correction = self.adjustment_speed * (structural_deficit - target);
+        if correction.abs() < 1e-6 {
+            return vec![];
+        }
+
+        let mut actions = vec![];
+        let purchases = government.spending_targets.level(&SpendingCategory::Purchases);
+        let new_purchases = (purchases - correction / 2.0).max(0.0);
+        if (new_purchases - purchases).abs() > 1e-6 {
+            actions.push(FiscalAction::SetSpendingTarget {
+                government_id: government.id,
+                category: SpendingCategory::Purchases,
+                new_level: new_purchases,
+            });
+        }
+        let income_base: f64 = state.agents.consumers.values().map(|consumer| consumer.income).sum();
+        if income_base > 0.0 {
+            let from_taxes = correction - (purchases - new_purchases);
+            let rate = government.tax_rates.income_tax;
+            let new_rate = (rate + from_taxes / income_base).clamp(0.0, MAX_INCOME_TAX_RATE);
+            if (new_rate - rate).abs() > 1e-9 {
+                actions.push(FiscalAction::ChangeTaxRate {
+                    government_id: government.id,
+                    tax_type: TaxType::Income,
+                    new_rate,
+                });
+            }
+        }
+        actions
+    }
+
+    /// Holds the auctions due today, and announces the calendar's auctions falling `lead_days` ahead,
+    /// sharing out the funding need between them.
+    pub fn auctions(&self, government: &Government, state: &SimState) -> Vec<FiscalAction> {
+        let fs = &state.financial_system;
+        let mut actions: Vec<FiscalAction> = fs
+            .treasury_auctions
+            .iter()
+            .filter(|auction| auction.auction_date <= state.current_date)
+            .map(|auction| FiscalAction::HoldAuction { government_id: government.id, auction_id: auction.id })
+            .collect();
+
+        // A linked bond needs a CPI to index to
+        let cpi_known = state.cpi_view().cpi > 0.0;
+        let due: Vec<_> = self
+            .auctions
+            .due_for_announcement(state.current_date, &fs.calendar)
+            .into_iter()
+            .filter(|(slot, _)| cpi_known || !slot.inflation_linked)
+            .collect();
+        if due.is_empty() {
+            return actions;
+        }
+        let bonds = (self.funding_need(government, state) / BENCHMARK_FACE_VALUE).floor().max(0.0) as u64;
+        let (share, odd) = (bonds / due.len() as u64, bonds % due.len() as u64);
+        for (i, (slot, auction_date)) in due.into_iter().enumerate() {
+            let offering = share + u64::from((i as u64) < odd);
+            if offering > 0 {
+                actions.push(FiscalAction::AnnounceAuction {
+                    government_id: government.id,
+                    tenor: slot.tenor,
+                    inflation_linked: slot.inflation_linked,
+                    offering,
+                    auction_date,
+                    format: self.auctions.format,
+                });
             }
         }
         actions
     }
-}
\ No newline at end of file
+
+    /// New debt to cover a month's spending targets and insurance claims and the bonds maturing within
+    /// 30 days, less the cash on hand and what is already announced, up to the debt ceiling.
+    pub fn funding_need(&self, government: &Government, state: &SimState) -> f64 {
+        let fs = &state.financial_system;
+        let horizon = state.current_date + chrono::Duration::days(30);
+        let maturing: f64 = fs
+            .instruments
+            .values()
+            .filter(|inst| inst.debtor == government.id)
+            .filter(|inst| {
+                inst.details.as_any().downcast_ref::<BondDetails>().is_some_and(|bond| bond.maturity_date <= horizon)
+            })
+            .map(|inst| inst.principal)
+            .sum();
+        let claims: f64 = self.unemployment_benefits(government, state).iter().map(|claim| claim.amount).sum();
+        let needed = government.spending_targets.total() / 12.0 + claims * 52.0 / 12.0 + maturing;
+        let shortfall = needed - fs.get_liquid_assets(&government.id) - fs.government_debt_announced();
+        match government.debt_ceiling {
+            Some(ceiling) => shortfall.min(ceiling - fs.government_debt() - fs.government_debt_announced()),
+            None => shortfall,
+        }
+    }
+}
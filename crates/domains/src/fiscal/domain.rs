@@ -1,9 +1,17 @@
+use crate::banking::BankingDomain;
 use serde::{Deserialize, Serialize};
 use sim_core::*;
 use sim_macros::SimDomain;
+use std::collections::HashMap;
 
-#[derive(Clone, Debug, Serialize, Deserialize, Default, SimDomain)]
-pub struct FiscalDomain {}
+/// Elasticity of a firm's output to public capital: a grant that adds a tenth to a firm's capital
+/// stock raises its productivity by 3%.
+pub const PUBLIC_CAPITAL_ELASTICITY: f64 = 0.3;
+
+#[derive(Clone, Debug, Serialize, Deserialize, SimDomain)]
+pub struct FiscalDomain {
+    payment_router: BankingDomain,
+}
 
 #[derive(Debug, Clone)]
 pub struct FiscalResult {
@@ -14,48 +22,428 @@ pub struct FiscalResult {
 
 impl FiscalDomain {
     pub fn new() -> Self {
-        Self {}
+        Self { payment_router: BankingDomain::new() }
     }
     pub fn can_handle(&self, action: &FiscalAction) -> bool {
         match action {
             FiscalAction::ChangeTaxRate { .. } => true,
-            FiscalAction::IssueDebt { .. } => true,
+            FiscalAction::AnnounceAuction { .. } => true,
+            FiscalAction::SubmitAuctionBid { .. } => true,
+            FiscalAction::HoldAuction { .. } => true,
             FiscalAction::SetSpendingTarget { .. } => true,
+            FiscalAction::Disburse { .. } => true,
+            FiscalAction::CollectTax { .. } => true,
+            FiscalAction::CloseFiscalPeriod { .. } => true,
         }
     }
-    pub fn validate(&self, _action: &FiscalAction, _state: &SimState) -> FiscalResult {
-        let errors = vec![];
-
-        FiscalResult { success: errors.is_empty(), effects: vec![], errors }
+    pub fn validate(&self, action: &FiscalAction, state: &SimState) -> Result<(), String> {
+        let government = &state.financial_system.government;
+        let bid = matches!(action, FiscalAction::SubmitAuctionBid { .. });
+        if !bid && action.agent_id() != government.id {
+            return Err(format!("Agent {} is not the government.", action.agent_id()));
+        }
+        match action {
+            FiscalAction::ChangeTaxRate { tax_type, new_rate, .. } => {
+                if !(0.0..=1.0).contains(new_rate) {
+                    return Err(format!("{:?} tax rate {} is not between 0 and 1.", tax_type, new_rate));
+                }
+                Ok(())
+            }
+            FiscalAction::AnnounceAuction { tenor, offering, auction_date, .. } => {
+                if *offering == 0 {
+                    return Err("An auction must offer at least one bond.".to_string());
+                }
+                if *auction_date <= state.current_date {
+                    return Err(format!("Auction date {} is not after {}.", auction_date, state.current_date));
+                }
+                let market_id = FinancialMarketId::Treasury { tenor: *tenor };
+                if state.financial_system.exchange.financial_market(&market_id).is_none() {
+                    return Err(format!("No Treasury market for {}.", tenor));
+                }
+                let fs = &state.financial_system;
+                let debt = fs.government_debt() + fs.government_debt_announced();
+                let face_value = *offering as f64 * BENCHMARK_FACE_VALUE;
+                match government.debt_ceiling {
+                    Some(ceiling) if debt + face_value > ceiling => Err(format!(
+                        "Auctioning ${:.0} would take debt of ${:.0} past the ceiling of ${:.0}.",
+                        face_value, debt, ceiling
+                    )),
+                    _ => Ok(()),
+                }
+            }
+            FiscalAction::SubmitAuctionBid { auction_id, bid } => {
+                let Some(auction) = state.financial_system.treasury_auction(auction_id) else {
+                    return Err(format!("No Treasury auction {}.", auction_id));
+                };
+                if !auction.is_open(state.current_date) {
+                    return Err(format!("Auction {} is not taking bids on {}.", auction_id, state.current_date));
+                }
+                if bid.quantity == 0 {
+                    return Err("A bid must be for at least one bond.".to_string());
+                }
+                if bid.yield_bid.is_some_and(|rate| !rate.is_finite()) {
+                    return Err(format!("Bid yield {:?} is not a number.", bid.yield_bid));
+                }
+                let committed: u64 = state
+                    .financial_system
+                    .treasury_auctions
+                    .iter()
+                    .flat_map(|auction| &auction.bids)
+                    .filter(|other| other.bidder == bid.bidder)
+                    .map(|other| other.quantity)
+                    .sum();
+                let needed = (committed + bid.quantity) as f64 * BENCHMARK_FACE_VALUE;
+                let available = self.payment_router.available_funds(bid.bidder, state);
+                if needed > available {
+                    return Err(format!(
+                        "Bidder {} has ${:.2} to cover ${:.2} of auction bids.",
+                        bid.bidder, available, needed
+                    ));
+                }
+                Ok(())
+            }
+            FiscalAction::HoldAuction { auction_id, .. } => match state.financial_system.treasury_auction(auction_id) {
+                None => Err(format!("No Treasury auction {}.", auction_id)),
+                Some(auction) if auction.auction_date > state.current_date => {
+                    Err(format!("Auction {} is not due until {}.", auction_id, auction.auction_date))
+                }
+                Some(_) => Ok(()),
+            },
+            FiscalAction::SetSpendingTarget { category, new_level, .. } => {
+                if !new_level.is_finite() || *new_level < 0.0 {
+                    return Err(format!("{:?} spending target {} is not a non-negative amount.", category, new_level));
+                }
+                Ok(())
+            }
+            FiscalAction::Disburse { payments, .. } => {
+                for payment in payments {
+                    Validator::positive_amount(payment.amount)?;
+                    let known = match payment.purpose {
+                        SpendingPurpose::Benefit(_) => state.agents.consumers.contains_key(&payment.recipient),
+                        SpendingPurpose::PublicInvestment => state.agents.firms.contains_key(&payment.recipient),
+                    };
+                    if !known {
+                        return Err(format!("{} is not eligible for {:?}.", payment.recipient, payment.purpose));
+                    }
+                }
+                let total: f64 = payments.iter().map(|payment| payment.amount).sum();
+                let available = self.payment_router.available_funds(government.id, state);
+                if total > available + 1e-6 {
+                    return Err(format!("The government has ${:.2} to cover ${:.2} of payments.", available, total));
+                }
+                Ok(())
+            }
+            FiscalAction::CollectTax { amount, .. } => Validator::positive_amount(*amount),
+            FiscalAction::CloseFiscalPeriod { .. } => Ok(()),
+        }
     }
     pub fn execute(&self, action: &FiscalAction, state: &SimState) -> FiscalResult {
-        let mut effects = vec![];
+        if let Err(error) = self.validate(action, state) {
+            return FiscalResult { success: false, effects: vec![], errors: vec![error] };
+        }
 
         match action {
-            FiscalAction::ChangeTaxRate { government_id, tax_type, new_rate } => {
-                println!("[FISCAL DOMAIN] Executing ChangeTaxRate for {} | Setting {:?} to {}", government_id, tax_type, new_rate);
-            }
-            FiscalAction::IssueDebt { government_id, tenor, face_value } => {
-                let maturity_date = tenor.add_to_date(state.current_date);
-                let bond = bond!(
-                    *government_id,
-                    *government_id,
-                    *face_value,
-                    0.04,
-                    maturity_date,
-                    *face_value,
-                    BondType::Government,
-                    2,
-                    *tenor,
-                    state.current_date
+            FiscalAction::ChangeTaxRate { tax_type, new_rate, .. } => {
+                println!("[FISCAL DOMAIN] {:?} tax rate set to {:.2}%", tax_type, new_rate * 100.0);
+                let effect =
+                    StateEffect::Financial(FinancialEffect::SetTaxRate { tax_type: *tax_type, rate: *new_rate });
+                FiscalResult { success: true, effects: vec![effect], errors: vec![] }
+            }
+            FiscalAction::AnnounceAuction { tenor, inflation_linked, offering, auction_date, format, .. } => {
+                let auction = TreasuryAuction {
+                    id: AuctionId(uuid::Uuid::new_v4()),
+                    tenor: *tenor,
+                    inflation_linked: *inflation_linked,
+                    offering: *offering,
+                    announced: state.current_date,
+                    auction_date: *auction_date,
+                    format: *format,
+                    bids: vec![],
+                };
+                println!(
+                    "[FISCAL DOMAIN] Announced {:?} auction of {} {} bonds for {}",
+                    format, offering, tenor, auction_date
                 );
-                effects.push(StateEffect::Financial(FinancialEffect::CreateInstrument(bond)));
+                let effect = StateEffect::Financial(FinancialEffect::AnnounceTreasuryAuction(auction));
+                FiscalResult { success: true, effects: vec![effect], errors: vec![] }
+            }
+            FiscalAction::SubmitAuctionBid { auction_id, bid } => {
+                let effect = StateEffect::Financial(FinancialEffect::SubmitAuctionBid {
+                    auction_id: *auction_id,
+                    bid: bid.clone(),
+                });
+                FiscalResult { success: true, effects: vec![effect], errors: vec![] }
             }
-            FiscalAction::SetSpendingTarget { government_id, .. } => {
-                println!("[FISCAL DOMAIN] Executing SetSpendingTarget for {}", government_id);
+            FiscalAction::HoldAuction { government_id, auction_id } => {
+                let auction = state.financial_system.treasury_auction(auction_id).expect("validated");
+                self.execute_hold_auction(*government_id, auction, state)
+            }
+            FiscalAction::SetSpendingTarget { category, new_level, .. } => {
+                println!("[FISCAL DOMAIN] {:?} spending target set to ${:.2} a year", category, new_level);
+                let effect = StateEffect::Financial(FinancialEffect::SetSpendingTarget {
+                    category: *category,
+                    level: *new_level,
+                });
+                FiscalResult { success: true, effects: vec![effect], errors: vec![] }
+            }
+            FiscalAction::Disburse { government_id, payments } => {
+                self.execute_disburse(*government_id, payments, state)
+            }
+            FiscalAction::CollectTax { government_id, taxpayer, tax_type, amount } => {
+                self.execute_collect_tax(*government_id, *taxpayer, *tax_type, *amount, state)
+            }
+            FiscalAction::CloseFiscalPeriod { government_id } => {
+                self.execute_close_fiscal_period(*government_id, state)
+            }
+        }
+    }
+
+    /// Allots the auction and issues the bonds. Each winner pays for its whole allotment at once, in
+    /// reserves or deposits; a winner that cannot pay gets nothing, and its bonds go unsold.
+    fn execute_hold_auction(
+        &self, government_id: AgentId, auction: &TreasuryAuction, state: &SimState,
+    ) -> FiscalResult {
+        let date = state.current_date;
+        let allotment = allot(auction);
+        let stop_out_yield = allotment.stop_out_yield;
+        let mut awards = vec![];
+        let mut effects = vec![];
+        let mut errors = vec![];
+        let cpi = state.cpi_view().cpi;
+        let security = stop_out_yield.map(|rate| auction.security(rate, date, cpi));
+        if let (Some(security), Some(stop_out_yield)) = (&security, stop_out_yield) {
+            let pricer = security.pricer(date);
+            let (accepted, yield_sum) = auction
+                .bids
+                .iter()
+                .zip(&allotment.quantities)
+                .filter_map(|(bid, units)| bid.yield_bid.map(|rate| (*units as f64, rate * *units as f64)))
+                .fold((0.0, 0.0), |(units, sum), (u, s)| (units + u, sum + s));
+            let average_yield = if accepted > 0.0 { yield_sum / accepted } else { stop_out_yield };
+
+            let mut bidders = vec![];
+            let mut totals: HashMap<AgentId, (u64, f64)> = HashMap::new();
+            for (bid, units) in auction.bids.iter().zip(&allotment.quantities).filter(|(_, units)| **units > 0) {
+                let rate = match auction.format {
+                    AuctionFormat::SinglePrice => stop_out_yield,
+                    AuctionFormat::MultiplePrice => bid.yield_bid.unwrap_or(average_yield),
+                };
+                let (quantity, amount) = totals.entry(bid.bidder).or_insert_with(|| {
+                    bidders.push(bid.bidder);
+                    (0, 0.0)
+                });
+                *quantity += units;
+                *amount += *units as f64 * pricer.clean_price(rate);
+            }
+
+            for bidder in bidders {
+                let (quantity, amount) = totals[&bidder];
+                let bond = BondDetails { quantity, ..security.clone() }.issue(
+                    bidder,
+                    government_id,
+                    date,
+                    &state.financial_system.calendar,
+                );
+                let payment = self.payment_router.execute_payment(
+                    bidder,
+                    government_id,
+                    amount,
+                    TransactionType::Transfer { from: bidder, to: government_id, amount },
+                    Some(bond.id),
+                    state,
+                );
+                if !payment.success {
+                    errors.extend(payment.errors);
+                    continue;
+                }
+                awards.push(AuctionAward { bidder, quantity, amount, instrument_id: bond.id });
+                effects.push(StateEffect::Financial(FinancialEffect::CreateInstrument(bond)));
+                effects.extend(payment.effects);
+                effects.push(StateEffect::Financial(FinancialEffect::RecordAcquisition {
+                    holder: bidder,
+                    market_id: MarketId::Financial(FinancialMarketId::Treasury { tenor: auction.tenor }),
+                    units: quantity,
+                    cost: amount,
+                }));
             }
         }
 
+        let result = AuctionResult {
+            auction_id: auction.id,
+            date,
+            tenor: auction.tenor,
+            format: auction.format,
+            offering: auction.offering,
+            tendered: auction.tendered(),
+            non_competitive: auction.bids.iter().filter(|bid| !bid.is_competitive()).map(|bid| bid.quantity).sum(),
+            accepted: awards.iter().map(|award| award.quantity).sum(),
+            stop_out_yield,
+            allotted_at_stop: allotment.allotted_at_stop,
+            coupon_rate: security.map_or(0.0, |security| security.coupon_rate),
+            awards,
+        };
+        println!(
+            "[FISCAL DOMAIN] {} auction: {} of {} bonds sold, bid-to-cover {:.2}, stop-out {}",
+            auction.tenor,
+            result.accepted,
+            result.offering,
+            result.bid_to_cover(),
+            result.stop_out_yield.map_or("none".to_string(), |rate| format!("{:.3}%", rate * 100.0))
+        );
+        effects.push(StateEffect::Financial(FinancialEffect::RecordAuctionResult(result)));
+        FiscalResult { success: true, effects, errors }
+    }
+
+    /// Pays out the day's benefits and investment grants in a single payment from the government's
+    /// account, booked as spending. A grant adds to the firm's capital stock and raises its
+    /// productivity by `PUBLIC_CAPITAL_ELASTICITY` times the share it adds to that stock.
+    fn execute_disburse(&self, government_id: AgentId, payments: &[Disbursement], state: &SimState) -> FiscalResult {
+        let transfers: Vec<_> = payments
+            .iter()
+            .map(|payment| {
+                let (recipient, purpose) = (payment.recipient, payment.purpose);
+                (recipient, payment.amount, TransactionType::GovernmentPayment { recipient, purpose })
+            })
+            .collect();
+        let result = self.payment_router.execute_split_payment(government_id, &transfers, None, state);
+        if !result.success {
+            return FiscalResult { success: false, effects: result.effects, errors: result.errors };
+        }
+
+        let mut effects = result.effects;
+        let spent: f64 = payments.iter().map(|payment| payment.amount).sum();
+        effects.push(StateEffect::Financial(FinancialEffect::RecordIncome {
+            agent_id: government_id,
+            line: IncomeLine::OperatingExpense,
+            amount: spent,
+        }));
+        for payment in payments.iter().filter(|payment| payment.purpose == SpendingPurpose::PublicInvestment) {
+            let firm = state.agents.firms.get(&payment.recipient).expect("validated");
+            let added = payment.amount / firm.capital_stock.max(payment.amount);
+            effects.push(StateEffect::Agent(AgentEffect::AddCapital {
+                firm_id: firm.id,
+                amount: payment.amount,
+                productivity_gain: firm.productivity * PUBLIC_CAPITAL_ELASTICITY * added,
+            }));
+        }
+        println!("[FISCAL DOMAIN] Paid out ${:.2} to {} recipients", spent, payments.len());
+        FiscalResult { success: true, effects, errors: vec![] }
+    }
+
+    fn execute_collect_tax(
+        &self, government_id: AgentId, taxpayer: AgentId, tax_type: TaxType, amount: f64, state: &SimState,
+    ) -> FiscalResult {
+        let tx_type = TransactionType::TaxPayment { payer: taxpayer, tax_type, period: state.current_date };
+        let payment = self.payment_router.execute_payment(taxpayer, government_id, amount, tx_type, None, state);
+        let mut effects = payment.effects;
+        if payment.success {
+            effects.push(StateEffect::Financial(FinancialEffect::RecordIncome {
+                agent_id: government_id,
+                line: IncomeLine::Revenue,
+                amount,
+            }));
+        }
+        FiscalResult { success: payment.success, effects, errors: payment.errors }
+    }
+
+    /// Reports the period's revenue, spending and net interest from the government's income statement,
+    /// then starts a new one.
+    fn execute_close_fiscal_period(&self, government_id: AgentId, state: &SimState) -> FiscalResult {
+        let fs = &state.financial_system;
+        let statement = fs.get_bs_by_id(&government_id).map(|bs| bs.income_statement.clone()).unwrap_or_default();
+        let report = FiscalReport {
+            date: state.current_date,
+            revenue: statement.revenue,
+            spending: statement.operating_expenses,
+            interest: statement.interest_expense - statement.interest_income,
+            debt: fs.government_debt(),
+        };
+        println!(
+            "[FISCAL DOMAIN] Fiscal period closed: revenue ${:.2}, spending ${:.2}, interest ${:.2}, deficit ${:.2}, \
+             debt ${:.2}",
+            report.revenue,
+            report.spending,
+            report.interest,
+            report.deficit(),
+            report.debt
+        );
+        let effects = vec![
+            StateEffect::Financial(FinancialEffect::RecordFiscalReport(report)),
+            StateEffect::Financial(FinancialEffect::CloseIncomeStatement { agent_id: government_id }),
+        ];
         FiscalResult { success: true, effects, errors: vec![] }
     }
-}
\ No newline at end of file
+}
+
+impl Default for FiscalDomain {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Bonds allotted to each bid of an auction, in bid order.
+struct Allotment {
+    quantities: Vec<u64>,
+    stop_out_yield: Option<f64>,
+    allotted_at_stop: f64,
+}
+
+/// Fills non-competitive bids first, cut back pro rata should they exceed the offering, then
+/// competitive bids from the lowest yield up. Bids at the stop-out yield share what is left pro rata,
+/// any odd bonds going to the earliest. With no competitive bid there is no stop-out yield and
+/// nothing is sold.
+fn allot(auction: &TreasuryAuction) -> Allotment {
+    let mut quantities = vec![0; auction.bids.len()];
+    let mut competitive: Vec<(usize, f64)> =
+        auction.bids.iter().enumerate().filter_map(|(i, bid)| bid.yield_bid.map(|rate| (i, rate))).collect();
+    if competitive.is_empty() {
+        return Allotment { quantities, stop_out_yield: None, allotted_at_stop: 0.0 };
+    }
+    competitive.sort_by(|a, b| a.1.total_cmp(&b.1));
+
+    let non_competitive: Vec<usize> = (0..auction.bids.len()).filter(|i| !auction.bids[*i].is_competitive()).collect();
+    let mut remaining = auction.offering;
+    remaining -= fill_pro_rata(&auction.bids, &non_competitive, remaining, &mut quantities);
+
+    let (mut stop_out_yield, mut allotted_at_stop) = (competitive[0].1, 0.0);
+    for group in competitive.chunk_by(|a, b| a.1 == b.1) {
+        if remaining == 0 {
+            break;
+        }
+        let indices: Vec<usize> = group.iter().map(|(i, _)| *i).collect();
+        let tendered: u64 = indices.iter().map(|i| auction.bids[*i].quantity).sum();
+        let filled = fill_pro_rata(&auction.bids, &indices, remaining, &mut quantities);
+        remaining -= filled;
+        stop_out_yield = group[0].1;
+        allotted_at_stop = filled as f64 / tendered as f64;
+    }
+    Allotment { quantities, stop_out_yield: Some(stop_out_yield), allotted_at_stop }
+}
+
+/// Fills the bids at `indices` in full if `available` covers them, otherwise pro rata. Returns the
+/// bonds allotted.
+fn fill_pro_rata(bids: &[AuctionBid], indices: &[usize], available: u64, quantities: &mut [u64]) -> u64 {
+    let tendered: u64 = indices.iter().map(|i| bids[*i].quantity).sum();
+    if tendered <= available {
+        for i in indices {
+            quantities[*i] = bids[*i].quantity;
+        }
+        return tendered;
+    }
+    let ratio = available as f64 / tendered as f64;
+    let mut allotted = 0;
+    for i in indices {
+        quantities[*i] = (bids[*i].quantity as f64 * ratio).floor() as u64;
+        allotted += quantities[*i];
+    }
+    for i in indices {
+        if allotted == available {
+            break;
+        }
+        if quantities[*i] < bids[*i].quantity {
+            quantities[*i] += 1;
+            allotted += 1;
+        }
+    }
+    allotted
+}
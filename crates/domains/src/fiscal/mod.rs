@@ -8,15 +8,44 @@
 //!
 //! The crate is organized into the standard domain/behavior modules:
 //!
-//! - **`domain.rs`**: Contains the `FiscalDomain` struct. This module handles the
-//!   execution of `FiscalAction`s, such as collecting taxes or distributing
-//!   transfer payments. It translates these high-level policy actions into concrete
-//!   `StateEffect`s that modify agent balance sheets.
+//! - **`domain.rs`**: Contains the `FiscalDomain` struct, which executes `FiscalAction`s:
+//!   - changing a tax rate or a spending target on the `Government`,
+//!   - collecting a tax payment into the government's account, recorded as revenue,
+//!   - running Treasury auctions. An announced auction counts against the debt ceiling and takes
+//!     competitive bids, at a yield, and non-competitive ones until the day before it is held. It then
+//!     fills non-competitive bids first and competitive ones from the lowest yield up to the stop-out
+//!     yield, where bids share what is left pro rata. Winners pay the stop-out price at a
+//!     single-price auction, or the price at their own yield at a multiple-price one, and receive the
+//!     new bonds; notes and bonds carry the stop-out yield, rounded down to an eighth, as their
+//!     coupon. The `AuctionResult` goes into the simulation history. Bonds reach the Treasury markets
+//!     only when their buyers trade them on,
+//!   - disbursing benefits to consumers and public investment grants to firms in one payment from
+//!     the government's account, booked as spending. A grant adds to the firm's capital stock and
+//!     raises its productivity,
+//!   - closing the fiscal period, which records a `FiscalReport` and starts a new income statement.
 //!
-//! - **`behaviour.rs`**: Contains the `BasicGovernmentDecisionModel`. This model implements
-//!   the `DecisionModel` trait and defines how the government agent behaves. For example,
-//!   it may decide to issue new bonds to fund a deficit based on its defined `FiscalPolicy`
-//!   (e.g., `Expansionary`, `Contractionary`).
+//! - **`tax.rs`**: The taxes levied on private agents as payments are made, paid to the government
+//!   out of the same funds and booked as its revenue. Each `Levy` is recorded as a `TaxPayment`:
+//!   - income tax withheld from wages, at the annual tax on the week's pay under the `TaxRates` bracket
+//!     schedule,
+//!   - corporate tax on the period's profit, paid before dividends,
+//!   - consumption tax on consumers' purchases of goods, on top of the price,
+//!   - the unemployment insurance contribution, a flat share of wages withheld with the income tax,
+//!   - capital-gains tax on sales of bonds and shares, withheld from the proceeds. Gains are measured
+//!     against the seller's average cost, kept by market in the financial system's `cost_basis`.
+//!
+//! - **`behaviour.rs`**: Contains the `BasicGovernmentDecisionModel`. Every Monday it spends a
+//!   week of its transfers and purchases targets and its unemployment insurance claims, and on the
+//!   first Monday of the month a month of public investment, cut back to the cash it has. Transfers
+//!   are shared between a universal and a means-tested benefit under its `TransferRules`; purchases
+//!   are bid into the goods markets, and what the government buys is used up. Insurance is paid to
+//!   consumers whose last job ended recently, at a share of its wage, provided they had worked long
+//!   enough before it. It closes the books monthly, setting the debt service target to a year's
+//!   coupons on its debt. Unless the `FiscalPolicy` is `Automatic`, it steers the month's deficit,
+//!   annualized and net of the insurance scheme, part of the way towards the policy's target each
+//!   month, through the purchases target and the income tax rate. It announces auctions on its
+//!   `AuctionCalendar` whenever its cash falls short of a month's spending plus the debt maturing
+//!   within the month, and holds each on its date.
 //!
 //! ## Key Components
 //!
@@ -26,6 +55,411 @@
 //! - **`FiscalPolicy`**: An enum from `sim_types` that guides the government's decision-making.
 pub mod behaviour;
 pub mod domain;
+pub mod tax;
 
 pub use behaviour::*;
-pub use domain::*;
\ No newline at end of file
+pub use domain::*;
+pub use tax::*;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use sim_core::*;
+    use uuid::Uuid;
+
+    fn execute(state: &mut SimState, action: FiscalAction) -> FiscalResult {
+        let result = FiscalDomain::new().execute(&action, state);
+        state.apply_effects(&result.effects).unwrap();
+        result
+    }
+
+    #[test]
+    fn test_tax_rate_and_spending_target_changes_apply() {
+        let mut state = SimState::default();
+        let government_id = state.financial_system.government.id;
+        let change_rate = |new_rate| FiscalAction::ChangeTaxRate { government_id, tax_type: TaxType::Income, new_rate };
+
+        assert!(execute(&mut state, change_rate(0.3)).success);
+        assert!(!execute(&mut state, change_rate(1.5)).success);
+        let category = SpendingCategory::Purchases;
+        let set_target =
+            |government_id, new_level| FiscalAction::SetSpendingTarget { government_id, category, new_level };
+        assert!(execute(&mut state, set_target(government_id, 1200.0)).success);
+        let outsider = AgentId(Uuid::new_v4());
+        assert!(!execute(&mut state, set_target(outsider, 0.0)).success);
+
+        let government = &state.financial_system.government;
+        assert_eq!(government.tax_rates.rate(&TaxType::Income), 0.3);
+        assert_eq!(government.spending_targets.level(&SpendingCategory::Purchases), 1200.0);
+    }
+
+    fn auction_state(dealers: usize) -> (SimState, Vec<AgentId>) {
+        let mut state = SimState::default();
+        let cb_id = state.financial_system.central_bank.id;
+        state.financial_system.exchange.register_financial_market(FinancialMarketId::Treasury { tenor: Tenor::T2Y });
+        let dealers = (0..dealers)
+            .map(|i| {
+                let dealer = AgentId(Uuid::new_v4());
+                state.agents.banks.insert(dealer, Bank::new(format!("Dealer {}", i), 0.0, 0.0));
+                state.financial_system.balance_sheets.insert(dealer, BalanceSheet::new(dealer));
+                let reserves = reserves!(dealer, cb_id, 20_000.0, state.current_date);
+                state.financial_system.create_instrument(reserves).unwrap();
+                dealer
+            })
+            .collect();
+        (state, dealers)
+    }
+
+    fn announce(state: &mut SimState, offering: u64, format: AuctionFormat) -> FiscalResult {
+        let government_id = state.financial_system.government.id;
+        let auction_date = state.current_date + chrono::Duration::days(2);
+        let action = FiscalAction::AnnounceAuction {
+            government_id,
+            tenor: Tenor::T2Y,
+            inflation_linked: false,
+            offering,
+            auction_date,
+            format,
+        };
+        execute(state, action)
+    }
+
+    fn hold(state: &mut SimState) -> AuctionResult {
+        let government_id = state.financial_system.government.id;
+        let auction_id = state.financial_system.treasury_auctions[0].id;
+        state.current_date += chrono::Duration::days(2);
+        let result = execute(state, FiscalAction::HoldAuction { government_id, auction_id });
+        assert!(result.success && result.errors.is_empty(), "{:?}", result.errors);
+        let settle = crate::clearing::ClearingDomain::new().execute(&ClearingAction::SettleNetPositions, state);
+        state.apply_effects(&settle.effects).unwrap();
+        state.history.auction_results.last().unwrap().clone()
+    }
+
+    #[test]
+    fn test_single_price_auction_allots_from_lowest_yield_at_stop_out() {
+        let (mut state, dealers) = auction_state(3);
+        let government_id = state.financial_system.government.id;
+        state.financial_system.government.debt_ceiling = Some(10_000.0);
+        assert!(!announce(&mut state, 11, AuctionFormat::SinglePrice).success);
+        assert!(announce(&mut state, 10, AuctionFormat::SinglePrice).success);
+        // Announced bonds count against the ceiling before they are sold
+        assert!(!announce(&mut state, 1, AuctionFormat::SinglePrice).success);
+
+        let auction_id = state.financial_system.treasury_auctions[0].id;
+        let bids = [
+            (dealers[0], 2, None),
+            (dealers[1], 6, Some(0.041)),
+            (dealers[2], 4, Some(0.043)),
+            (dealers[0], 4, Some(0.043)),
+        ];
+        for (bidder, quantity, yield_bid) in bids {
+            let bid = AuctionBid { bidder, quantity, yield_bid };
+            assert!(execute(&mut state, FiscalAction::SubmitAuctionBid { auction_id, bid }).success);
+        }
+        let result = hold(&mut state);
+
+        // Two non-competitive, six at 4.1%, and the last two shared between the bids at 4.3%
+        assert_eq!((result.offering, result.tendered, result.accepted), (10, 16, 10));
+        assert!((result.bid_to_cover() - 1.6).abs() < 1e-12);
+        assert_eq!(result.stop_out_yield, Some(0.043));
+        assert_eq!(result.allotted_at_stop, 0.25);
+        assert_eq!(result.coupon_rate, 0.0425);
+        let fs = &state.financial_system;
+        assert_eq!(fs.treasuries_held(&dealers[0], &Tenor::T2Y), 3);
+        assert_eq!(fs.treasuries_held(&dealers[1], &Tenor::T2Y), 6);
+        assert_eq!(fs.treasuries_held(&dealers[2], &Tenor::T2Y), 1);
+        assert_eq!(fs.government_debt(), 10_000.0);
+        assert!(fs.treasury_auctions.is_empty());
+
+        // Everyone pays the stop-out price, just under par for a coupon rounded down from the yield
+        let price = result.awards[0].amount / result.awards[0].quantity as f64;
+        assert!(result.awards.iter().all(|award| (award.amount / award.quantity as f64 - price).abs() < 1e-9));
+        assert!(price < 1_000.0 && price > 999.0);
+        assert!((fs.get_liquid_assets(&government_id) - result.proceeds()).abs() < 1e-6);
+        assert!((fs.get_bank_reserves(&dealers[1]).unwrap() - (20_000.0 - 6.0 * price)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_multiple_price_auction_charges_each_bid_its_own_yield() {
+        let (mut state, dealers) = auction_state(2);
+        assert!(announce(&mut state, 8, AuctionFormat::MultiplePrice).success);
+        let auction_id = state.financial_system.treasury_auctions[0].id;
+        for (bidder, yield_bid) in [(dealers[0], 0.040), (dealers[1], 0.045)] {
+            let bid = AuctionBid { bidder, quantity: 5, yield_bid: Some(yield_bid) };
+            assert!(execute(&mut state, FiscalAction::SubmitAuctionBid { auction_id, bid }).success);
+        }
+        // Bids close the day before the auction
+        state.current_date += chrono::Duration::days(2);
+        let late = AuctionBid { bidder: dealers[0], quantity: 1, yield_bid: Some(0.03) };
+        let late_bid = FiscalAction::SubmitAuctionBid { auction_id, bid: late };
+        assert!(FiscalDomain::new().validate(&late_bid, &state).is_err());
+        state.current_date -= chrono::Duration::days(2);
+
+        let result = hold(&mut state);
+        assert_eq!(result.stop_out_yield, Some(0.045));
+        let paid = |dealer| result.awards.iter().find(|award| award.bidder == dealer).unwrap();
+        let (low, high) = (paid(dealers[0]), paid(dealers[1]));
+        assert_eq!((low.quantity, high.quantity), (5, 3));
+        assert!(low.amount / 5.0 > high.amount / 3.0, "The lower yield pays the higher price");
+    }
+
+    #[test]
+    fn test_linked_auction_indexes_bonds_to_the_cpi_at_issue() {
+        let (mut state, _) = auction_state(1);
+        let government_id = state.financial_system.government.id;
+        let auction_date = state.current_date + chrono::Duration::days(2);
+        let action = FiscalAction::AnnounceAuction {
+            government_id,
+            tenor: Tenor::T2Y,
+            inflation_linked: true,
+            offering: 5,
+            auction_date,
+            format: AuctionFormat::SinglePrice,
+        };
+        assert!(execute(&mut state, action).success);
+
+        let auction = &state.financial_system.treasury_auctions[0];
+        assert!(auction.inflation_linked);
+        let linker = auction.security(0.015, auction_date, 250.0);
+        assert_eq!(linker.inflation_linked, Some(InflationLinkage { base_cpi: 250.0 }));
+        assert_eq!(linker.index_ratio(275.0), 1.1);
+        assert_eq!(linker.coupon_rate, 0.015);
+    }
+
+    #[test]
+    fn test_government_steers_surplus_towards_balanced_budget() {
+        let mut state = SimState {
+            current_date: chrono::NaiveDate::from_ymd_opt(2026, 3, 1).unwrap(),
+            ..Default::default()
+        };
+        let bank_id = AgentId(Uuid::new_v4());
+        for _ in 0..10 {
+            let mut consumer = Consumer::new(40, bank_id, PersonalityArchetype::Balanced);
+            consumer.income = 12_000.0;
+            state.agents.consumers.insert(consumer.id, consumer);
+        }
+        let government_id = state.financial_system.government.id;
+        state.financial_system.balance_sheets.get_mut(&government_id).unwrap().income_statement.revenue = 1_000.0;
+
+        // A surplus of 12,000 a year against a balanced target: a quarter of it is closed, half by
+        // buying more and half by cutting the income tax rate
+        let model = BasicGovernmentDecisionModel::default();
+        let government = state.financial_system.government.clone();
+        let actions = model.steer_budget(&government, &state);
+        assert_eq!(actions.len(), 2);
+        for action in actions {
+            assert!(execute(&mut state, action).success);
+        }
+        let government = &state.financial_system.government;
+        assert!((government.spending_targets.level(&SpendingCategory::Purchases) - 1_500.0).abs() < 1e-9);
+        assert!((government.tax_rates.rate(&TaxType::Income) - 0.1875).abs() < 1e-9);
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+        let actions = model.decide(government, &state, &mut rng);
+        let closes = |action: &SimAction| matches!(action, SimAction::Fiscal(FiscalAction::CloseFiscalPeriod { .. }));
+        assert!(actions.iter().any(closes));
+
+        state.financial_system.government.fiscal_policy = FiscalPolicy::Automatic;
+        assert!(model.steer_budget(&state.financial_system.government, &state).is_empty());
+    }
+
+    #[test]
+    fn test_wages_are_paid_net_of_income_tax_withheld_under_the_brackets() {
+        let mut state = SimState::default();
+        let government_id = state.financial_system.government.id;
+        let rates = &state.financial_system.government.tax_rates;
+        // 20% above the 12,000 allowance, plus 10% above 100,000
+        assert_eq!(rates.income_tax_on(10_000.0), 0.0);
+        assert!((rates.income_tax_on(60_000.0) - 9_600.0).abs() < 1e-9);
+        assert!((rates.income_tax_on(130_000.0) - 26_600.0).abs() < 1e-9);
+
+        let bank_id = AgentId(Uuid::new_v4());
+        let mut firm = Firm::new(bank_id, "Test Firm".to_string(), None, 62.5);
+        let firm_id = firm.id;
+        let employee = AgentId(Uuid::new_v4());
+        let start_date = state.current_date;
+        let contract = EmploymentContract { employee_id: employee, wage_rate: 62.5, hours: 40.0, start_date };
+        firm.employees.insert(employee, contract);
+        state.agents.firms.insert(firm_id, firm);
+        for id in [bank_id, firm_id, employee] {
+            state.financial_system.balance_sheets.insert(id, BalanceSheet::new(id));
+        }
+        let deposit = deposit!(firm_id, bank_id, 5_000.0, 0.0, state.current_date);
+        state.financial_system.create_instrument(deposit).unwrap();
+
+        let action = BankingAction::PayPayroll { agent_id: firm_id };
+        let result = crate::banking::BankingDomain::new().execute(&action, &state);
+        assert!(result.success, "{:?}", result.errors);
+        state.apply_effects(&result.effects).unwrap();
+
+        // 130,000 a year owes 26,600, or 511.54 a week, and the week's pay 2% in contributions
+        let withheld = 26_600.0 / 52.0 + 50.0;
+        let fs = &state.financial_system;
+        assert!((fs.get_liquid_assets(&firm_id) - 2_500.0).abs() < 1e-6);
+        assert!((fs.get_liquid_assets(&employee) - (2_500.0 - withheld)).abs() < 1e-6);
+        assert!((fs.get_liquid_assets(&government_id) - withheld).abs() < 1e-6);
+        assert!((fs.get_bs_by_id(&government_id).unwrap().income_statement.revenue - withheld).abs() < 1e-6);
+        assert_eq!(fs.get_bs_by_id(&firm_id).unwrap().income_statement.operating_expenses, 2_500.0);
+        let tax_payment = state.history.transactions.iter().find_map(|tx| match tx.tx_type {
+            TransactionType::TaxPayment { payer, tax_type, period } => Some((payer, tax_type, period)),
+            _ => None,
+        });
+        assert_eq!(tax_payment, Some((employee, TaxType::Income, state.current_date)));
+        let contributed = |tx: &&Transaction| {
+            matches!(tx.tx_type, TransactionType::TaxPayment { tax_type: TaxType::PayrollContribution, .. })
+        };
+        assert_eq!(state.history.transactions.iter().filter(contributed).count(), 1);
+    }
+
+    #[test]
+    fn test_consumers_pay_consumption_tax_on_top_of_the_price() {
+        let mut state = SimState::default();
+        let cb_id = state.financial_system.central_bank.id;
+        let government_id = state.financial_system.government.id;
+        let good_id = GoodId(Uuid::new_v4());
+        let bank_id = AgentId(Uuid::new_v4());
+        let consumer = Consumer::new(40, bank_id, PersonalityArchetype::Balanced);
+        let (consumer_id, seller, firm_buyer) = (consumer.id, AgentId(Uuid::new_v4()), AgentId(Uuid::new_v4()));
+        state.agents.consumers.insert(consumer_id, consumer);
+        for (id, cash) in [(consumer_id, 1_100.0), (seller, 0.0), (firm_buyer, 1_000.0)] {
+            state.financial_system.balance_sheets.insert(id, BalanceSheet::new(id));
+            if cash > 0.0 {
+                state.financial_system.create_instrument(cash!(id, cash, cb_id, state.current_date)).unwrap();
+            }
+        }
+        let stock = InventoryEffect::AddInventory { owner: seller, good_id, quantity: 20.0, unit_cost: 60.0 };
+        state.apply_effects(&[StateEffect::Inventory(stock)]).unwrap();
+
+        let trading = crate::trading::TradingDomain::new();
+        for buyer in [consumer_id, firm_buyer] {
+            let trade = Trade { market_id: MarketId::Goods(good_id), buyer, seller, quantity: 10.0, price: 100.0 };
+            let result = trading.settle_trade(&trade, &state);
+            assert!(result.success, "{:?}", result.errors);
+            state.apply_effects(&result.effects).unwrap();
+        }
+
+        // The consumer pays 10% on top; the firm buys its input free of tax
+        let fs = &state.financial_system;
+        assert!(fs.get_liquid_assets(&consumer_id).abs() < 1e-6);
+        assert!(fs.get_liquid_assets(&firm_buyer).abs() < 1e-6);
+        assert!((fs.get_liquid_assets(&seller) - 2_000.0).abs() < 1e-6);
+        assert!((fs.get_liquid_assets(&government_id) - 100.0).abs() < 1e-6);
+        let taxed = |tx: &&Transaction| match tx.tx_type {
+            TransactionType::TaxPayment { payer, tax_type, .. } => {
+                payer == consumer_id && tax_type == TaxType::Consumption
+            },
+            _ => false,
+        };
+        assert_eq!(state.history.transactions.iter().filter(taxed).count(), 1);
+    }
+
+    #[test]
+    fn test_government_pays_benefits_and_investment_grants_within_its_cash() {
+        let mut state = SimState {
+            current_date: chrono::NaiveDate::from_ymd_opt(2026, 1, 5).unwrap(),
+            ..Default::default()
+        };
+        let cb_id = state.financial_system.central_bank.id;
+        let government_id = state.financial_system.government.id;
+        let bank = Bank::new("Bank".to_string(), 0.0, 0.0);
+        let bank_id = bank.id;
+        state.agents.banks.insert(bank_id, bank);
+        let firm = Firm::new(bank_id, "Firm".to_string(), None, 20.0);
+        let firm_id = firm.id;
+        state.agents.firms.insert(firm_id, firm);
+        let (jobless, earner) = (AgentId(Uuid::new_v4()), AgentId(Uuid::new_v4()));
+        for (id, income) in [(jobless, 0.0), (earner, 60_000.0)] {
+            let mut consumer = Consumer::new(40, bank_id, PersonalityArchetype::Balanced);
+            consumer.id = id;
+            consumer.income = income;
+            state.agents.consumers.insert(id, consumer);
+        }
+        for id in [bank_id, firm_id, jobless, earner] {
+            state.financial_system.balance_sheets.insert(id, BalanceSheet::new(id));
+        }
+        let account = deposit!(government_id, cb_id, 1_000.0, 0.0, state.current_date);
+        state.financial_system.create_instrument(account).unwrap();
+        let targets = &mut state.financial_system.government.spending_targets;
+        targets.transfers = 52_000.0;
+        targets.investment = 12_000.0;
+
+        // A week's transfers and a month's investment come to 2,000 against 1,000 of cash: half is paid
+        let model = BasicGovernmentDecisionModel::default();
+        let government = state.financial_system.government.clone();
+        let actions = model.spend(&government, &state);
+        let [SimAction::Fiscal(action)] = actions.as_slice() else { panic!("{:?}", actions) };
+        let result = execute(&mut state, action.clone());
+        assert!(result.success, "{:?}", result.errors);
+
+        // A quarter of the transfers goes to both consumers, the means-tested rest to the one without income
+        let fs = &state.financial_system;
+        assert!((fs.get_liquid_assets(&jobless) - 437.5).abs() < 1e-6);
+        assert!((fs.get_liquid_assets(&earner) - 62.5).abs() < 1e-6);
+        assert!((fs.get_liquid_assets(&firm_id) - 500.0).abs() < 1e-6);
+        assert!(fs.get_liquid_assets(&government_id).abs() < 1e-6);
+        assert_eq!(fs.get_bs_by_id(&government_id).unwrap().income_statement.operating_expenses, 1_000.0);
+        let firm = &state.agents.firms[&firm_id];
+        assert_eq!(firm.capital_stock, 10_500.0);
+        assert!((firm.productivity - (1.0 + 0.3 * 500.0 / 10_000.0)).abs() < 1e-12);
+        let benefits = state.history.transactions.iter().filter(|tx| {
+            matches!(tx.tx_type, TransactionType::GovernmentPayment { purpose: SpendingPurpose::Benefit(_), .. })
+        });
+        assert_eq!(benefits.count(), 3);
+
+        // With the account empty nothing more can be paid
+        assert!(!execute(&mut state, action.clone()).success);
+    }
+
+    #[test]
+    fn test_unemployment_insurance_pays_laid_off_workers_with_enough_work_behind_them() {
+        let monday = chrono::NaiveDate::from_ymd_opt(2026, 6, 1).unwrap();
+        let mut state = SimState { current_date: monday, ..Default::default() };
+        let cb_id = state.financial_system.central_bank.id;
+        let government_id = state.financial_system.government.id;
+        let bank_id = AgentId(Uuid::new_v4());
+        let mut firm = Firm::new(bank_id, "Firm".to_string(), None, 20.0);
+        let firm_id = firm.id;
+        // Twenty-one weeks in the job qualifies; two weeks does not
+        let (veteran, newcomer) = (AgentId(Uuid::new_v4()), AgentId(Uuid::new_v4()));
+        for (id, weeks) in [(veteran, 21), (newcomer, 2)] {
+            let start_date = monday - chrono::Duration::weeks(weeks);
+            let contract = EmploymentContract { employee_id: id, wage_rate: 20.0, hours: 40.0, start_date };
+            firm.employees.insert(id, contract);
+            let consumer = Consumer::new(40, bank_id, PersonalityArchetype::Balanced);
+            let consumer = Consumer { id, employed_by: Some(firm_id), ..consumer };
+            state.agents.consumers.insert(id, consumer);
+            state.financial_system.balance_sheets.insert(id, BalanceSheet::new(id));
+        }
+        state.agents.firms.insert(firm_id, firm);
+        state.financial_system.create_instrument(cash!(government_id, 10_000.0, cb_id, monday)).unwrap();
+
+        let model = BasicGovernmentDecisionModel::default();
+        let government = state.financial_system.government.clone();
+        // While both work the scheme runs a surplus of 2% of the 1,600 weekly payroll
+        assert!((model.insurance_deficit(&government, &state) + 32.0 * 52.0).abs() < 1e-6);
+
+        let layoffs: Vec<StateEffect> = [veteran, newcomer]
+            .into_iter()
+            .map(|consumer_id| StateEffect::Agent(AgentEffect::TerminateEmployment { firm_id, consumer_id }))
+            .collect();
+        state.apply_effects(&layoffs).unwrap();
+        let history = &state.agents.consumers[&veteran].employment_history;
+        assert_eq!(history.len(), 1);
+        assert_eq!((history[0].weekly_wage, history[0].end_date), (800.0, monday));
+
+        let actions = model.spend(&government, &state);
+        let [SimAction::Fiscal(action)] = actions.as_slice() else { panic!("{:?}", actions) };
+        let result = execute(&mut state, action.clone());
+        assert!(result.success, "{:?}", result.errors);
+        let fs = &state.financial_system;
+        assert!((fs.get_liquid_assets(&veteran) - 400.0).abs() < 1e-6);
+        assert!(fs.get_liquid_assets(&newcomer).abs() < 1e-6);
+        assert!((model.insurance_deficit(&government, &state) - 400.0 * 52.0).abs() < 1e-6);
+
+        // The claim runs out after 26 weeks
+        state.current_date = monday + chrono::Duration::weeks(25);
+        assert_eq!(model.unemployment_benefits(&government, &state).len(), 1);
+        state.current_date = monday + chrono::Duration::weeks(26);
+        assert!(model.unemployment_benefits(&government, &state).is_empty());
+    }
+}
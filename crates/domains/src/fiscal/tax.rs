@@ -0,0 +1,98 @@
+use crate::banking::{BankingDomain, BankingResult};
+use sim_core::*;
+
+/// Weeks in a year, for annualizing weekly pay against the income tax schedule.
+const PAY_PERIODS_PER_YEAR: f64 = 52.0;
+
+/// Tax owed by `taxpayer` on a payment, paid to the government out of the payment's funds.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Levy {
+    pub taxpayer: AgentId,
+    pub tax_type: TaxType,
+    pub amount: f64,
+}
+
+impl Levy {
+    /// Tax of `tax_type` at its flat rate on `base`. Nothing is due on a base of zero or less, or from
+    /// the government and the central bank.
+    pub fn assess(taxpayer: AgentId, tax_type: TaxType, base: f64, state: &SimState) -> Option<Levy> {
+        let rate = state.financial_system.government.tax_rates.rate(&tax_type);
+        Self::due(taxpayer, tax_type, base * rate, state)
+    }
+
+    /// Income tax withheld from a week's pay: the tax on a year at that wage under the bracket
+    /// schedule, spread over the year's pay periods.
+    pub fn withholding(employee: AgentId, weekly_wage: f64, state: &SimState) -> Option<Levy> {
+        let rates = &state.financial_system.government.tax_rates;
+        let amount = rates.income_tax_on(weekly_wage * PAY_PERIODS_PER_YEAR) / PAY_PERIODS_PER_YEAR;
+        Self::due(employee, TaxType::Income, amount.min(weekly_wage), state)
+    }
+
+    /// The unemployment insurance contribution withheld from a week's pay.
+    pub fn contribution(employee: AgentId, weekly_wage: f64, state: &SimState) -> Option<Levy> {
+        Self::assess(employee, TaxType::PayrollContribution, weekly_wage, state)
+    }
+
+    /// Consumption tax on `buyer`'s purchase of goods worth `value`.
+    pub fn on_purchase(buyer: AgentId, value: f64, state: &SimState) -> Option<Levy> {
+        Self::due(buyer, TaxType::Consumption, value * consumption_tax_rate(buyer, state), state)
+    }
+
+    /// Capital gains tax on `seller`'s sale of `units` from `holding` at `price`. The gain is measured
+    /// against the seller's average cost in the holding's market, or the holding's book value where
+    /// it has no recorded purchases. Losses are not taxed.
+    pub fn on_sale(
+        seller: AgentId, holding: &FinancialInstrument, units: u64, price: f64, state: &SimState,
+    ) -> Option<Levy> {
+        let fs = &state.financial_system;
+        let book_value = holding.principal / holding.quantity().filter(|&held| held > 0)? as f64;
+        let cost =
+            fs.holding_market(holding).and_then(|market_id| fs.average_cost(&seller, &market_id)).unwrap_or(book_value);
+        Self::assess(seller, TaxType::CapitalGains, (price - cost) * units as f64, state)
+    }
+
+    fn due(taxpayer: AgentId, tax_type: TaxType, amount: f64, state: &SimState) -> Option<Levy> {
+        let fs = &state.financial_system;
+        let exempt = taxpayer == fs.government.id || taxpayer == fs.central_bank.id;
+        (!exempt && amount > 1e-6).then_some(Levy { taxpayer, tax_type, amount })
+    }
+
+    pub fn transaction_type(&self, state: &SimState) -> TransactionType {
+        TransactionType::TaxPayment { payer: self.taxpayer, tax_type: self.tax_type, period: state.current_date }
+    }
+
+    /// Books the tax as the government's revenue.
+    pub fn revenue(&self, state: &SimState) -> StateEffect {
+        StateEffect::Financial(FinancialEffect::RecordIncome {
+            agent_id: state.financial_system.government.id,
+            line: IncomeLine::Revenue,
+            amount: self.amount,
+        })
+    }
+}
+
+/// The consumption tax rate `buyer` pays on goods. Only consumers pay it: firms buy their inputs free
+/// of tax, as under a VAT where businesses reclaim the tax on what they buy.
+pub fn consumption_tax_rate(buyer: AgentId, state: &SimState) -> f64 {
+    if state.agents.consumers.contains_key(&buyer) {
+        state.financial_system.government.tax_rates.consumption_tax
+    } else {
+        0.0
+    }
+}
+
+/// Makes `payments` from `from`, each a payee, amount and transaction type, and pays the `levies` to
+/// the government out of the same funds. Each tax is recorded as a `TaxPayment` and booked as revenue.
+/// Nothing is paid unless `from` can fund them all.
+pub fn pay_with_levy(
+    router: &BankingDomain, from: AgentId, mut payments: Vec<(AgentId, f64, TransactionType)>,
+    instrument_id: Option<InstrumentId>, levies: &[Levy], state: &SimState,
+) -> BankingResult {
+    let government = state.financial_system.government.id;
+    payments.extend(levies.iter().map(|levy| (government, levy.amount, levy.transaction_type(state))));
+    let mut result = router.execute_split_payment(from, &payments, instrument_id, state);
+    if result.success {
+        result.effects.extend(levies.iter().map(|levy| levy.revenue(state)));
+    }
+    result
+}
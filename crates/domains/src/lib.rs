@@ -35,8 +35,11 @@ pub struct DomainRegistration {
 inventory::collect!(DomainRegistration);
 
 pub mod banking;
+pub mod clearing;
 pub mod consumption;
+pub mod credit;
 pub mod fiscal;
+pub mod monetary;
 pub mod prelude;
 pub mod production;
 pub mod settlement;
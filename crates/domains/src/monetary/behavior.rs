@@ -0,0 +1,94 @@
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sim_core::*;
+use std::any::Any;
+
+/// Sets the policy rate by a `TaylorRule` at each meeting on its `MeetingCalendar`, and runs its
+/// `AssetPurchaseProgram` through daily Treasury auctions. Meetings and auctions fall on business days
+/// only, and there is no meeting until there is enough CPI history to measure inflation.
+#[derive(Clone, Debug, Serialize, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct TaylorRuleDecisionModel {
+    pub rule: TaylorRule,
+    pub meetings: MeetingCalendar,
+    pub asset_purchases: AssetPurchaseProgram,
+}
+
+#[typetag::serde]
+impl DecisionModel for TaylorRuleDecisionModel {
+    fn decide(&self, agent: &dyn Any, state: &SimState, _rng: &mut dyn RngCore) -> Vec<SimAction> {
+        let central_bank = match agent.downcast_ref::<CentralBank>() {
+            Some(cb) => cb,
+            None => return vec![],
+        };
+        let calendar = &state.financial_system.calendar;
+        if !calendar.is_business_day(state.current_date) {
+            return vec![];
+        }
+
+        let mut actions = vec![];
+        let last_meeting = state.history.policy_decisions.last().map(|decision| decision.date);
+        if self.meetings.is_meeting(state.current_date, last_meeting, calendar)
+            && let Some(inflation) = state.history.annual_inflation(state.current_date)
+        {
+            let unemployment = state.unemployment_rate();
+            let prescribed_rate = self.rule.prescribe(inflation, unemployment);
+            let decision = PolicyDecision {
+                date: state.current_date,
+                previous_rate: central_bank.policy_rate,
+                new_rate: self.rule.next_rate(central_bank.policy_rate, prescribed_rate),
+                prescribed_rate,
+                inflation,
+                unemployment,
+            };
+            actions.push(SimAction::Monetary(MonetaryAction::SetPolicyRate {
+                central_bank_id: central_bank.id,
+                decision,
+            }));
+        }
+        actions.extend(self.balance_sheet_operations(state).into_iter().map(|operation| {
+            SimAction::Monetary(MonetaryAction::OpenMarketOperation { central_bank_id: central_bank.id, operation })
+        }));
+        actions
+    }
+}
+
+impl TaylorRuleDecisionModel {
+    /// The day's auctions to move each targeted tenor towards its target: purchases up to the target,
+    /// and sales down to it if the runoff schedule sells. Prices are limited to the curve's price of the
+    /// tenor's benchmark issue, give or take the program's tolerance.
+    pub fn balance_sheet_operations(&self, state: &SimState) -> Vec<OpenMarketOperation> {
+        let fs = &state.financial_system;
+        let program = &self.asset_purchases;
+        program
+            .targets
+            .iter()
+            .filter_map(|target| {
+                let tenor = target.tenor;
+                let gap = program.target(&tenor, state.current_date)?
+                    - fs.treasuries_held(&fs.central_bank.id, &tenor) as f64;
+                let side = if gap >= 1.0 {
+                    OperationSide::Purchase
+                } else if gap <= -1.0 && program.sells_excess() {
+                    OperationSide::Sale
+                } else {
+                    return None;
+                };
+                let curve_price = BondDetails::benchmark(tenor, state.current_date)
+                    .pricer(state.current_date)
+                    .clean_price(fs.risk_free_rate(&tenor));
+                let limit_price = match side {
+                    OperationSide::Purchase => curve_price * (1.0 + program.price_tolerance),
+                    OperationSide::Sale => curve_price * (1.0 - program.price_tolerance),
+                };
+                Some(OpenMarketOperation {
+                    tenor,
+                    side,
+                    method: OperationMethod::Auction,
+                    quantity: gap.abs().min(program.max_daily_operation).floor(),
+                    limit_price,
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,292 @@
+use crate::banking::BankingDomain;
+use serde::{Deserialize, Serialize};
+use sim_core::*;
+use sim_macros::SimDomain;
+use std::collections::HashMap;
+
+#[derive(Clone, Debug, Serialize, Deserialize, SimDomain)]
+pub struct MonetaryDomain {
+    payment_router: BankingDomain,
+}
+
+#[derive(Debug, Clone)]
+pub struct MonetaryResult {
+    pub success: bool,
+    pub effects: Vec<StateEffect>,
+    pub errors: Vec<String>,
+}
+
+impl MonetaryDomain {
+    pub fn new() -> Self {
+        Self { payment_router: BankingDomain::new() }
+    }
+
+    pub fn can_handle(&self, action: &MonetaryAction) -> bool {
+        matches!(
+            action,
+            MonetaryAction::SetPolicyRate { .. }
+                | MonetaryAction::OpenMarketOperation { .. }
+                | MonetaryAction::AssessReserveCompliance
+        )
+    }
+
+    pub fn validate(&self, action: &MonetaryAction, state: &SimState) -> Result<(), String> {
+        let central_bank_id = action.agent_id();
+        let system_action = matches!(action, MonetaryAction::AssessReserveCompliance);
+        if !system_action && central_bank_id != state.financial_system.central_bank.id {
+            return Err(format!("Agent {} is not the central bank.", central_bank_id));
+        }
+        match action {
+            MonetaryAction::SetPolicyRate { decision, .. } => {
+                if !decision.new_rate.is_finite() {
+                    return Err(format!("Policy rate {} is not a number.", decision.new_rate));
+                }
+                Ok(())
+            }
+            MonetaryAction::OpenMarketOperation { operation, .. } => {
+                Validator::positive_amount(operation.quantity)?;
+                Validator::positive_amount(operation.limit_price)?;
+                let market_id = FinancialMarketId::Treasury { tenor: operation.tenor };
+                if state.financial_system.exchange.financial_market(&market_id).is_none() {
+                    return Err(format!("No Treasury market for {}.", operation.tenor));
+                }
+                if operation.side == OperationSide::Sale {
+                    market_id.check_holdings(&central_bank_id, operation.quantity, &state.financial_system)?;
+                }
+                Ok(())
+            }
+            MonetaryAction::AssessReserveCompliance => Ok(()),
+        }
+    }
+
+    pub fn execute(&self, action: &MonetaryAction, state: &SimState) -> MonetaryResult {
+        if let Err(error) = self.validate(action, state) {
+            return MonetaryResult { success: false, effects: vec![], errors: vec![error] };
+        }
+
+        match action {
+            MonetaryAction::SetPolicyRate { decision, .. } => {
+                println!(
+                    "[MONETARY DOMAIN] Policy rate {:.2}% -> {:.2}% (rule {:.2}%, inflation {:.2}%, unemployment {})",
+                    decision.previous_rate * 100.0,
+                    decision.new_rate * 100.0,
+                    decision.prescribed_rate * 100.0,
+                    decision.inflation * 100.0,
+                    decision.unemployment.map_or("n/a".to_string(), |rate| format!("{:.2}%", rate * 100.0))
+                );
+                let effect = StateEffect::Financial(FinancialEffect::SetPolicyRate(decision.clone()));
+                MonetaryResult { success: true, effects: vec![effect], errors: vec![] }
+            }
+            MonetaryAction::OpenMarketOperation { central_bank_id, operation } => match operation.method {
+                OperationMethod::Outright => self.execute_outright(*central_bank_id, operation),
+                OperationMethod::Auction => self.execute_auction(*central_bank_id, operation, state),
+            },
+            MonetaryAction::AssessReserveCompliance => self.execute_assess_reserve_compliance(state),
+        }
+    }
+
+    /// Closes the maintenance period once it has run `maintenance_period_days` since the last one
+    /// closed, or since the first settlement day. Each bank's end-of-day reserves and requirement are
+    /// averaged over the period's clearing reports; a bank short on average pays penalty interest on
+    /// the shortfall for the whole period, in reserves.
+    fn execute_assess_reserve_compliance(&self, state: &SimState) -> MonetaryResult {
+        let fs = &state.financial_system;
+        let central_bank = &fs.central_bank;
+        let last_period_end = state.history.reserve_compliance.last().map(|report| report.period_end);
+        let reports: Vec<&ClearingReport> = state
+            .history
+            .clearing_reports
+            .iter()
+            .filter(|report| last_period_end.is_none_or(|end| report.date > end) && report.date <= state.current_date)
+            .collect();
+        let Some(first_report) = reports.first() else {
+            return MonetaryResult { success: true, effects: vec![], errors: vec![] };
+        };
+        let period_start = last_period_end.map_or(first_report.date, |end| end + chrono::Duration::days(1));
+        let days = (state.current_date - period_start).num_days() + 1;
+        if days < central_bank.maintenance_period_days.max(1) as i64 {
+            return MonetaryResult { success: true, effects: vec![], errors: vec![] };
+        }
+
+        let mut banks: Vec<AgentId> = vec![];
+        let mut totals: HashMap<AgentId, (f64, f64, u32)> = HashMap::new();
+        for position in reports.iter().flat_map(|report| &report.positions) {
+            let (reserves, required, observed) = totals.entry(position.bank_id).or_insert_with(|| {
+                banks.push(position.bank_id);
+                (0.0, 0.0, 0)
+            });
+            *reserves += position.reserves_after;
+            *required += position.required_reserves;
+            *observed += 1;
+        }
+
+        let penalty_rate = central_bank.reserve_penalty_rate();
+        let mut effects = vec![];
+        for bank_id in banks {
+            let (reserves, required, observed) = totals[&bank_id];
+            let average_reserves = reserves / observed as f64;
+            let average_required = required / observed as f64;
+            let shortfall = (average_required - average_reserves).max(0.0);
+            let penalty = shortfall * penalty_rate * days as f64 / 360.0;
+            if penalty > 1e-6 {
+                let (from, to) = (bank_id, central_bank.id);
+                let payment = self.payment_router.execute_payment(
+                    from,
+                    to,
+                    penalty,
+                    TransactionType::InterestPayment { payer: from, receiver: to, amount: penalty },
+                    None,
+                    state,
+                );
+                effects.extend(payment.effects);
+                if payment.success {
+                    effects.push(StateEffect::Financial(FinancialEffect::RecordIncome {
+                        agent_id: bank_id,
+                        line: IncomeLine::InterestExpense,
+                        amount: penalty,
+                    }));
+                }
+                println!(
+                    "[MONETARY DOMAIN] Bank {} averaged ${:.2} of reserves against ${:.2} required: penalty ${:.2}",
+                    bank_id, average_reserves, average_required, penalty
+                );
+            }
+            effects.push(StateEffect::Financial(FinancialEffect::RecordReserveCompliance(ReserveComplianceReport {
+                bank_id,
+                period_start,
+                period_end: state.current_date,
+                days: days as u32,
+                average_reserves,
+                average_required,
+                shortfall,
+                penalty_rate,
+                penalty,
+            })));
+        }
+        MonetaryResult { success: true, effects, errors: vec![] }
+    }
+
+    /// Posts the operation as a limit order on the Treasury book, where it clears with everyone else's.
+    fn execute_outright(&self, central_bank_id: AgentId, operation: &OpenMarketOperation) -> MonetaryResult {
+        let (agent_id, quantity, price) = (central_bank_id, operation.quantity, operation.limit_price);
+        let order = match operation.side {
+            OperationSide::Purchase => Order::Bid(Bid { agent_id, quantity, price }),
+            OperationSide::Sale => Order::Ask(Ask { agent_id, quantity, price }),
+        };
+        let market_id = MarketId::Financial(FinancialMarketId::Treasury { tenor: operation.tenor });
+        let effect = StateEffect::Market(MarketEffect::PlaceOrderInBook { market_id, order });
+        MonetaryResult { success: true, effects: vec![effect], errors: vec![] }
+    }
+
+    /// Fills the operation from the dealers' side of the Treasury book, best price first and each order
+    /// at its own price, as far as the limit price allows. Bonds and reserves change hands at once. Each
+    /// dealer is filled on its best order only, up to the bonds it holds or, when the central bank is
+    /// selling, what it can pay for.
+    fn execute_auction(
+        &self, central_bank_id: AgentId, operation: &OpenMarketOperation, state: &SimState,
+    ) -> MonetaryResult {
+        let fs = &state.financial_system;
+        let market_id = FinancialMarketId::Treasury { tenor: operation.tenor };
+        let book = &fs.exchange.financial_market(&market_id).expect("validated").order_book;
+        let mut orders: Vec<(Order, AgentId, f64, f64)> = match operation.side {
+            OperationSide::Purchase => book
+                .asks
+                .iter()
+                .filter(|ask| ask.price <= operation.limit_price)
+                .map(|ask| (Order::Ask(ask.clone()), ask.agent_id, ask.price, ask.quantity))
+                .collect(),
+            OperationSide::Sale => book
+                .bids
+                .iter()
+                .filter(|bid| bid.price >= operation.limit_price)
+                .map(|bid| (Order::Bid(bid.clone()), bid.agent_id, bid.price, bid.quantity))
+                .collect(),
+        };
+        orders.sort_by(|a, b| match operation.side {
+            OperationSide::Purchase => a.2.total_cmp(&b.2),
+            OperationSide::Sale => b.2.total_cmp(&a.2),
+        });
+
+        let mut central_bank_lots = fs.treasury_lots(&central_bank_id, &operation.tenor);
+        let mut dealers_filled = vec![];
+        let mut remaining = operation.quantity.floor() as u64;
+        let (mut filled, mut turnover) = (0, 0.0);
+        let mut effects = vec![];
+        for (order, dealer, price, quantity) in orders {
+            if remaining == 0 {
+                break;
+            }
+            if dealer == central_bank_id || dealers_filled.contains(&dealer) {
+                continue;
+            }
+            let (seller, buyer) = match operation.side {
+                OperationSide::Purchase => (dealer, central_bank_id),
+                OperationSide::Sale => (central_bank_id, dealer),
+            };
+            let mut dealer_lots = fs.treasury_lots(&dealer, &operation.tenor);
+            let lots = match operation.side {
+                OperationSide::Purchase => &mut dealer_lots,
+                OperationSide::Sale => &mut central_bank_lots,
+            };
+            let held: u64 = lots.iter().map(|(_, bonds)| bonds).sum();
+            let affordable = (self.payment_router.available_funds(buyer, state) / price).floor() as u64;
+            let units = remaining.min(quantity.floor() as u64).min(held).min(affordable);
+            if units == 0 {
+                continue;
+            }
+
+            let amount = units as f64 * price;
+            let payment = self.payment_router.execute_payment(
+                buyer,
+                seller,
+                amount,
+                TransactionType::Transfer { from: buyer, to: seller, amount },
+                lots.first().map(|(id, _)| *id),
+                state,
+            );
+            if !payment.success {
+                continue;
+            }
+            let mut to_deliver = units;
+            for (id, bonds) in lots.iter_mut().filter(|(_, bonds)| *bonds > 0) {
+                let delivered = to_deliver.min(*bonds);
+                effects.push(StateEffect::Financial(FinancialEffect::SplitAndTransferInstrument {
+                    id: *id,
+                    buyer,
+                    quantity: delivered,
+                }));
+                *bonds -= delivered;
+                to_deliver -= delivered;
+                if to_deliver == 0 {
+                    break;
+                }
+            }
+            effects.extend(payment.effects);
+            effects.push(StateEffect::Market(MarketEffect::FillOrder {
+                market_id: MarketId::Financial(market_id.clone()),
+                order,
+                quantity: units as f64,
+            }));
+            dealers_filled.push(dealer);
+            remaining -= units;
+            filled += units;
+            turnover += amount;
+        }
+
+        println!(
+            "[MONETARY DOMAIN] {:?} auction in {}: {} of {} bonds at an average {:.2}",
+            operation.side,
+            operation.tenor,
+            filled,
+            operation.quantity,
+            if filled > 0 { turnover / filled as f64 } else { 0.0 }
+        );
+        MonetaryResult { success: true, effects, errors: vec![] }
+    }
+}
+
+impl Default for MonetaryDomain {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,273 @@
+//! # Monetary Domain Crate
+//!
+//! This crate holds the central bank's monetary policy: how it decides on the policy rate and how
+//! those decisions reach the financial system.
+//!
+//! ## Crate Structure and Purpose
+//!
+//! - **`domain.rs`**: Contains the `MonetaryDomain` struct, which validates and executes
+//!   `MonetaryAction`s.
+//!   - **`SetPolicyRate`**: Moves `CentralBank::policy_rate` and records the `PolicyDecision` in the
+//!     history. Everything priced off the policy rate, such as the lending facility, floating-rate
+//!     resets and new Treasury coupons, follows from the next tick.
+//!   - **`OpenMarketOperation`**: Buys or sells Treasuries against reserves. An outright operation posts
+//!     a limit order on the Treasury book. An auction fills at once from the orders already on the
+//!     book, best price first, each at its own price: the central bank pays with newly created reserves
+//!     and is paid by draining them. Either way the book, and so the yield curve read off it, moves.
+//!   - **`AssessReserveCompliance`**: Issued by the engine after each day's clearing. Once a maintenance
+//!     period has run, it averages every bank's end-of-day reserves and requirement over the period's
+//!     clearing reports, charges penalty interest on any shortfall and records a
+//!     `ReserveComplianceReport` per bank. Only demand and savings deposits are reservable.
+//!
+//! - **`behavior.rs`**: Contains the `TaylorRuleDecisionModel`. At each meeting on its
+//!   `MeetingCalendar` it reads CPI inflation and the unemployment rate, asks its `TaylorRule` for a
+//!   rate, smooths towards it by the rule's inertia and floors it at the effective lower bound. It
+//!   does not meet until there are four months of CPI history to measure inflation over. Every
+//!   business day it also auctions towards the Treasury holdings its `AssetPurchaseProgram` targets,
+//!   buying for QE and, once a runoff schedule has shrunk the targets, letting bonds mature or selling
+//!   them for QT. The resulting `CentralBankBalanceSheet` is recorded in the history each day.
+//!
+//! ## Key Components
+//!
+//! - **`MonetaryDomain`**: The service for executing central bank actions.
+//! - **`TaylorRuleDecisionModel`**: The logic controller for the central bank agent.
+//! - **`TaylorRule`**, **`MeetingCalendar`** and **`PolicyDecision`**: The reaction function, the
+//!   meeting schedule and the decision log (defined in `sim_types`).
+//! - **`AssetPurchaseProgram`** and **`RunoffSchedule`**: Target holdings per tenor and how they shrink
+//!   (defined in `sim_types`).
+pub mod behavior;
+pub mod domain;
+
+pub use behavior::*;
+pub use domain::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use sim_core::*;
+    use uuid::Uuid;
+
+    fn state_with_unemployment(employed: usize, unemployed: usize) -> SimState {
+        let mut state = SimState::default();
+        let bank_id = AgentId(Uuid::new_v4());
+        let start_date = state.current_date - chrono::Duration::days(365);
+        for i in 0..employed + unemployed {
+            let mut consumer = Consumer::new(40, bank_id, PersonalityArchetype::Balanced);
+            if i < employed {
+                consumer.employed_by = Some(AgentId(Uuid::new_v4()));
+            } else {
+                let employer = AgentId(Uuid::new_v4());
+                let end_date = state.current_date;
+                consumer.employment_history.push(PastEmployment { employer, weekly_wage: 800.0, start_date, end_date });
+            }
+            state.agents.consumers.insert(consumer.id, consumer);
+        }
+        // Someone who has never worked is not in the labour force
+        let retiree = Consumer::new(70, bank_id, PersonalityArchetype::Balanced);
+        state.agents.consumers.insert(retiree.id, retiree);
+        state
+    }
+
+    fn decide(model: &TaylorRuleDecisionModel, state: &SimState) -> Vec<SimAction> {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+        model.decide(&state.financial_system.central_bank, state, &mut rng)
+    }
+
+    #[test]
+    fn test_taylor_rule_smooths_and_respects_lower_bound() {
+        let rule = TaylorRule::default();
+        // At target inflation and natural unemployment the rule prescribes the neutral nominal rate
+        assert!((rule.prescribe(0.02, Some(0.045)) - 0.03).abs() < 1e-12);
+        // Inflation a point above target raises the prescription by 1.5 points
+        assert!((rule.prescribe(0.03, Some(0.045)) - 0.045).abs() < 1e-12);
+        // Without a labour force there is no unemployment gap
+        assert!((rule.prescribe(0.02, None) - 0.03).abs() < 1e-12);
+        assert!((rule.next_rate(0.05, 0.03) - 0.046).abs() < 1e-12);
+        let slump = rule.prescribe(0.0, Some(0.15));
+        assert!(slump < 0.0);
+        assert_eq!(TaylorRule { inertia: 0.0, ..rule }.next_rate(0.01, slump), 0.0);
+    }
+
+    #[test]
+    fn test_central_bank_cuts_when_unemployment_is_high() {
+        let mut state = state_with_unemployment(8, 2);
+        state.current_date = chrono::NaiveDate::from_ymd_opt(2026, 1, 5).unwrap();
+        assert_eq!(state.unemployment_rate(), Some(0.2));
+        let model = TaylorRuleDecisionModel::default();
+        assert!(decide(&model, &state).is_empty(), "No meeting before inflation can be measured");
+
+        // Four months of flat prices is enough history to meet on
+        for days in 0..120 {
+            let date = state.current_date - chrono::Duration::days(120 - days);
+            state.history.cpi.push((date, 100.0));
+        }
+        let actions = decide(&model, &state);
+        assert_eq!(actions.len(), 1);
+
+        let SimAction::Monetary(action) = &actions[0] else { panic!("expected a monetary action") };
+        let result = MonetaryDomain::new().execute(action, &state);
+        assert!(result.success, "{:?}", result.errors);
+        state.apply_effects(&result.effects).unwrap();
+
+        let decision = state.history.policy_decisions.last().unwrap().clone();
+        assert_eq!((decision.inflation, decision.unemployment, decision.previous_rate), (0.0, Some(0.2), 0.043));
+        assert!(decision.new_rate < decision.previous_rate);
+        assert_eq!(state.financial_system.central_bank.policy_rate, decision.new_rate);
+
+        // The committee does not meet again until the interval has passed
+        let next_meeting = decision.date + chrono::Duration::days(45);
+        while state.current_date < next_meeting {
+            assert!(decide(&model, &state).is_empty());
+            state.history.cpi.push((state.current_date, 100.0));
+            state.current_date += chrono::Duration::days(1);
+        }
+        assert_eq!(decide(&model, &state).len(), 1);
+    }
+
+    #[test]
+    fn test_meeting_on_a_holiday_moves_to_next_business_day() {
+        let us = RuleCalendar::us_settlement();
+        let date = |m, d| chrono::NaiveDate::from_ymd_opt(2026, m, d).unwrap();
+        // Independence Day 2026 is observed on Friday 3 July
+        let meetings = MeetingCalendar { dates: vec![date(7, 3)], ..Default::default() };
+        assert!(!meetings.is_meeting(date(7, 2), None, &us));
+        assert!(meetings.is_meeting(date(7, 6), None, &us));
+        assert!(!meetings.is_meeting(date(7, 7), None, &us));
+    }
+
+    fn hold_treasuries(state: &mut SimState, holder: AgentId, tenor: Tenor, bonds: u64) {
+        let government_id = state.financial_system.government.id;
+        let maturity = tenor.add_to_date(state.current_date);
+        let date = state.current_date;
+        let mut treasury =
+            bond!(holder, government_id, 1000.0, 0.04, maturity, 1000.0, BondType::Government, 2, tenor, date);
+        treasury.set_quantity(bonds);
+        state.financial_system.create_instrument(treasury).unwrap();
+    }
+
+    #[test]
+    fn test_reverse_auction_buys_cheapest_offers_against_reserves() {
+        let mut state = SimState::default();
+        let cb_id = state.financial_system.central_bank.id;
+        let market_id = FinancialMarketId::Treasury { tenor: Tenor::T10Y };
+        state.financial_system.exchange.register_financial_market(market_id.clone());
+        let dealers: Vec<AgentId> = (0..2).map(|_| AgentId(Uuid::new_v4())).collect();
+        for (i, (dealer, price)) in dealers.iter().zip([990.0, 1010.0]).enumerate() {
+            state.agents.banks.insert(*dealer, Bank::new(format!("Dealer {}", i), 0.0, 0.0));
+            state.financial_system.balance_sheets.insert(*dealer, BalanceSheet::new(*dealer));
+            hold_treasuries(&mut state, *dealer, Tenor::T10Y, 10);
+            let ask = Ask { agent_id: *dealer, price, quantity: 5.0 };
+            state.apply_effects(&[StateEffect::Market(MarketEffect::PlaceOrderInBook {
+                market_id: MarketId::Financial(market_id.clone()),
+                order: Order::Ask(ask),
+            })]).unwrap();
+        }
+
+        let operation = OpenMarketOperation {
+            tenor: Tenor::T10Y,
+            side: OperationSide::Purchase,
+            method: OperationMethod::Auction,
+            quantity: 8.0,
+            limit_price: 1000.0,
+        };
+        let action = MonetaryAction::OpenMarketOperation { central_bank_id: cb_id, operation };
+        let result = MonetaryDomain::new().execute(&action, &state);
+        assert!(result.success, "{:?}", result.errors);
+        state.apply_effects(&result.effects).unwrap();
+        let settle = crate::clearing::ClearingDomain::new().execute(&ClearingAction::SettleNetPositions, &state);
+        state.apply_effects(&settle.effects).unwrap();
+
+        // Only the offer under the limit is taken, at its own price
+        let fs = &state.financial_system;
+        assert_eq!(fs.treasuries_held(&cb_id, &Tenor::T10Y), 5);
+        assert_eq!(fs.treasuries_held(&dealers[0], &Tenor::T10Y), 5);
+        assert!((fs.get_bank_reserves(&dealers[0]).unwrap() - 4950.0).abs() < 1e-6);
+        assert_eq!(fs.get_bank_reserves(&dealers[1]).unwrap(), 0.0);
+        let asks = &fs.exchange.financial_market(&market_id).unwrap().order_book.asks;
+        assert_eq!(asks.len(), 1);
+        assert_eq!(asks[0].agent_id, dealers[1]);
+
+        let balance_sheet = fs.central_bank_balance_sheet(state.current_date);
+        assert_eq!(balance_sheet.treasuries.len(), 1);
+        assert_eq!(balance_sheet.treasuries[0].quantity, 5);
+        assert!((balance_sheet.reserves - 4950.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_asset_purchases_buy_to_target_and_run_off_on_schedule() {
+        let date = |m, d| chrono::NaiveDate::from_ymd_opt(2026, m, d).unwrap();
+        let mut state = SimState { current_date: date(1, 5), ..Default::default() };
+        let cb_id = state.financial_system.central_bank.id;
+        let model = TaylorRuleDecisionModel {
+            asset_purchases: AssetPurchaseProgram {
+                targets: vec![HoldingTarget { tenor: Tenor::T10Y, quantity: 100.0 }],
+                max_daily_operation: 20.0,
+                runoff: Some(RunoffSchedule { start: date(2, 1), monthly_cap: 30.0, sell: true }),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let operations = model.balance_sheet_operations(&state);
+        assert_eq!(operations.len(), 1);
+        let purchase = &operations[0];
+        assert_eq!((purchase.side, purchase.method), (OperationSide::Purchase, OperationMethod::Auction));
+        assert_eq!(purchase.quantity, 20.0);
+
+        // Cuts on 1 February and 1 March leave a target of 40 against 50 held: the excess is sold off
+        state.current_date = date(3, 16);
+        hold_treasuries(&mut state, cb_id, Tenor::T10Y, 50);
+        assert_eq!(model.asset_purchases.target(&Tenor::T10Y, state.current_date), Some(40.0));
+        let operations = model.balance_sheet_operations(&state);
+        let sale = &operations[0];
+        assert_eq!((sale.side, sale.quantity), (OperationSide::Sale, 10.0));
+        assert!(sale.limit_price < purchase.limit_price);
+    }
+
+    #[test]
+    fn test_reserve_shortfall_over_maintenance_period_is_penalised() {
+        let mut state = SimState::default();
+        state.financial_system.central_bank.maintenance_period_days = 2;
+        let cb_id = state.financial_system.central_bank.id;
+        let banks: Vec<AgentId> = (0..2).map(|_| AgentId(Uuid::new_v4())).collect();
+        for (i, (bank_id, reserves)) in banks.iter().zip([50.0, 150.0]).enumerate() {
+            state.agents.banks.insert(*bank_id, Bank::new(format!("Bank {}", i), 0.0, 0.0));
+            state.financial_system.balance_sheets.insert(*bank_id, BalanceSheet::new(*bank_id));
+            let depositor = AgentId(Uuid::new_v4());
+            state.financial_system.balance_sheets.insert(depositor, BalanceSheet::new(depositor));
+            let deposit = deposit!(depositor, *bank_id, 1000.0, 0.0, state.current_date);
+            state.financial_system.create_instrument(deposit).unwrap();
+            let reserves = reserves!(*bank_id, cb_id, reserves, state.current_date);
+            state.financial_system.create_instrument(reserves).unwrap();
+        }
+        assert_eq!(state.financial_system.required_reserves(&banks[0]), 100.0);
+
+        let close_day = |state: &mut SimState| {
+            let clearing = crate::clearing::ClearingDomain::new().execute(&ClearingAction::SettleNetPositions, state);
+            state.apply_effects(&clearing.effects).unwrap();
+            let result = MonetaryDomain::new().execute(&MonetaryAction::AssessReserveCompliance, state);
+            assert!(result.success, "{:?}", result.errors);
+            state.apply_effects(&result.effects).unwrap();
+        };
+        close_day(&mut state);
+        assert!(state.history.reserve_compliance.is_empty(), "The period has a day to run");
+        state.current_date += chrono::Duration::days(1);
+        close_day(&mut state);
+
+        let reports = &state.history.reserve_compliance;
+        assert_eq!(reports.len(), 2);
+        let short = reports.iter().find(|report| report.bank_id == banks[0]).unwrap();
+        let expected = 50.0 * state.financial_system.central_bank.reserve_penalty_rate() * 2.0 / 360.0;
+        assert_eq!((short.days, short.shortfall), (2, 50.0));
+        assert!((short.penalty - expected).abs() < 1e-9);
+        assert!(reports.iter().find(|report| report.bank_id == banks[1]).unwrap().is_compliant());
+        let owed = state.financial_system.payment_system.net_positions()[&cb_id];
+        assert!((owed - expected).abs() < 1e-9, "The penalty is paid in reserves");
+
+        // The next period starts the day after this one closed
+        state.current_date += chrono::Duration::days(1);
+        close_day(&mut state);
+        assert_eq!(state.history.reserve_compliance.len(), 2);
+    }
+}
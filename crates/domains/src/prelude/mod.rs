@@ -1,8 +1,11 @@
 pub use super::{Domain, DomainRegistration, DomainResult};
 pub use crate::banking::{BankingDomain, BankingResult, BasicBankDecisionModel};
+pub use crate::clearing::{ClearingDomain, ClearingResult};
 pub use crate::consumption::{BasicConsumerDecisionModel, CESConsumerDecisionModel, ConsumptionDomain, ConsumptionResult, ParametricMPC};
+pub use crate::credit::{CreditDomain, CreditResult, RatingAgency};
 pub use crate::fiscal::{BasicGovernmentDecisionModel, FiscalDomain, FiscalResult};
+pub use crate::monetary::{MonetaryDomain, MonetaryResult, TaylorRuleDecisionModel};
 pub use crate::production::{BasicFirmDecisionModel, ProductionDomain, ProductionResult};
 pub use crate::settlement::{SettlementDomain, SettlementResult};
-pub use crate::trading::{TradingDomain, TradingResult};
+pub use crate::trading::{EquityInvestorDecisionModel, TradingDomain, TradingResult};
 pub use crate::labor::{LabourDomain, LabourResult};
\ No newline at end of file
@@ -2,6 +2,16 @@ use sim_core::*;
 use std::any::Any;
 use rand::RngCore;
 use serde::{Deserialize, Serialize};
+use chrono::{Datelike, Weekday};
+use crate::production::CORPORATE_BOND_FACE_VALUE;
+
+/// Weeks of wages a firm keeps in liquid assets before it borrows.
+const MIN_LIQUIDITY_WEEKS: f64 = 4.0;
+/// Weeks of wages borrowing restores liquid assets to.
+const TARGET_LIQUIDITY_WEEKS: f64 = 12.0;
+/// Days a firm waits after asking the banks for a loan before asking again. In between it borrows in
+/// the bond market.
+const LOAN_REQUEST_INTERVAL_DAYS: i64 = 30;
 
 #[derive(Clone, Debug, Serialize, Default, Deserialize)]
 pub struct BasicFirmDecisionModel;
@@ -40,21 +50,35 @@ impl DecisionModel for BasicFirmDecisionModel {
             }
         }
 
-        for (employee_id, contract) in &firm.employees {
-            let weekly_wage = contract.wage_rate * contract.hours;
-            if weekly_wage > 0.0 {
-                actions.push(SimAction::Banking(BankingAction::PayWages {
+        let weekly_wage_bill: f64 = firm.employees.values().map(|c| c.wage_rate * c.hours).sum();
+        let liquid_assets = fs.get_liquid_assets(&firm.id);
+        let in_default = fs.credit.rating(&firm.id) == Some(&CreditRating::D);
+        if weekly_wage_bill > 0.0 && liquid_assets < MIN_LIQUIDITY_WEEKS * weekly_wage_bill && !in_default {
+            let shortfall = TARGET_LIQUIDITY_WEEKS * weekly_wage_bill - liquid_assets;
+            let since = state.current_date - chrono::Duration::days(LOAN_REQUEST_INTERVAL_DAYS);
+            if !state.history.requested_loan_since(&firm.id, since) {
+                let (amount, loan_type) = (shortfall, LoanType::Commercial);
+                let request = LoanRequest { borrower: firm.id, amount, loan_type, tenor: Tenor::T52W };
+                actions.push(SimAction::Banking(BankingAction::RequestLoan { request }));
+            } else {
+                actions.push(SimAction::Production(ProductionAction::IssueBonds {
                     agent_id: firm.id,
-                    employee: *employee_id,
-                    amount: weekly_wage,
+                    quantity: (shortfall / CORPORATE_BOND_FACE_VALUE).ceil() as u64,
+                    tenor: Tenor::T5Y,
                 }));
             }
         }
 
+        // Wages are paid on Mondays, since withholding treats each payment as a week's pay.
+        if state.current_date.weekday() == Weekday::Mon && weekly_wage_bill > 0.0 {
+            actions.push(SimAction::Banking(BankingAction::PayPayroll { agent_id: firm.id }));
+        }
+
         if let Some(recipe_id) = firm.recipe {
             if let Some(recipe) = fs.goods.get_recipe(&recipe_id) {
                 let weekly_labor_cost: f64 = firm.employees.values().map(|c| c.wage_rate * c.hours).sum();
-                let weekly_output = recipe.output.1 * recipe.efficiency * firm.employees.len() as f64;
+                let weekly_output =
+                    recipe.output.1 * recipe.efficiency * firm.productivity * firm.employees.len() as f64;
                 
                 if weekly_output > 0.0 {
                     let unit_cost = weekly_labor_cost / weekly_output;
@@ -2,9 +2,15 @@ use serde::{Deserialize, Serialize};
 use sim_core::*;
 use sim_macros::SimDomain;
 use uuid::Uuid;
+use crate::banking::BankingDomain;
+
+/// Face value of one corporate bond.
+pub const CORPORATE_BOND_FACE_VALUE: f64 = 1_000.0;
 
 #[derive(Clone, Debug, Serialize, Deserialize, SimDomain)]
-pub struct ProductionDomain {}
+pub struct ProductionDomain {
+    payment_router: BankingDomain,
+}
 
 #[derive(Debug, Clone)]
 pub struct ProductionResult {
@@ -15,11 +21,14 @@ pub struct ProductionResult {
 
 impl ProductionDomain {
     pub fn new() -> Self {
-        Self {}
+        Self { payment_router: BankingDomain::new() }
     }
 
     pub fn can_handle(&self, action: &ProductionAction) -> bool {
-        matches!(action, ProductionAction::Hire { .. } | ProductionAction::Produce { .. })
+        matches!(
+            action,
+            ProductionAction::Hire { .. } | ProductionAction::Produce { .. } | ProductionAction::IssueBonds { .. }
+        )
     }
 
     pub fn validate(&self, action: &ProductionAction, state: &SimState) -> Result<(), String> {
@@ -28,6 +37,9 @@ impl ProductionDomain {
             ProductionAction::Produce { agent_id, recipe_id, batches } => {
                 self.validate_produce(*agent_id, *recipe_id, *batches, state)
             }
+            ProductionAction::IssueBonds { agent_id, quantity, .. } => {
+                self.validate_issue_bonds(*agent_id, *quantity, state)
+            }
         }
     }
 
@@ -67,6 +79,37 @@ impl ProductionDomain {
         Ok(())
     }
 
+    fn validate_issue_bonds(&self, firm_id: AgentId, quantity: u64, state: &SimState) -> Result<(), String> {
+        if quantity == 0 {
+            return Err("Bond issue quantity must be positive".to_string());
+        }
+        let firm = state.agents.firms.get(&firm_id).ok_or(format!("Firm {:?} not found", firm_id))?;
+        if state.financial_system.credit.rating(&firm_id) == Some(&CreditRating::D) {
+            return Err(format!("Firm {:?} is in default and cannot issue bonds", firm_id));
+        }
+        let rating = state.financial_system.credit.rating_or_unrated(&firm_id);
+        if let Some(bank) = state.agents.banks.get(&firm.bank_id)
+            && !bank.lends_to(&rating)
+        {
+            return Err(format!("Underwriter {:?} declines to underwrite a {} issuer", firm.bank_id, rating));
+        }
+        let proceeds = CORPORATE_BOND_FACE_VALUE * quantity as f64;
+        let available = self.payment_router.available_funds(firm.bank_id, state);
+        if available < proceeds {
+            return Err(format!(
+                "Underwriter {:?} cannot fund bond issue: needs ${:.2}, has ${:.2}",
+                firm.bank_id, proceeds, available
+            ));
+        }
+        Ok(())
+    }
+
+    /// Coupon a firm pays on new bonds of `tenor`: the risk-free rate plus the spread for its rating.
+    pub fn corporate_coupon_rate(&self, firm_id: &AgentId, tenor: &Tenor, state: &SimState) -> f64 {
+        let fs = &state.financial_system;
+        fs.risk_free_rate(tenor) + fs.credit.rating_or_unrated(firm_id).spread()
+    }
+
     pub fn execute(&self, action: &ProductionAction, state: &SimState) -> ProductionResult {
         if let Err(error) = self.validate(action, state) {
             return ProductionResult { success: false, effects: vec![], errors: vec![error] };
@@ -77,6 +120,9 @@ impl ProductionDomain {
             ProductionAction::Produce { agent_id, recipe_id, batches } => {
                 self.execute_produce(*agent_id, *recipe_id, *batches, state)
             }
+            ProductionAction::IssueBonds { agent_id, quantity, tenor } => {
+                self.execute_issue_bonds(*agent_id, *quantity, *tenor, state)
+            }
         }
     }
     pub fn execute_hire(&self, firm_id: AgentId, count: u32, state: &SimState) -> ProductionResult {
@@ -133,7 +179,8 @@ impl ProductionDomain {
         }
 
         let (output_good, output_qty) = &recipe.output;
-        let total_output = output_qty * total_batches * recipe.efficiency;
+        let productivity = state.agents.firms.get(&firm_id).map_or(1.0, |firm| firm.productivity);
+        let total_output = output_qty * total_batches * recipe.efficiency * productivity;
         effects.push(StateEffect::Inventory(InventoryEffect::AddInventory {
             owner: firm_id,
             good_id: *output_good,
@@ -143,6 +190,53 @@ impl ProductionDomain {
 
         ProductionResult { success: true, effects, errors: vec![] }
     }
+
+    /// Issues a block of corporate bonds underwritten by the firm's bank, which buys the whole issue at
+    /// par and pays for it through the payment engine. The bonds carry `BondType::Corporate` with the
+    /// spread for the firm's rating, then pay coupons, accrue and default like any other bond liability.
+    pub fn execute_issue_bonds(
+        &self, firm_id: AgentId, quantity: u64, tenor: Tenor, state: &SimState,
+    ) -> ProductionResult {
+        let Some(firm) = state.agents.firms.get(&firm_id) else {
+            return ProductionResult {
+                success: false,
+                effects: vec![],
+                errors: vec![format!("Firm {:?} not found", firm_id)],
+            };
+        };
+
+        let spread = state.financial_system.credit.rating_or_unrated(&firm_id).spread();
+        let proceeds = CORPORATE_BOND_FACE_VALUE * quantity as f64;
+        let mut bond = bond!(
+            firm.bank_id,
+            firm_id,
+            proceeds,
+            self.corporate_coupon_rate(&firm_id, &tenor, state),
+            tenor.add_to_date(state.current_date),
+            CORPORATE_BOND_FACE_VALUE,
+            BondType::Corporate { spread },
+            2,
+            tenor,
+            state.current_date
+        );
+        bond.set_quantity(quantity);
+
+        let payment = self.payment_router.execute_payment(
+            firm.bank_id,
+            firm_id,
+            proceeds,
+            TransactionType::Transfer { from: firm.bank_id, to: firm_id, amount: proceeds },
+            Some(bond.id),
+            state,
+        );
+        if !payment.success {
+            return ProductionResult { success: false, effects: payment.effects, errors: payment.errors };
+        }
+
+        let mut effects = vec![StateEffect::Financial(FinancialEffect::CreateInstrument(bond))];
+        effects.extend(payment.effects);
+        ProductionResult { success: true, effects, errors: vec![] }
+    }
 }
 
 impl Default for ProductionDomain {
@@ -12,11 +12,17 @@
 //!   and executes `ProductionAction`s, such as `Produce` and `Hire`. For a `Produce`
 //!   action, it validates that the firm has the necessary input goods and labor, as
 //!   defined by its `ProductionRecipe`. If valid, it generates `StateEffect`s to consume
-//!   the inputs and add the finished product to the firm's inventory.
+//!   the inputs and add the finished product to the firm's inventory, scaled by the firm's
+//!   productivity, which public investment raises. `IssueBonds` raises
+//!   funding in the bond market: the firm's bank underwrites the issue at par, and the coupon is
+//!   the risk-free rate plus the spread for the firm's credit rating.
 //!
 //! - **`behavior.rs`**: Implements the `BasicFirmDecisionModel`. This is the "AI" for
 //!   firm agents. It analyzes market conditions and its own inventory levels to decide
-//!   whether to increase production, hire more employees, or purchase more raw materials.
+//!   whether to increase production, hire more employees, or purchase more raw materials. A firm
+//!   whose liquid assets fall below a few weeks of wages asks the banks for a loan to rebuild them,
+//!   and issues bonds instead when it has already asked within the past month. Wages are paid
+//!   weekly, on Mondays.
 //!
 //! ## Key Components
 //!
@@ -29,3 +35,124 @@ pub mod behavior;
 pub mod domain;
 pub use domain::*;
 pub use behavior::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Datelike, Weekday};
+    use rand::SeedableRng;
+    use sim_core::*;
+    use uuid::Uuid;
+
+    fn setup_firm_with_bank(bank_reserves: f64) -> (SimState, AgentId, AgentId) {
+        let mut state = SimState::default();
+        let cb_id = state.financial_system.central_bank.id;
+        let bank = Bank::new("Test Bank".to_string(), 0.0, 0.0);
+        let bank_id = bank.id;
+        let firm = Firm::new(bank_id, "Test Firm".to_string(), None, 25.0);
+        let firm_id = firm.id;
+        state.agents.banks.insert(bank_id, bank);
+        state.agents.firms.insert(firm_id, firm);
+        for id in [firm_id, bank_id] {
+            state.financial_system.balance_sheets.insert(id, BalanceSheet::new(id));
+        }
+        state.financial_system.create_instrument(reserves!(bank_id, cb_id, bank_reserves, state.current_date)).unwrap();
+        (state, firm_id, bank_id)
+    }
+
+    #[test]
+    fn test_firm_issues_bonds_at_rating_spread() {
+        let (mut state, firm_id, bank_id) = setup_firm_with_bank(10_000.0);
+        state.financial_system.credit.ratings.insert(firm_id, CreditRating::BB);
+        let action = ProductionAction::IssueBonds { agent_id: firm_id, quantity: 5, tenor: Tenor::T5Y };
+        let result = ProductionDomain::new().execute(&action, &state);
+        assert!(result.success, "{:?}", result.errors);
+        state.apply_effects(&result.effects).unwrap();
+
+        let fs = &state.financial_system;
+        let bond = fs.get_bs_by_id(&bank_id).unwrap().assets.values().find_map(|inst| {
+            inst.details.as_any().downcast_ref::<BondDetails>().map(|details| (inst, details.clone()))
+        });
+        let (bond, details) = bond.expect("underwriter holds the issue");
+        assert_eq!(bond.debtor, firm_id);
+        assert_eq!(bond.principal, 5_000.0);
+        assert_eq!(details.quantity, 5);
+        assert_eq!(details.bond_type, BondType::Corporate { spread: 0.03 });
+        assert!((details.coupon_rate - (fs.central_bank.policy_rate + 0.03)).abs() < 1e-12);
+        assert!((fs.get_liquid_assets(&firm_id) - 5_000.0).abs() < 1e-6);
+        assert_eq!(fs.corporate_bonds_held(&bank_id, &CreditRating::BB), 5);
+    }
+
+    #[test]
+    fn test_defaulted_firm_cannot_issue_bonds() {
+        let (mut state, firm_id, _) = setup_firm_with_bank(10_000.0);
+        let action = ProductionAction::IssueBonds { agent_id: firm_id, quantity: 20, tenor: Tenor::T5Y };
+        let domain = ProductionDomain::new();
+        assert!(domain.validate(&action, &state).unwrap_err().contains("cannot fund"));
+
+        state.financial_system.credit.ratings.insert(firm_id, CreditRating::CCC);
+        let action = ProductionAction::IssueBonds { agent_id: firm_id, quantity: 1, tenor: Tenor::T5Y };
+        assert!(domain.validate(&action, &state).unwrap_err().contains("declines to underwrite"));
+
+        state.financial_system.credit.ratings.insert(firm_id, CreditRating::D);
+        let action = ProductionAction::IssueBonds { agent_id: firm_id, quantity: 1, tenor: Tenor::T5Y };
+        assert!(domain.validate(&action, &state).unwrap_err().contains("in default"));
+    }
+
+    #[test]
+    fn test_firm_pays_wages_once_a_week() {
+        let (mut state, firm_id, _) = setup_firm_with_bank(10_000.0);
+        let employee = AgentId(Uuid::new_v4());
+        let contract = EmploymentContract {
+            employee_id: employee,
+            wage_rate: 25.0,
+            hours: 40.0,
+            start_date: state.current_date,
+        };
+        state.agents.firms.get_mut(&firm_id).unwrap().employees.insert(employee, contract);
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+        let mut paydays = Vec::new();
+        for _ in 0..14 {
+            let firm = &state.agents.firms[&firm_id];
+            let actions = BasicFirmDecisionModel.decide(firm, &state, &mut rng);
+            let paid = actions.iter().filter(|action| {
+                matches!(action, SimAction::Banking(BankingAction::PayPayroll { agent_id }) if *agent_id == firm_id)
+            });
+            if paid.count() == 1 {
+                paydays.push(state.current_date);
+            }
+            state.current_date = state.current_date.succ_opt().unwrap();
+        }
+        assert_eq!(paydays.len(), 2);
+        assert!(paydays.iter().all(|date| date.weekday() == Weekday::Mon));
+    }
+
+    #[test]
+    fn test_payroll_debits_the_firm_for_every_employee() {
+        let (mut state, firm_id, bank_id) = setup_firm_with_bank(10_000.0);
+        let deposit = deposit!(firm_id, bank_id, 5_000.0, 0.0, state.current_date);
+        state.financial_system.create_instrument(deposit).unwrap();
+        let employees = [(AgentId(Uuid::new_v4()), 25.0), (AgentId(Uuid::new_v4()), 30.0)];
+        for (employee, wage_rate) in employees {
+            state.financial_system.balance_sheets.insert(employee, BalanceSheet::new(employee));
+            let start_date = state.current_date;
+            let contract = EmploymentContract { employee_id: employee, wage_rate, hours: 40.0, start_date };
+            state.agents.firms.get_mut(&firm_id).unwrap().employees.insert(employee, contract);
+        }
+
+        let action = BankingAction::PayPayroll { agent_id: firm_id };
+        let result = crate::banking::BankingDomain::new().execute(&action, &state);
+        assert!(result.success, "{:?}", result.errors);
+        state.apply_effects(&result.effects).unwrap();
+
+        // 1,000 and 1,200 gross; what is withheld goes to the government rather than back to the firm
+        let fs = &state.financial_system;
+        let government_id = fs.government.id;
+        assert!((fs.get_liquid_assets(&firm_id) - 2_800.0).abs() < 1e-6);
+        let paid: f64 = employees.iter().map(|(employee, _)| fs.get_liquid_assets(employee)).sum();
+        assert!((paid + fs.get_liquid_assets(&government_id) - 2_200.0).abs() < 1e-6);
+        assert!(employees.iter().all(|(employee, _)| fs.get_liquid_assets(employee) > 0.0));
+        assert_eq!(fs.get_bs_by_id(&firm_id).unwrap().income_statement.operating_expenses, 2_200.0);
+    }
+}
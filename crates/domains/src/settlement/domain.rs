@@ -1,9 +1,13 @@
+use crate::banking::BankingDomain;
+use crate::fiscal::Levy;
 use serde::{Deserialize, Serialize};
 use sim_core::*;
 use sim_macros::SimDomain;
 
-#[derive(Clone, Debug, Serialize, Deserialize, Default, SimDomain)]
-pub struct SettlementDomain {}
+#[derive(Clone, Debug, Serialize, Deserialize, SimDomain)]
+pub struct SettlementDomain {
+    payment_router: BankingDomain,
+}
 
 #[derive(Debug, Clone)]
 pub struct SettlementResult {
@@ -14,7 +18,7 @@ pub struct SettlementResult {
 
 impl SettlementDomain {
     pub fn new() -> Self {
-        Self {}
+        Self { payment_router: BankingDomain::new() }
     }
 
     pub fn can_handle(&self, action: &SettlementAction) -> bool {
@@ -23,6 +27,10 @@ impl SettlementDomain {
             SettlementAction::AccrueInterest { .. }
                 | SettlementAction::PayInterest { .. }
                 | SettlementAction::ProcessCouponPayment { .. }
+                | SettlementAction::ResetRate { .. }
+                | SettlementAction::RepayInterbankLoan { .. }
+                | SettlementAction::RedeemBond { .. }
+                | SettlementAction::DistributeDividends { .. }
         )
     }
 
@@ -33,6 +41,12 @@ impl SettlementDomain {
             SettlementAction::ProcessCouponPayment { instrument_id } => {
                 self.validate_process_coupon_payment(instrument_id, state)
             }
+            SettlementAction::ResetRate { instrument_id } => self.validate_reset_rate(instrument_id, state),
+            SettlementAction::RepayInterbankLoan { instrument_id } => {
+                self.validate_repay_interbank_loan(instrument_id, state)
+            }
+            SettlementAction::RedeemBond { instrument_id } => self.validate_redeem_bond(instrument_id, state),
+            SettlementAction::DistributeDividends { firm_id } => self.validate_distribute_dividends(firm_id, state),
         }
     }
 
@@ -44,36 +58,27 @@ impl SettlementDomain {
     }
 
     fn validate_pay_interest(&self, instrument_id: &InstrumentId, state: &SimState) -> Result<(), String> {
-        let instrument = state
-            .financial_system
-            .instruments
-            .get(instrument_id)
-            .ok_or(format!("Instrument {:?} not found for interest payment.", instrument_id))?;
-
-        let interest_to_pay = instrument.accrued_interest;
-        if interest_to_pay <= 1e-6 {
-            return Ok(());
-        }
-
-        let available_funds = state.financial_system.get_liquid_assets(&instrument.debtor);
-        if available_funds < interest_to_pay {
-            return Err(format!(
-                "Insufficient funds for interest payment: agent {:?} needs ${:.2}, has ${:.2}",
-                instrument.debtor, interest_to_pay, available_funds
-            ));
+        if !state.financial_system.instruments.contains_key(instrument_id) {
+            return Err(format!("Instrument {:?} not found for interest payment.", instrument_id));
         }
         Ok(())
     }
 
-    fn get_coupon_payment_amount(&self, instrument: &FinancialInstrument) -> Option<f64> {
+    /// A coupon on an inflation-linked bond is paid on the CPI-indexed principal.
+    fn get_coupon_payment_amount(&self, instrument: &FinancialInstrument, state: &SimState) -> Option<f64> {
         if let Some(bond) = instrument.details.as_any().downcast_ref::<BondDetails>() {
-            let payment = (instrument.principal * bond.coupon_rate) / bond.frequency as f64;
+            let principal = instrument.principal * self.index_ratio(bond, state);
+            let payment = (principal * bond.coupon_rate) / bond.frequency as f64;
             Some(payment)
         } else {
             None
         }
     }
 
+    fn index_ratio(&self, bond: &BondDetails, state: &SimState) -> f64 {
+        if bond.inflation_linked.is_some() { bond.index_ratio(state.cpi_view().cpi) } else { 1.0 }
+    }
+
     fn validate_process_coupon_payment(&self, instrument_id: &InstrumentId, state: &SimState) -> Result<(), String> {
         let instrument = state
             .financial_system
@@ -81,41 +86,115 @@ impl SettlementDomain {
             .get(instrument_id)
             .ok_or(format!("Instrument {:?} not found for coupon payment.", instrument_id))?;
 
-        let payment_amount = self
-            .get_coupon_payment_amount(instrument)
+        self.get_coupon_payment_amount(instrument, state)
             .ok_or(format!("Instrument {:?} is not a bond, no coupon payment.", instrument_id))?;
+        Ok(())
+    }
 
-        let available_funds = state.financial_system.get_liquid_assets(&instrument.debtor);
-        if available_funds < payment_amount {
-            return Err(format!(
-                "Insufficient funds for coupon payment: agent {:?} needs ${:.2}, has ${:.2}",
-                instrument.debtor, payment_amount, available_funds
-            ));
+    fn validate_reset_rate(&self, instrument_id: &InstrumentId, state: &SimState) -> Result<(), String> {
+        let instrument = state
+            .financial_system
+            .instruments
+            .get(instrument_id)
+            .ok_or(format!("Instrument {:?} not found for rate reset.", instrument_id))?;
+        instrument
+            .floating_rate()
+            .map(|_| ())
+            .ok_or(format!("Instrument {:?} does not pay a floating rate.", instrument_id))
+    }
+
+    fn validate_repay_interbank_loan(&self, instrument_id: &InstrumentId, state: &SimState) -> Result<(), String> {
+        let instrument = state
+            .financial_system
+            .instruments
+            .get(instrument_id)
+            .ok_or(format!("Instrument {:?} not found for loan repayment.", instrument_id))?;
+        match instrument.details.as_any().downcast_ref::<LoanDetails>() {
+            Some(loan) if loan.is_overnight_funding() => Ok(()),
+            _ => Err(format!("Instrument {:?} is not an interbank loan.", instrument_id)),
+        }
+    }
+
+    fn validate_redeem_bond(&self, instrument_id: &InstrumentId, state: &SimState) -> Result<(), String> {
+        let instrument = state
+            .financial_system
+            .instruments
+            .get(instrument_id)
+            .ok_or(format!("Instrument {:?} not found for redemption.", instrument_id))?;
+        match instrument.details.as_any().downcast_ref::<BondDetails>() {
+            Some(bond) if bond.maturity_date <= state.current_date => Ok(()),
+            Some(_) => Err(format!("Bond {:?} has not matured.", instrument_id)),
+            None => Err(format!("Instrument {:?} is not a bond, nothing to redeem.", instrument_id)),
+        }
+    }
+
+    fn validate_distribute_dividends(&self, firm_id: &AgentId, state: &SimState) -> Result<(), String> {
+        if state.agents.get_firm(firm_id).is_none() {
+            return Err(format!("Firm {:?} not found for dividend distribution.", firm_id));
+        }
+        if state.financial_system.get_bs_by_id(firm_id).is_none() {
+            return Err(format!("Firm {:?} has no balance sheet.", firm_id));
         }
         Ok(())
     }
 
-    fn create_payment_effects(&self, from: AgentId, to: AgentId, amount: f64, state: &SimState) -> Vec<StateEffect> {
-        let mut effects = vec![];
-        let cb_id = state.financial_system.central_bank.id;
-        if let Some(from_bs) = state.financial_system.get_bs_by_id(&from) {
-            if let Some((cash_inst_id, cash_inst)) =
-                from_bs.assets.iter().find(|(_, inst)| inst.details.as_any().is::<CashDetails>())
-            {
-                let new_principal = cash_inst.principal - amount;
-                if new_principal < 1e-6 {
-                    effects.push(StateEffect::Financial(FinancialEffect::RemoveInstrument(*cash_inst_id)));
-                } else {
-                    effects.push(StateEffect::Financial(FinancialEffect::UpdateInstrument {
-                        id: *cash_inst_id,
-                        new_principal,
-                    }));
-                }
-                let new_cash_for_to = cash!(to, amount, cb_id, state.current_date);
-                effects.push(StateEffect::Financial(FinancialEffect::CreateInstrument(new_cash_for_to)));
-            }
+    /// Pays interest on an instrument through `pay_scheduled` and books it to both income statements.
+    fn make_payment(
+        &self, from: AgentId, to: AgentId, amount: f64, instrument_id: InstrumentId, state: &SimState,
+    ) -> SettlementResult {
+        let tx_type = TransactionType::InterestPayment { payer: from, receiver: to, amount };
+        let mut result = self.pay_scheduled(from, to, amount, tx_type, instrument_id, state);
+        if result.success {
+            result.effects.extend(self.book_interest(from, to, amount));
+        }
+        result
+    }
+
+    /// Pays a settlement cash flow through the banking domain's payment engine. A payment that cannot be
+    /// funded comes back unsuccessful, carrying the recorded `PaymentFailure` and marking the instrument
+    /// delinquent; a successful payment cures an instrument that was delinquent.
+    fn pay_scheduled(
+        &self, from: AgentId, to: AgentId, amount: f64, tx_type: TransactionType, instrument_id: InstrumentId,
+        state: &SimState,
+    ) -> SettlementResult {
+        let mut result =
+            self.payment_router.execute_payment(from, to, amount, tx_type, Some(instrument_id), state);
+        let credit_event = |kind| {
+            StateEffect::Credit(CreditEffect::RecordEvent(CreditEvent::new(
+                state.ticknum,
+                state.current_date,
+                from,
+                Some(instrument_id),
+                kind,
+            )))
+        };
+        if !result.success {
+            result.effects.push(StateEffect::Credit(CreditEffect::RecordMissedPayment {
+                instrument_id,
+                amount,
+                date: state.current_date,
+            }));
+            result.effects.push(credit_event(CreditEventKind::MissedPayment { amount }));
+        } else if state.financial_system.credit.is_delinquent(&instrument_id) {
+            result.effects.push(StateEffect::Credit(CreditEffect::Cure { instrument_id }));
+            result.effects.push(credit_event(CreditEventKind::Cured));
         }
-        effects
+        SettlementResult { success: result.success, effects: result.effects, errors: result.errors }
+    }
+
+    fn book_interest(&self, payer: AgentId, receiver: AgentId, amount: f64) -> [StateEffect; 2] {
+        [
+            StateEffect::Financial(FinancialEffect::RecordIncome {
+                agent_id: payer,
+                line: IncomeLine::InterestExpense,
+                amount,
+            }),
+            StateEffect::Financial(FinancialEffect::RecordIncome {
+                agent_id: receiver,
+                line: IncomeLine::InterestIncome,
+                amount,
+            }),
+        ]
     }
 
     pub fn execute(&self, action: &SettlementAction, state: &SimState) -> SettlementResult {
@@ -129,32 +208,53 @@ impl SettlementDomain {
             SettlementAction::ProcessCouponPayment { instrument_id } => {
                 self.execute_process_coupon_payment(instrument_id, state)
             }
+            SettlementAction::ResetRate { instrument_id } => self.execute_reset_rate(instrument_id, state),
+            SettlementAction::RepayInterbankLoan { instrument_id } => {
+                self.execute_repay_interbank_loan(instrument_id, state)
+            }
+            SettlementAction::RedeemBond { instrument_id } => self.execute_redeem_bond(instrument_id, state),
+            SettlementAction::DistributeDividends { firm_id } => self.execute_distribute_dividends(firm_id, state),
         }
     }
 
-    fn calculate_daily_interest_accrual(
-        &self, instrument: &FinancialInstrument, current_date: chrono::NaiveDate,
-    ) -> f64 {
-        let days_since_last_accrual = (current_date - instrument.last_accrual_date).num_days();
-        if days_since_last_accrual <= 0 {
+    /// Interest accrued since the last accrual on the instrument's day count. Bonds accrue on their
+    /// CPI-indexed principal, and reserves only on the excess over the requirement. If a floating rate
+    /// was due to reset in the period, the old rate runs up to the reset date and today's fixing after it.
+    fn calculate_daily_interest_accrual(&self, instrument: &FinancialInstrument, state: &SimState) -> f64 {
+        let (from, to) = (instrument.last_accrual_date, state.current_date);
+        if to <= from {
             return 0.0;
         }
 
-        let annual_rate = if let Some(deposit) = instrument.details.as_any().downcast_ref::<DemandDepositDetails>() {
-            deposit.interest_rate
-        } else if let Some(bond) = instrument.details.as_any().downcast_ref::<BondDetails>() {
-            bond.coupon_rate
-        } else {
-            return 0.0;
-        };
+        let (annual_rate, principal) =
+            if let Some(deposit) = instrument.details.as_any().downcast_ref::<DemandDepositDetails>() {
+                (deposit.interest_rate, instrument.principal)
+            } else if let Some(bond) = instrument.details.as_any().downcast_ref::<BondDetails>() {
+                (bond.coupon_rate, instrument.principal * self.index_ratio(bond, state))
+            } else if let Some(loan) = instrument.details.as_any().downcast_ref::<LoanDetails>()
+                && !loan.is_overnight_funding()
+            {
+                (loan.interest_rate, instrument.principal)
+            } else if instrument.details.as_any().is::<CentralBankReservesDetails>() {
+                let fs = &state.financial_system;
+                (fs.central_bank.interest_on_excess_reserves_rate(), fs.excess_reserves(&instrument.creditor))
+            } else {
+                return 0.0;
+            };
 
-        let daily_rate = annual_rate / 365.0;
-        instrument.principal * daily_rate * days_since_last_accrual as f64
+        let accrue = |rate: f64, start, end| principal * rate * instrument.day_count.year_fraction(start, end);
+        match instrument.floating_rate() {
+            Some(floating) if floating.next_reset > from && floating.is_due(to) => {
+                let fixing = floating.rate(state.rate_fixing(floating.index));
+                accrue(annual_rate, from, floating.next_reset) + accrue(fixing, floating.next_reset, to)
+            }
+            _ => accrue(annual_rate, from, to),
+        }
     }
 
     fn execute_accrue_interest(&self, instrument_id: &InstrumentId, state: &SimState) -> SettlementResult {
         if let Some(instrument) = state.financial_system.instruments.get(instrument_id) {
-            let accrued_amount = self.calculate_daily_interest_accrual(instrument, state.current_date);
+            let accrued_amount = self.calculate_daily_interest_accrual(instrument, state);
             if accrued_amount > 1e-6 {
                 let effect = StateEffect::Financial(FinancialEffect::AccrueInterest {
                     instrument_id: *instrument_id,
@@ -176,11 +276,13 @@ impl SettlementDomain {
             if interest_amount <= 1e-6 {
                 return SettlementResult { success: true, effects: vec![], errors: vec![] };
             }
-            let mut effects =
-                self.create_payment_effects(instrument.debtor, instrument.creditor, interest_amount, state);
-            effects
-                .push(StateEffect::Financial(FinancialEffect::ResetAccruedInterest { instrument_id: *instrument_id }));
-            SettlementResult { success: true, effects, errors: vec![] }
+            let mut result =
+                self.make_payment(instrument.debtor, instrument.creditor, interest_amount, *instrument_id, state);
+            if result.success {
+                let reset = FinancialEffect::ResetAccruedInterest { instrument_id: *instrument_id };
+                result.effects.push(StateEffect::Financial(reset));
+            }
+            result
         } else {
             SettlementResult { success: false, effects: vec![], errors: vec!["Instrument not found".to_string()] }
         }
@@ -188,13 +290,17 @@ impl SettlementDomain {
 
     fn execute_process_coupon_payment(&self, instrument_id: &InstrumentId, state: &SimState) -> SettlementResult {
         if let Some(instrument) = state.financial_system.instruments.get(instrument_id) {
-            if let Some(payment_amount) = self.get_coupon_payment_amount(instrument) {
+            if let Some(payment_amount) = self.get_coupon_payment_amount(instrument, state) {
                 if payment_amount <= 1e-6 {
                     return SettlementResult { success: true, effects: vec![], errors: vec![] };
                 }
-                let effects =
-                    self.create_payment_effects(instrument.debtor, instrument.creditor, payment_amount, state);
-                SettlementResult { success: true, effects, errors: vec![] }
+                let mut result =
+                    self.make_payment(instrument.debtor, instrument.creditor, payment_amount, *instrument_id, state);
+                if result.success {
+                    let reset = FinancialEffect::ResetAccruedInterest { instrument_id: *instrument_id };
+                    result.effects.push(StateEffect::Financial(reset));
+                }
+                result
             } else {
                 SettlementResult {
                     success: false,
@@ -206,4 +312,166 @@ impl SettlementDomain {
             SettlementResult { success: false, effects: vec![], errors: vec!["Instrument not found".to_string()] }
         }
     }
+
+    /// Refixes a floating rate at today's fixing of its index plus its spread. Interest up to today has
+    /// already accrued at the old rate. Resets missed on days the instrument was not refixed are
+    /// skipped, so the next reset always falls after today.
+    fn execute_reset_rate(&self, instrument_id: &InstrumentId, state: &SimState) -> SettlementResult {
+        let instrument = state.financial_system.instruments.get(instrument_id);
+        let Some(floating) = instrument.and_then(|inst| inst.floating_rate()) else {
+            let errors = vec!["Floating-rate instrument not found".to_string()];
+            return SettlementResult { success: false, effects: vec![], errors };
+        };
+        let rate = floating.rate(state.rate_fixing(floating.index));
+        let mut next_reset = floating.next_reset;
+        while next_reset <= state.current_date {
+            next_reset = floating.reset_period.add_to_date(next_reset);
+        }
+        let effect =
+            StateEffect::Financial(FinancialEffect::ResetRate { instrument_id: *instrument_id, rate, next_reset });
+        SettlementResult { success: true, effects: vec![effect], errors: vec![] }
+    }
+
+    /// Repays an overnight interbank or lending-facility loan. Repayment is a reserve payment, so it is
+    /// queued with the payment system and settles net with the rest of the day's interbank flows.
+    fn execute_repay_interbank_loan(&self, instrument_id: &InstrumentId, state: &SimState) -> SettlementResult {
+        let Some(instrument) = state.financial_system.instruments.get(instrument_id) else {
+            let errors = vec!["Instrument not found".to_string()];
+            return SettlementResult { success: false, effects: vec![], errors };
+        };
+        let Some(loan) = instrument.details.as_any().downcast_ref::<LoanDetails>() else {
+            let errors = vec!["Instrument is not a loan".to_string()];
+            return SettlementResult { success: false, effects: vec![], errors };
+        };
+        let repayment = loan.repayment_amount(instrument.principal, instrument.originated_date);
+        let mut effects = vec![
+            StateEffect::Financial(FinancialEffect::RemoveInstrument(*instrument_id)),
+            StateEffect::Financial(FinancialEffect::QueueInterbankObligation(InterbankObligation {
+                from_bank: instrument.debtor,
+                to_bank: instrument.creditor,
+                amount: repayment,
+                date: state.current_date,
+            })),
+            StateEffect::Financial(FinancialEffect::RecordTransaction(Transaction::new(
+                state.ticknum,
+                instrument.debtor,
+                instrument.creditor,
+                repayment,
+                TransactionType::LoanRepayment {
+                    borrower: instrument.debtor,
+                    lender: instrument.creditor,
+                    principal: instrument.principal,
+                    interest: repayment - instrument.principal,
+                },
+                Some(*instrument_id),
+            ))),
+        ];
+        effects.extend(self.book_interest(instrument.debtor, instrument.creditor, repayment - instrument.principal));
+        SettlementResult { success: true, effects, errors: vec![] }
+    }
+
+    /// Repays a matured bond at par, face value times the bonds held, and retires it. An inflation-linked
+    /// bond repays its CPI-indexed principal if that is above par. A bond the issuer cannot repay stays
+    /// outstanding and delinquent.
+    fn execute_redeem_bond(&self, instrument_id: &InstrumentId, state: &SimState) -> SettlementResult {
+        let Some(instrument) = state.financial_system.instruments.get(instrument_id) else {
+            let errors = vec!["Instrument not found".to_string()];
+            return SettlementResult { success: false, effects: vec![], errors };
+        };
+        let Some(bond) = instrument.details.as_any().downcast_ref::<BondDetails>() else {
+            let errors = vec!["Instrument is not a bond".to_string()];
+            return SettlementResult { success: false, effects: vec![], errors };
+        };
+        let (issuer, holder) = (instrument.debtor, instrument.creditor);
+        let amount = bond.face_value * bond.quantity as f64 * self.index_ratio(bond, state).max(1.0);
+        let tx_type = TransactionType::BondRedemption { issuer, holder, amount };
+        let mut result = self.pay_scheduled(issuer, holder, amount, tx_type, *instrument_id, state);
+        if result.success {
+            result.effects.push(StateEffect::Financial(FinancialEffect::RemoveInstrument(*instrument_id)));
+            if let Some(market_id) = state.financial_system.holding_market(instrument) {
+                let units = bond.quantity;
+                let disposal = FinancialEffect::RecordDisposal { holder, market_id, units };
+                result.effects.push(StateEffect::Financial(disposal));
+            }
+        }
+        result
+    }
+
+    /// Closes the firm's accounting period. Net profit is worked out from the period's income statement
+    /// with `Firm::calculate_profits` at the corporate tax rate, and the tax is paid to the government.
+    /// The share of net profit the firm does not retain is then paid to its shareholders pro rata to the
+    /// shares they hold. A firm that cannot fund the full tax or dividend pays what it can. The
+    /// period's results are published as an `EarningsReport`.
+    fn execute_distribute_dividends(&self, firm_id: &AgentId, state: &SimState) -> SettlementResult {
+        let (Some(firm), Some(bs)) = (state.agents.get_firm(firm_id), state.financial_system.get_bs_by_id(firm_id))
+        else {
+            return SettlementResult { success: false, effects: vec![], errors: vec!["Firm not found".to_string()] };
+        };
+        let statement = &bs.income_statement;
+        let tax_rate = state.financial_system.government.tax_rates.corporate_tax;
+        let profits = firm.calculate_profits(statement.total_revenues(), statement.total_costs(), tax_rate);
+        let holdings = state.financial_system.shareholders(firm_id);
+        let shares_outstanding: u64 = holdings.iter().map(|(.., shares)| shares).sum();
+
+        let mut effects = vec![];
+        let mut errors = vec![];
+        // The tax comes out of the firm's funds first and the dividend out of what is left; both are
+        // paid in one split payment so the firm's accounts are drawn down once
+        let mut available = self.payment_router.available_funds(*firm_id, state);
+        let levy = Levy::assess(*firm_id, TaxType::Corporate, profits.gross, state)
+            .map(|levy| Levy { amount: levy.amount.min(available), ..levy })
+            .filter(|levy| levy.amount > 1e-6);
+        let mut payments = vec![];
+        if let Some(levy) = &levy {
+            available -= levy.amount;
+            payments.push((state.financial_system.government.id, levy.amount, levy.transaction_type(state)));
+        }
+        let payout = profits.dividend().min(available);
+        let dividends: Vec<(AgentId, f64)> = if payout > 1e-6 && shares_outstanding > 0 {
+            holdings
+                .iter()
+                .map(|(_, shareholder, shares)| (*shareholder, payout * *shares as f64 / shares_outstanding as f64))
+                .collect()
+        } else {
+            vec![]
+        };
+        payments.extend(dividends.iter().map(|&(shareholder, amount)| {
+            (shareholder, amount, TransactionType::DividendPayment { payer: *firm_id, receiver: shareholder, amount })
+        }));
+
+        let mut paid = 0.0;
+        if !payments.is_empty() {
+            let result = self.payment_router.execute_split_payment(*firm_id, &payments, None, state);
+            if result.success {
+                effects.extend(result.effects);
+                effects.extend(levy.map(|levy| levy.revenue(state)));
+                for (recipient, amount) in dividends {
+                    effects.push(StateEffect::Agent(AgentEffect::RecordDividendIncome { recipient, amount }));
+                    paid += amount;
+                }
+            } else {
+                errors.extend(result.errors);
+            }
+        }
+        effects.push(StateEffect::Financial(FinancialEffect::RecordEarnings(EarningsReport {
+            tick: state.ticknum,
+            date: state.current_date,
+            firm: *firm_id,
+            revenues: statement.total_revenues(),
+            costs: statement.total_costs(),
+            interest_expense: statement.interest_expense,
+            net_profit: profits.net,
+            dividends: paid,
+            shares_outstanding,
+        })));
+        effects.push(StateEffect::Financial(FinancialEffect::CloseIncomeStatement { agent_id: *firm_id }));
+
+        SettlementResult { success: true, effects, errors }
+    }
+}
+
+impl Default for SettlementDomain {
+    fn default() -> Self {
+        Self::new()
+    }
 }
@@ -14,13 +14,26 @@
 //! - **`domain.rs`**: Contains the `SettlementDomain` struct. This service validates
 //!   and executes `SettlementAction`s.
 //!   - **`AccrueInterest`**: Calculates and records the interest that has accrued on an
-//!     instrument since the last calculation.
+//!     instrument since the last calculation, on the instrument's `DayCount`. Reserves accrue
+//!     interest on excess reserves, on the balance above the reserve requirement only.
 //!   - **`PayInterest`**: Creates the financial transaction to move accrued interest from
 //!     the debtor to the creditor.
-//!   - **`ProcessCouponPayment`**: Handles the fixed payments for bond instruments.
+//!   - **`ProcessCouponPayment`**: Handles the fixed payments for bond instruments, government and
+//!     corporate alike, and clears the interest they have accrued. Bonds pay only through coupons.
+//!   - **`RepayInterbankLoan`**: Repays maturing overnight SOFR loans, lending-facility loans and
+//!     deposit-facility placements through the interbank payment queue.
+//!   - **`RedeemBond`**: Repays a matured bond or bill at par and retires it.
+//!   - **`ResetRate`**: Refixes a floating-rate bond or loan at its index (the policy rate or SOFR)
+//!     plus its spread. A reset falling between two accruals splits the accrual at the reset date.
+//!   - **`DistributeDividends`**: Closes a firm's quarterly income statement and pays the share of
+//!     net profit it does not retain to its shareholders, pro rata to their holdings.
 //!
 //! The `SettlementDomain` translates these financial events into concrete `StateEffect`s,
-//! ensuring that the simulation's financial plumbing works correctly over time.
+//! ensuring that the simulation's financial plumbing works correctly over time. Every cash flow is
+//! paid through the `BankingDomain` payment engine, so debtors pay from cash, then deposits, then
+//! (for banks) reserves; a debtor who cannot pay produces a `PaymentFailure` instead of an overdraft.
+//! Interest paid and received is booked to both parties' income statements. Inflation-linked bonds
+//! accrue and pay coupons on their CPI-indexed principal and redeem at the greater of that and par.
 //!
 //! ## Key Components
 //!
@@ -29,4 +42,248 @@
 //!   `AccrueInterest` (defined in `sim_actions`).
 //! - **`SettlementResult`**: A struct wrapping the outcome, containing effects or errors.
 pub mod domain;
-pub use domain::*;
\ No newline at end of file
+pub use domain::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sim_core::*;
+    use uuid::Uuid;
+
+    fn setup_coupon_state(issuer_cash: f64, issuer_deposit: f64) -> (SimState, AgentId, AgentId, InstrumentId) {
+        let mut state = SimState::default();
+        let cb_id = state.financial_system.central_bank.id;
+        let issuer_id = AgentId(Uuid::new_v4());
+        let investor_id = AgentId(Uuid::new_v4());
+        let bank_id = AgentId(Uuid::new_v4());
+        state.agents.banks.insert(bank_id, Bank::new("Test Bank".to_string(), 0.0, 0.0));
+        state.agents.consumers.insert(investor_id, Consumer::new(40, bank_id, PersonalityArchetype::Saver));
+        for id in [issuer_id, investor_id, bank_id] {
+            state.financial_system.balance_sheets.insert(id, BalanceSheet::new(id));
+        }
+        if issuer_cash > 0.0 {
+            state.financial_system.create_instrument(cash!(issuer_id, issuer_cash, cb_id, state.current_date)).unwrap();
+        }
+        if issuer_deposit > 0.0 {
+            let deposit = deposit!(issuer_id, bank_id, issuer_deposit, 0.01, state.current_date);
+            state.financial_system.create_instrument(deposit).unwrap();
+        }
+        let bond = bond!(
+            investor_id,
+            issuer_id,
+            1000.0,
+            0.06,
+            state.current_date + chrono::Duration::days(365 * 5),
+            1000.0,
+            BondType::Corporate { spread: 0.01 },
+            2,
+            Tenor::T5Y,
+            state.current_date
+        );
+        let bond_id = bond.id;
+        state.financial_system.create_instrument(bond).unwrap();
+        (state, issuer_id, investor_id, bond_id)
+    }
+
+    #[test]
+    fn test_coupon_paid_from_cash_then_deposits() {
+        let (mut state, issuer_id, investor_id, bond_id) = setup_coupon_state(10.0, 100.0);
+        let action = SettlementAction::ProcessCouponPayment { instrument_id: bond_id };
+        let result = SettlementDomain::new().execute(&action, &state);
+        assert!(result.success, "{:?}", result.errors);
+        state.apply_effects(&result.effects).unwrap();
+
+        let fs = &state.financial_system;
+        assert_eq!(fs.get_cash_assets(&issuer_id), 0.0);
+        assert!((fs.get_total_deposits(&issuer_id) - 80.0).abs() < 1e-6);
+        assert!((fs.get_liquid_assets(&investor_id) - 30.0).abs() < 1e-6);
+        assert!(state.history.payment_failures.is_empty());
+    }
+
+    #[test]
+    fn test_bond_accrues_on_its_day_count_and_pays_on_its_schedule() {
+        let (mut state, _, _, bond_id) = setup_coupon_state(0.0, 0.0);
+        let bond = &state.financial_system.instruments[&bond_id];
+        assert_eq!(bond.day_count, DayCount::Thirty360);
+        assert_eq!(bond.payment_schedule.len(), 10);
+        assert_eq!(bond.payment_schedule.last(), Some(&(state.current_date + chrono::Duration::days(365 * 5))));
+
+        // 1 January to 31 January is 30 days on the 30/360 basis
+        state.current_date += chrono::Duration::days(30);
+        let result =
+            SettlementDomain::new().execute(&SettlementAction::AccrueInterest { instrument_id: bond_id }, &state);
+        state.apply_effects(&result.effects).unwrap();
+        let accrued = state.financial_system.instruments[&bond_id].accrued_interest;
+        assert!((accrued - 1000.0 * 0.06 * 30.0 / 360.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_matured_bond_is_redeemed_at_par() {
+        let (mut state, issuer_id, investor_id, bond_id) = setup_coupon_state(1200.0, 0.0);
+        let action = SettlementAction::RedeemBond { instrument_id: bond_id };
+        assert!(!SettlementDomain::new().execute(&action, &state).success, "The bond has not matured yet");
+
+        state.current_date += chrono::Duration::days(365 * 5);
+        let result = SettlementDomain::new().execute(&action, &state);
+        assert!(result.success, "{:?}", result.errors);
+        state.apply_effects(&result.effects).unwrap();
+
+        let fs = &state.financial_system;
+        assert!(!fs.instruments.contains_key(&bond_id));
+        assert!((fs.get_cash_assets(&issuer_id) - 200.0).abs() < 1e-6);
+        assert!((fs.get_liquid_assets(&investor_id) - 1000.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_floating_rate_resets_to_index_and_splits_accrual() {
+        let (mut state, _, _, bond_id) = setup_coupon_state(0.0, 0.0);
+        state.financial_system.central_bank.policy_rate = 0.04;
+        let bond = state.financial_system.instruments.remove(&bond_id).unwrap();
+        let floater = bond.with_floating_rate(RateIndex::PolicyRate, 0.01, TimePeriod::Months(1));
+        state.financial_system.instruments.insert(bond_id, floater);
+        let reset = SettlementAction::ResetRate { instrument_id: bond_id };
+        assert!(SettlementDomain::new().validate(&reset, &state).is_ok());
+
+        // 1 January to 16 February: 30 days at the 6% coupon, then 15 at the 5% fixing
+        state.current_date += chrono::Duration::days(46);
+        let accrue = SettlementAction::AccrueInterest { instrument_id: bond_id };
+        let result = SettlementDomain::new().execute(&accrue, &state);
+        state.apply_effects(&result.effects).unwrap();
+        let accrued = state.financial_system.instruments[&bond_id].accrued_interest;
+        assert!((accrued - 1000.0 * (0.06 * 30.0 + 0.05 * 15.0) / 360.0).abs() < 1e-9);
+
+        let result = SettlementDomain::new().execute(&reset, &state);
+        assert!(result.success, "{:?}", result.errors);
+        state.apply_effects(&result.effects).unwrap();
+        let bond = &state.financial_system.instruments[&bond_id];
+        let details = bond.details.as_any().downcast_ref::<BondDetails>().unwrap();
+        assert!((details.coupon_rate - 0.05).abs() < 1e-12);
+        let floating = bond.floating_rate().unwrap();
+        assert!(floating.next_reset > state.current_date && !floating.is_due(state.current_date));
+    }
+
+    #[test]
+    fn test_inflation_linked_bond_pays_on_indexed_principal() {
+        let (mut state, issuer_id, investor_id, bond_id) = setup_coupon_state(2000.0, 0.0);
+        let good_id = GoodId(Uuid::new_v4());
+        let good = Good {
+            id: good_id,
+            name: "Basket".to_string(),
+            unit: "unit".to_string(),
+            category: GoodCategory::FinalGood,
+            cpi_weight: 1.0,
+        };
+        state.financial_system.goods.goods.insert(good_id, good);
+        let tick = MarketTick { close: Some(110.0), last_price: Some(110.0), ..Default::default() };
+        state.history.market_ticks.insert(MarketId::Goods(good_id), [tick].into());
+        let bond = state.financial_system.instruments.remove(&bond_id).unwrap();
+        state.financial_system.instruments.insert(bond_id, bond.with_inflation_linkage(100.0));
+
+        let coupon = SettlementAction::ProcessCouponPayment { instrument_id: bond_id };
+        let result = SettlementDomain::new().execute(&coupon, &state);
+        assert!(result.success, "{:?}", result.errors);
+        state.apply_effects(&result.effects).unwrap();
+        assert!((state.financial_system.get_liquid_assets(&investor_id) - 33.0).abs() < 1e-6);
+
+        state.current_date += chrono::Duration::days(365 * 5);
+        let result = SettlementDomain::new().execute(&SettlementAction::RedeemBond { instrument_id: bond_id }, &state);
+        assert!(result.success, "{:?}", result.errors);
+        state.apply_effects(&result.effects).unwrap();
+        assert!((state.financial_system.get_cash_assets(&issuer_id) - (2000.0 - 33.0 - 1100.0)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_reserves_accrue_interest_on_the_excess_only() {
+        let mut state = SimState::default();
+        let cb_id = state.financial_system.central_bank.id;
+        let (bank_id, depositor_id) = (AgentId(Uuid::new_v4()), AgentId(Uuid::new_v4()));
+        state.agents.banks.insert(bank_id, Bank::new("Test Bank".to_string(), 0.0, 0.0));
+        for id in [bank_id, depositor_id] {
+            state.financial_system.balance_sheets.insert(id, BalanceSheet::new(id));
+        }
+        let deposit = deposit!(depositor_id, bank_id, 10_000.0, 0.0, state.current_date);
+        state.financial_system.create_instrument(deposit).unwrap();
+        let reserves = reserves!(bank_id, cb_id, 3_000.0, state.current_date);
+        let reserves_id = reserves.id;
+        state.financial_system.create_instrument(reserves).unwrap();
+        assert_eq!(state.financial_system.excess_reserves(&bank_id), 2_000.0);
+
+        state.current_date += chrono::Duration::days(73);
+        let action = SettlementAction::AccrueInterest { instrument_id: reserves_id };
+        let result = SettlementDomain::new().execute(&action, &state);
+        state.apply_effects(&result.effects).unwrap();
+        let rate = state.financial_system.central_bank.interest_on_excess_reserves_rate();
+        let accrued = state.financial_system.instruments[&reserves_id].accrued_interest;
+        assert!((accrued - 2_000.0 * rate * 73.0 / 365.0).abs() < 1e-9);
+
+        let result =
+            SettlementDomain::new().execute(&SettlementAction::PayInterest { instrument_id: reserves_id }, &state);
+        assert!(result.success, "{:?}", result.errors);
+        state.apply_effects(&result.effects).unwrap();
+        let queue = &state.financial_system.payment_system.queue;
+        assert_eq!((queue[0].from_bank, queue[0].to_bank), (cb_id, bank_id));
+        assert!((queue[0].amount - accrued).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_unfunded_coupon_records_payment_failure() {
+        let (mut state, issuer_id, investor_id, bond_id) = setup_coupon_state(10.0, 5.0);
+        let action = SettlementAction::ProcessCouponPayment { instrument_id: bond_id };
+        let result = SettlementDomain::new().execute(&action, &state);
+        assert!(!result.success);
+        state.apply_effects(&result.effects).unwrap();
+
+        let fs = &state.financial_system;
+        assert_eq!(fs.get_cash_assets(&issuer_id), 10.0);
+        assert_eq!(fs.get_liquid_assets(&investor_id), 0.0);
+        let failure = &state.history.payment_failures[0];
+        assert_eq!((failure.payer, failure.payee, failure.instrument_id), (issuer_id, investor_id, Some(bond_id)));
+        assert!((failure.amount - 30.0).abs() < 1e-6);
+        assert!((failure.available - 15.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_dividends_paid_pro_rata_from_net_profit() {
+        let mut state = SimState::default();
+        let cb_id = state.financial_system.central_bank.id;
+        let date = state.current_date;
+        let bank_id = AgentId(Uuid::new_v4());
+        let firm = Firm::new(bank_id, "Test Firm".to_string(), None, 25.0);
+        let holder_id = AgentId(Uuid::new_v4());
+        state.agents.banks.insert(bank_id, Bank::new("Test Bank".to_string(), 0.0, 0.0));
+        state.agents.consumers.insert(holder_id, Consumer::new(40, bank_id, PersonalityArchetype::Saver));
+        for id in [firm.id, holder_id, bank_id] {
+            state.financial_system.balance_sheets.insert(id, BalanceSheet::new(id));
+        }
+        state.financial_system.create_instrument(cash!(firm.id, 10_000.0, cb_id, date)).unwrap();
+        state.financial_system.create_instrument(equity!(holder_id, firm.id, 300u64, 400u64, 10.0, date)).unwrap();
+        state.financial_system.create_instrument(equity!(bank_id, firm.id, 100u64, 400u64, 10.0, date)).unwrap();
+        let statement = &mut state.financial_system.balance_sheets.get_mut(&firm.id).unwrap().income_statement;
+        statement.record(IncomeLine::Revenue, 1500.0);
+        statement.record(IncomeLine::OperatingExpense, 500.0);
+        let firm_id = firm.id;
+        state.agents.firms.insert(firm_id, firm);
+
+        let result = SettlementDomain::new().execute(&SettlementAction::DistributeDividends { firm_id }, &state);
+        assert!(result.success, "{:?}", result.errors);
+        state.apply_effects(&result.effects).unwrap();
+
+        // Net profit 1000 * (1 - 20% tax) = 800, of which 40% is paid out
+        let fs = &state.financial_system;
+        assert!((fs.get_cash_assets(&firm_id) - 9_480.0).abs() < 1e-6);
+        assert!((fs.get_liquid_assets(&fs.government.id) - 200.0).abs() < 1e-6);
+        assert!((fs.get_liquid_assets(&holder_id) - 240.0).abs() < 1e-6);
+        assert!((fs.get_liquid_assets(&bank_id) - 80.0).abs() < 1e-6);
+        assert!((fs.get_bs_by_id(&holder_id).unwrap().income_statement.dividend_income - 240.0).abs() < 1e-6);
+        assert_eq!(fs.get_bs_by_id(&firm_id).unwrap().income_statement.net_income, 0.0);
+        let dividends = state
+            .history
+            .transactions
+            .iter()
+            .filter(|tx| matches!(tx.tx_type, TransactionType::DividendPayment { .. }));
+        assert_eq!(dividends.count(), 2);
+        let report = &state.history.earnings_reports[0];
+        assert!((report.dividends - 320.0).abs() < 1e-6);
+        assert!((report.earnings_per_share().unwrap() - 8.0).abs() < 1e-9);
+    }
+}
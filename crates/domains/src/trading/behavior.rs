@@ -0,0 +1,106 @@
+use rand::{Rng, RngCore};
+use serde::{Deserialize, Serialize};
+use sim_core::*;
+use std::any::Any;
+
+/// A value investor for listed shares, usable by any consumer or bank. It values each firm as a blend
+/// of its dividend stream capitalised at `required_yield` and its book value per share, and trades
+/// when the market price strays more than `threshold` from that value. Each investor's valuation is
+/// perturbed by `valuation_noise`, so investors disagree and the market finds a price between them.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EquityInvestorDecisionModel {
+    pub required_yield: f64,
+    pub dividend_weight: f64,
+    pub threshold: f64,
+    pub valuation_noise: f64,
+    /// Share of liquid assets committed to one purchase.
+    pub budget_share: f64,
+    /// Share of a holding offered when it looks expensive.
+    pub sell_share: f64,
+}
+
+impl Default for EquityInvestorDecisionModel {
+    fn default() -> Self {
+        Self {
+            required_yield: 0.05,
+            dividend_weight: 0.5,
+            threshold: 0.05,
+            valuation_noise: 0.1,
+            budget_share: 0.05,
+            sell_share: 0.2,
+        }
+    }
+}
+
+#[typetag::serde]
+impl DecisionModel for EquityInvestorDecisionModel {
+    fn decide(&self, agent: &dyn Any, state: &SimState, rng: &mut dyn RngCore) -> Vec<SimAction> {
+        let investor = if let Some(consumer) = agent.downcast_ref::<Consumer>() {
+            consumer.id
+        } else if let Some(bank) = agent.downcast_ref::<Bank>() {
+            bank.id
+        } else {
+            return vec![];
+        };
+
+        let mut firms: Vec<AgentId> = state
+            .financial_system
+            .exchange
+            .financial_markets
+            .keys()
+            .filter_map(|market_id| match market_id {
+                FinancialMarketId::Equity { firm } => Some(*firm),
+                _ => None,
+            })
+            .collect();
+        firms.sort_by_key(|id| id.0);
+
+        firms.into_iter().filter_map(|firm| self.trade(investor, firm, state, rng)).collect()
+    }
+}
+
+impl EquityInvestorDecisionModel {
+    /// Value per share before the investor's own noise.
+    pub fn fair_value(&self, firm: &AgentId, state: &SimState) -> Option<f64> {
+        let book = state.book_value_per_share(firm)?.max(0.0);
+        let dividend = state.latest_earnings(firm).and_then(|report| report.dividend_per_share());
+        match dividend {
+            Some(dps) if dps > 0.0 => {
+                Some(self.dividend_weight * dps / self.required_yield + (1.0 - self.dividend_weight) * book)
+            }
+            _ => Some(book),
+        }
+    }
+
+    fn trade(&self, investor: AgentId, firm: AgentId, state: &SimState, rng: &mut dyn RngCore) -> Option<SimAction> {
+        let fs = &state.financial_system;
+        let price = state.share_price(&firm)?;
+        let noise = 1.0 + self.valuation_noise * rng.random_range(-1.0..=1.0);
+        let value = self.fair_value(&firm, state)? * noise;
+        if price <= 0.0 || value <= 0.0 {
+            return None;
+        }
+        let market_id = MarketId::Financial(FinancialMarketId::Equity { firm });
+        let limit_price = (price + value) / 2.0;
+
+        if value > price * (1.0 + self.threshold) {
+            let quantity = (fs.get_liquid_assets(&investor) * self.budget_share / limit_price).floor();
+            (quantity >= 1.0).then_some(SimAction::Trading(TradingAction::PostBid {
+                agent_id: investor,
+                market_id,
+                quantity,
+                price: limit_price,
+            }))
+        } else if value < price * (1.0 - self.threshold) {
+            let quantity = (fs.shares_held(&investor, &firm) as f64 * self.sell_share).floor();
+            (quantity >= 1.0).then_some(SimAction::Trading(TradingAction::PostAsk {
+                agent_id: investor,
+                market_id,
+                quantity,
+                price: limit_price,
+            }))
+        } else {
+            None
+        }
+    }
+}
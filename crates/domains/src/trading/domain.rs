@@ -1,7 +1,8 @@
 use serde::{Deserialize, Serialize};
 use sim_core::*;
 use sim_macros::SimDomain;
-use crate::banking::BankingDomain;
+use crate::banking::{BankingDomain, BankingResult};
+use crate::fiscal::{Levy, pay_with_levy};
 
 #[derive(Clone, Debug, Serialize, Deserialize, SimDomain)]
 pub struct TradingDomain {
@@ -112,7 +113,24 @@ impl TradingDomain {
                         ));
                     }
                 }
-                FinancialMarketId::CorporateBond { .. } => {}
+                FinancialMarketId::CorporateBond { rating } => {
+                    let held_quantity = state.financial_system.corporate_bonds_held(&agent_id, rating) as f64;
+                    if held_quantity < quantity {
+                        return Err(format!(
+                            "Insufficient corporate bond holdings ({}) for ask: agent {:?} needs {:.0}, has {:.0}",
+                            rating, agent_id, quantity, held_quantity
+                        ));
+                    }
+                }
+                FinancialMarketId::Equity { firm } => {
+                    let held_shares = state.financial_system.shares_held(&agent_id, firm) as f64;
+                    if held_shares < quantity {
+                        return Err(format!(
+                            "Insufficient shares of {} for ask: agent {:?} needs {:.0}, has {:.0}",
+                            firm, agent_id, quantity, held_shares
+                        ));
+                    }
+                }
             },
             MarketId::Labour(_) => {}
         }
@@ -164,7 +182,16 @@ impl TradingDomain {
         let mut effects = vec![];
         let total_payment = trade.price * trade.quantity;
 
-        let payment_result = self.payment_router.execute_transfer(trade.buyer, trade.seller, total_payment, state);
+        let levy = Levy::on_purchase(trade.buyer, total_payment, state);
+        let tx_type = TransactionType::Transfer { from: trade.buyer, to: trade.seller, amount: total_payment };
+        let payment_result = pay_with_levy(
+            &self.payment_router,
+            trade.buyer,
+            vec![(trade.seller, total_payment, tx_type)],
+            None,
+            levy.as_slice(),
+            state,
+        );
 
         if !payment_result.success {
             return TradingResult {
@@ -180,17 +207,42 @@ impl TradingDomain {
             _ => return TradingResult { success: false, effects: vec![], errors: vec!["Invalid MarketId for goods trade".to_string()] },
         };
 
+        let unit_cost = state
+            .financial_system
+            .get_bs_by_id(&trade.seller)
+            .and_then(|bs| bs.get_inventory())
+            .and_then(|inventory| inventory.get(&good_id))
+            .map_or(0.0, |item| item.unit_cost);
+        effects.push(StateEffect::Financial(FinancialEffect::RecordIncome {
+            agent_id: trade.seller,
+            line: IncomeLine::Revenue,
+            amount: total_payment,
+        }));
+        effects.push(StateEffect::Financial(FinancialEffect::RecordIncome {
+            agent_id: trade.seller,
+            line: IncomeLine::CostOfGoodsSold,
+            amount: unit_cost * trade.quantity,
+        }));
         effects.push(StateEffect::Inventory(InventoryEffect::RemoveInventory {
             owner: trade.seller,
             good_id,
             quantity: trade.quantity,
         }));
-        effects.push(StateEffect::Inventory(InventoryEffect::AddInventory {
-            owner: trade.buyer,
-            good_id,
-            quantity: trade.quantity,
-            unit_cost: trade.price,
-        }));
+        if trade.buyer == state.financial_system.government.id {
+            // The government uses what it buys at once: its purchases are spending, not stock
+            effects.push(StateEffect::Financial(FinancialEffect::RecordIncome {
+                agent_id: trade.buyer,
+                line: IncomeLine::OperatingExpense,
+                amount: total_payment,
+            }));
+        } else {
+            effects.push(StateEffect::Inventory(InventoryEffect::AddInventory {
+                owner: trade.buyer,
+                good_id,
+                quantity: trade.quantity,
+                unit_cost: trade.price,
+            }));
+        }
 
         TradingResult { success: true, effects, errors: vec![] }
     }
@@ -214,8 +266,7 @@ impl TradingDomain {
                                     quantity: trade.quantity as u64,
                                 }));
 
-                                let total_payment = trade.price * trade.quantity;
-                                let payment_result = self.payment_router.execute_transfer(trade.buyer, trade.seller, total_payment, state);
+                                let payment_result = self.pay_for_holding(trade, inst, state);
 
                                 if !payment_result.success {
                                      return TradingResult {
@@ -236,15 +287,127 @@ impl TradingDomain {
                     }
                 }
             }
-             MarketId::Financial(FinancialMarketId::SecuredOvernightFinancing) => {
-                println!("[INFO] SOFR Trade executed (Settlement logic TBD)");
+            MarketId::Financial(market_id @ FinancialMarketId::SecuredOvernightFinancing) => {
+                effects.extend(self.settle_overnight_loan(market_id, trade, state));
+            }
+            MarketId::Financial(FinancialMarketId::CorporateBond { rating }) => {
+                let fs = &state.financial_system;
+                return self.settle_holding_trade(trade, state, &format!("{} corporate bonds", rating), |inst| {
+                    fs.corporate_bond_rating(inst).as_ref() == Some(rating)
+                });
+            }
+            MarketId::Financial(FinancialMarketId::Equity { firm }) => {
+                return self.settle_holding_trade(trade, state, &format!("shares of {}", firm), |inst| {
+                    inst.is_equity_of(firm)
+                });
             }
             _ => {}
         }
 
         TradingResult { success: !effects.is_empty(), effects, errors: vec![] }
     }
-    
+
+    /// Moves the traded units out of one of the seller's holdings matching `is_traded` into the buyer's,
+    /// against payment of `quantity * price`. Used for shares and for corporate bonds, which trade in
+    /// rating buckets rather than as individual issues.
+    fn settle_holding_trade(
+        &self, trade: &Trade, state: &SimState, description: &str, is_traded: impl Fn(&FinancialInstrument) -> bool,
+    ) -> TradingResult {
+        let units = trade.quantity as u64;
+        let holding = state.financial_system.get_bs_by_id(&trade.seller).and_then(|bs| {
+            bs.assets.values().find(|inst| is_traded(inst) && inst.quantity().is_some_and(|held| held >= units))
+        });
+        let Some(holding) = holding else {
+            return TradingResult {
+                success: false,
+                effects: vec![],
+                errors: vec![format!("Seller does not hold {} {}", units, description)],
+            };
+        };
+
+        let payment_result = self.pay_for_holding(trade, holding, state);
+        if !payment_result.success {
+            return TradingResult {
+                success: false,
+                effects: vec![],
+                errors: vec![format!("Trade settlement failed during payment: {:?}", payment_result.errors)],
+            };
+        }
+
+        let mut effects = vec![StateEffect::Financial(FinancialEffect::SplitAndTransferInstrument {
+            id: holding.id,
+            buyer: trade.buyer,
+            quantity: units,
+        })];
+        effects.extend(payment_result.effects);
+        TradingResult { success: true, effects, errors: vec![] }
+    }
+
+    /// Pays the seller of `units` of `holding` out of the buyer's funds, less the capital gains tax on
+    /// the sale, which goes to the government. The units move from the seller's cost basis to the
+    /// buyer's at the trade price.
+    fn pay_for_holding(&self, trade: &Trade, holding: &FinancialInstrument, state: &SimState) -> BankingResult {
+        let units = trade.quantity as u64;
+        let total_payment = trade.price * units as f64;
+        let levy = Levy::on_sale(trade.seller, holding, units, trade.price, state);
+        let proceeds = total_payment - levy.as_ref().map_or(0.0, |levy| levy.amount);
+        let tx_type = TransactionType::Transfer { from: trade.buyer, to: trade.seller, amount: proceeds };
+        let mut result = pay_with_levy(
+            &self.payment_router,
+            trade.buyer,
+            vec![(trade.seller, proceeds, tx_type)],
+            Some(holding.id),
+            levy.as_slice(),
+            state,
+        );
+        if result.success {
+            let market_id = trade.market_id.clone();
+            result.effects.push(StateEffect::Financial(FinancialEffect::RecordDisposal {
+                holder: trade.seller,
+                market_id: market_id.clone(),
+                units,
+            }));
+            result.effects.push(StateEffect::Financial(FinancialEffect::RecordAcquisition {
+                holder: trade.buyer,
+                market_id,
+                units,
+                cost: total_payment,
+            }));
+        }
+        result
+    }
+
+    /// A SOFR trade is an overnight loan from the seller (the lender of reserves) to the buyer. The
+    /// buyer receives `quantity * price` today and repays `quantity` tomorrow; the cash leg goes through
+    /// the interbank payment queue like any other reserve payment.
+    fn settle_overnight_loan(
+        &self, market_id: &FinancialMarketId, trade: &Trade, state: &SimState,
+    ) -> Vec<StateEffect> {
+        let advanced = trade.quantity * trade.price;
+        let annual_rate = market_id.daily_rate_to_annual_bps(market_id.price_to_daily_rate(trade.price)) / 10000.0;
+        let loan = loan!(
+            trade.seller,
+            trade.buyer,
+            advanced,
+            LoanType::Interbank,
+            annual_rate,
+            state.current_date + chrono::Duration::days(1),
+            state.current_date,
+            &state.financial_system.calendar
+        );
+        let loan_id = loan.id;
+        let mut effects = vec![StateEffect::Financial(FinancialEffect::CreateInstrument(loan))];
+        effects.extend(self.payment_router.queue_reserve_payment(trade.seller, trade.buyer, advanced, state));
+        effects.push(self.payment_router.record_transaction(
+            trade.seller,
+            trade.buyer,
+            advanced,
+            TransactionType::Transfer { from: trade.seller, to: trade.buyer, amount: advanced },
+            Some(loan_id),
+            state,
+        ));
+        effects
+    }
 }
 
 impl Default for TradingDomain {
@@ -17,16 +17,164 @@
 //!       `TradingDomain`'s `settle_financial_trade` method is called. This method is responsible
 //!       for creating the `StateEffect`s that represent the financial outcome of the trade:
 //!       transferring the asset from the seller to the buyer and transferring payment from the
-//!       buyer to the seller.
+//!       buyer to the seller. Share trades in `Equity { firm }` markets and corporate bond trades
+//!       in `CorporateBond { rating }` markets move units between holdings with
+//!       `SplitAndTransferInstrument`. A corporate bond trades in the bucket of its issuer's
+//!       current rating, so a downgrade moves every bond of that issuer to a lower bucket.
 //!
-//! This crate does *not* contain a `behavior.rs` module because the *decision* to trade
-//! is made within each agent's own domain (e.g., a bank's `BasicBankDecisionModel` decides
-//! to trade bonds). This crate only provides the *mechanism* for that trade to occur.
+//! Most decisions to trade are made within each agent's own domain (e.g., a bank's
+//! `BasicBankDecisionModel` decides to trade bonds). The exception is portfolio investment in
+//! shares, which consumers and banks share:
+//!
+//! - **`behavior.rs`**: Implements `EquityInvestorDecisionModel`, which values each listed firm from
+//!   its dividends and book value and bids or offers shares when the market price strays from that
+//!   value. Agents run it alongside their own model through a `CompositeDecisionModel`.
 //!
 //! ## Key Components
 //!
 //! - **`TradingDomain`**: The service for posting orders and settling completed trades.
+//! - **`EquityInvestorDecisionModel`**: The dividend-yield and book-value share investor.
 //! - **`TradingAction`**: The actions for posting bids and asks (defined in `sim_actions`).
 //! - **`Trade`**: A data structure from `sim_types` representing a matched trade to be settled.
+pub mod behavior;
 pub mod domain;
-pub use domain::*;
\ No newline at end of file
+pub use behavior::*;
+pub use domain::*;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sim_core::*;
+    use uuid::Uuid;
+
+    fn setup_listed_firm() -> (SimState, AgentId, AgentId, AgentId) {
+        let mut state = SimState::default();
+        let cb_id = state.financial_system.central_bank.id;
+        let date = state.current_date;
+        let firm_id = AgentId(Uuid::new_v4());
+        let holder_id = AgentId(Uuid::new_v4());
+        let investor_id = AgentId(Uuid::new_v4());
+        for id in [firm_id, holder_id, investor_id] {
+            state.financial_system.balance_sheets.insert(id, BalanceSheet::new(id));
+        }
+        state.financial_system.create_instrument(cash!(firm_id, 1_000.0, cb_id, date)).unwrap();
+        state.financial_system.create_instrument(cash!(investor_id, 2_000.0, cb_id, date)).unwrap();
+        state.financial_system.create_instrument(equity!(holder_id, firm_id, 100u64, 100u64, 10.0, date)).unwrap();
+        state.financial_system.exchange.register_financial_market(FinancialMarketId::Equity { firm: firm_id });
+        (state, firm_id, holder_id, investor_id)
+    }
+
+    #[test]
+    fn test_share_trade_settles_against_payment() {
+        let (mut state, firm_id, holder_id, investor_id) = setup_listed_firm();
+        let trade = Trade {
+            market_id: MarketId::Financial(FinancialMarketId::Equity { firm: firm_id }),
+            buyer: investor_id,
+            seller: holder_id,
+            quantity: 40.0,
+            price: 12.5,
+        };
+        let result = TradingDomain::new().settle_trade(&trade, &state);
+        assert!(result.success, "{:?}", result.errors);
+        state.apply_effects(&result.effects).unwrap();
+
+        let fs = &state.financial_system;
+        assert_eq!(fs.shares_held(&holder_id, &firm_id), 60);
+        assert_eq!(fs.shares_held(&investor_id, &firm_id), 40);
+        // The gain over the book value of 10 a share is taxed at 15%: 40 * 2.5 * 0.15 = 15
+        assert_eq!(fs.get_cash_assets(&holder_id), 485.0);
+        assert_eq!(fs.get_cash_assets(&investor_id), 1_500.0);
+        assert_eq!(fs.get_bs_by_id(&fs.government.id).unwrap().income_statement.revenue, 15.0);
+        assert_eq!(fs.average_cost(&investor_id, &trade.market_id), Some(12.5));
+    }
+
+    #[test]
+    fn test_corporate_bond_trades_in_issuer_rating_bucket() {
+        let (mut state, firm_id, holder_id, investor_id) = setup_listed_firm();
+        let date = state.current_date;
+        let mut bond = bond!(
+            holder_id,
+            firm_id,
+            5_000.0,
+            0.06,
+            Tenor::T5Y.add_to_date(date),
+            1_000.0,
+            BondType::Corporate { spread: 0.01 },
+            2,
+            Tenor::T5Y,
+            date
+        );
+        bond.set_quantity(5);
+        state.financial_system.create_instrument(bond).unwrap();
+        state.financial_system.credit.ratings.insert(firm_id, CreditRating::A);
+
+        let domain = TradingDomain::new();
+        let ask = |rating| TradingAction::PostAsk {
+            agent_id: holder_id,
+            market_id: MarketId::Financial(FinancialMarketId::CorporateBond { rating }),
+            quantity: 2.0,
+            price: 990.0,
+        };
+        assert!(domain.validate(&ask(CreditRating::A), &state).is_ok());
+        assert!(domain.validate(&ask(CreditRating::AAA), &state).is_err());
+
+        let trade = Trade {
+            market_id: MarketId::Financial(FinancialMarketId::CorporateBond { rating: CreditRating::A }),
+            buyer: investor_id,
+            seller: holder_id,
+            quantity: 2.0,
+            price: 990.0,
+        };
+        let result = domain.settle_trade(&trade, &state);
+        assert!(result.success, "{:?}", result.errors);
+        state.apply_effects(&result.effects).unwrap();
+
+        let fs = &state.financial_system;
+        assert_eq!(fs.corporate_bonds_held(&holder_id, &CreditRating::A), 3);
+        assert_eq!(fs.corporate_bonds_held(&investor_id, &CreditRating::A), 2);
+        assert_eq!(fs.get_cash_assets(&investor_id), 20.0);
+        assert_eq!(fs.get_cash_assets(&holder_id), 1_980.0);
+    }
+
+    #[test]
+    fn test_investor_bids_for_shares_priced_below_dividend_value() {
+        let (mut state, firm_id, _, investor_id) = setup_listed_firm();
+        let market_id = MarketId::Financial(FinancialMarketId::Equity { firm: firm_id });
+        state.history.market_ticks.entry(market_id.clone()).or_default().push_back(MarketTick {
+            date: state.current_date,
+            last_price: Some(8.0),
+            close: Some(8.0),
+            volume: 10.0,
+            turnover: 80.0,
+            ..Default::default()
+        });
+        state.history.earnings_reports.push(EarningsReport {
+            tick: 0,
+            date: state.current_date,
+            firm: firm_id,
+            revenues: 300.0,
+            costs: 100.0,
+            interest_expense: 0.0,
+            net_profit: 200.0,
+            dividends: 50.0,
+            shares_outstanding: 100,
+        });
+
+        // Book value 10 and dividends of 2 a share capitalised at 5% give a fair value of 25
+        let model = EquityInvestorDecisionModel { valuation_noise: 0.0, ..Default::default() };
+        assert_eq!(model.fair_value(&firm_id, &state), Some(25.0));
+        let saver = Consumer::new(40, AgentId::default(), PersonalityArchetype::Saver);
+        let investor = Consumer { id: investor_id, ..saver };
+        let actions = model.decide(&investor, &state, &mut rand::rng());
+        match actions.as_slice() {
+            [SimAction::Trading(TradingAction::PostBid { quantity, price, .. })] => {
+                assert_eq!(*price, 16.5);
+                assert_eq!(*quantity, 6.0);
+            }
+            other => panic!("expected a single bid, got {:?}", other),
+        }
+
+        let view = state.market_view(&market_id).unwrap();
+        assert_eq!(view.market_cap, Some(800.0));
+        assert_eq!(view.pe_ratio, Some(1.0));
+    }
+}
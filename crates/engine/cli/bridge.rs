@@ -21,6 +21,10 @@ pub async fn run_http(state: Arc<AppState>, shutdown: CancellationToken) -> anyh
         .route("/sim/control/state", get(routes::query_state))
         .route("/sim/control/markets", get(routes::query_market_snapshot))
         .route("/sim/control/fs", get(routes::query_fs))
+        .route("/sim/control/clearing", get(routes::query_clearing))
+        .route("/ledger/{agent_id}", get(routes::query_ledger))
+        .route("/credit/events", get(routes::query_credit_events))
+        .route("/credit/ratings", get(routes::query_credit_ratings))
         .with_state(state)
         .layer(cors);
 
@@ -3,7 +3,9 @@ use async_nats::{Client, Message};
 use rand::rngs::ThreadRng;
 use serde_json::json;
 use std::sync::Arc;
-use axum::{extract::{State, Path}, Json};
+use axum::{extract::{State, Path, Query}, Json};
+use serde::Deserialize;
+use sim_core::AgentId;
 
 pub async fn handle_message(msg: Message, client: Client, state: Arc<AppState>) {
     println!("[NATS] Received message on '{}'", msg.subject);
@@ -78,9 +80,13 @@ pub async fn handle_init_sim(
 ) -> Json<serde_json::Value> {
     let mut engine_guard = state.sim_engine.lock().unwrap();
     
-    let engine = state.scenario.initialize_engine();
-    *engine_guard = Some(engine);
-    Json(json!({ "status": "Simulation initialized successfully" }))
+    match state.scenario.initialize_engine() {
+        Ok(engine) => {
+            *engine_guard = Some(engine);
+            Json(json!({ "status": "Simulation initialized successfully" }))
+        }
+        Err(e) => Json(json!({ "error": format!("Invalid scenario: {}", e) })),
+    }
 }
 
 pub async fn tick(
@@ -134,4 +140,105 @@ pub async fn query_fs(
     } else {
         Json(json!({ "error": "Simulation not initialized. Send 'init' command first." }))
     }
-}
\ No newline at end of file
+}
+pub async fn query_clearing(
+    State(state): State<Arc<AppState>>,
+) -> Json<serde_json::Value> {
+    let engine_guard = state.sim_engine.lock().unwrap();
+
+    if let Some(engine) = engine_guard.as_ref() {
+        let reports = &engine.state.history.clearing_reports;
+        Json(json!({ "clearing_reports": reports, "pending": engine.state.financial_system.payment_system.queue }))
+    } else {
+        Json(json!({ "error": "Simulation not initialized. Send 'init' command first." }))
+    }
+}
+
+const DEFAULT_LEDGER_PAGE_SIZE: usize = 50;
+const MAX_LEDGER_PAGE_SIZE: usize = 500;
+
+#[derive(Debug, Deserialize)]
+pub struct LedgerQuery {
+    pub offset: Option<usize>,
+    pub limit: Option<usize>,
+}
+
+pub async fn query_ledger(
+    Path(agent_id): Path<String>,
+    Query(query): Query<LedgerQuery>,
+    State(state): State<Arc<AppState>>,
+) -> Json<serde_json::Value> {
+    let engine_guard = state.sim_engine.lock().unwrap();
+
+    let Some(engine) = engine_guard.as_ref() else {
+        return Json(json!({ "error": "Simulation not initialized. Send 'init' command first." }));
+    };
+    let agent_id = match agent_id.parse::<AgentId>() {
+        Ok(id) => id,
+        Err(_) => return Json(json!({ "error": format!("Invalid agent id: {}", agent_id) })),
+    };
+
+    let offset = query.offset.unwrap_or(0);
+    let limit = query.limit.unwrap_or(DEFAULT_LEDGER_PAGE_SIZE).min(MAX_LEDGER_PAGE_SIZE);
+    let page = engine.state.history.ledger_page(&agent_id, offset, limit);
+    Json(json!({ "ledger": page }))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreditEventQuery {
+    pub agent_id: Option<String>,
+    pub defaults_only: Option<bool>,
+}
+
+pub async fn query_credit_events(
+    Query(query): Query<CreditEventQuery>,
+    State(state): State<Arc<AppState>>,
+) -> Json<serde_json::Value> {
+    let engine_guard = state.sim_engine.lock().unwrap();
+
+    let Some(engine) = engine_guard.as_ref() else {
+        return Json(json!({ "error": "Simulation not initialized. Send 'init' command first." }));
+    };
+    let agent_id = match query.agent_id.as_deref().map(str::parse::<AgentId>).transpose() {
+        Ok(id) => id,
+        Err(_) => return Json(json!({ "error": format!("Invalid agent id: {:?}", query.agent_id) })),
+    };
+
+    let credit = &engine.state.financial_system.credit;
+    let events: Vec<_> = match &agent_id {
+        Some(id) => credit.events_for(id).collect(),
+        None => credit.events.iter().collect(),
+    };
+    let events: Vec<_> =
+        events.into_iter().filter(|event| !query.defaults_only.unwrap_or(false) || event.is_default()).collect();
+    Json(json!({ "credit_events": events, "ratings": credit.ratings }))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RatingQuery {
+    pub agent_id: Option<String>,
+}
+
+pub async fn query_credit_ratings(
+    Query(query): Query<RatingQuery>,
+    State(state): State<Arc<AppState>>,
+) -> Json<serde_json::Value> {
+    let engine_guard = state.sim_engine.lock().unwrap();
+
+    let Some(engine) = engine_guard.as_ref() else {
+        return Json(json!({ "error": "Simulation not initialized. Send 'init' command first." }));
+    };
+    let agent_id = match query.agent_id.as_deref().map(str::parse::<AgentId>).transpose() {
+        Ok(id) => id,
+        Err(_) => return Json(json!({ "error": format!("Invalid agent id: {:?}", query.agent_id) })),
+    };
+
+    let credit = &engine.state.financial_system.credit;
+    match &agent_id {
+        Some(id) => Json(json!({
+            "rating": credit.rating(id),
+            "history": credit.rating_history_for(id).collect::<Vec<_>>(),
+        })),
+        None => Json(json!({ "ratings": credit.ratings, "history": credit.rating_history })),
+    }
+}
@@ -38,6 +38,11 @@ impl SimulationEngine {
         if let Some(model) = self.decision_models.get(&government.id) {
             all_actions.extend(model.decide(government, &self.state, rng));
         }
+
+        let central_bank = &self.state.financial_system.central_bank;
+        if let Some(model) = self.decision_models.get(&central_bank.id) {
+            all_actions.extend(model.decide(central_bank, &self.state, rng));
+        }
         all_actions
     }
 
@@ -50,23 +55,65 @@ impl SimulationEngine {
 
         // Execute actions (this includes posting bids from PurchaseAtBest)
         let effects = self.execute_actions(&actions);
-        if let Err(e) = self.state.apply_effects(&effects) {
-            println!("[ERROR] applying action effects: {}", e);
-        }
+
+        // The yield curve is read off the Treasury quotes before the books are cleared
+        let current_date = self.state.current_date;
+        self.state.financial_system.update_yield_curve(current_date);
+        let curve = self.state.financial_system.yield_curve.clone();
+        self.state.history.yield_curves.push(curve);
 
         // Clear Markets
         // Modified: clear_markets now returns trades and snapshots (Point 1)
-        let (trades, snapshots) = self.state.financial_system.exchange.clear_markets();
+        let business_day = self.state.financial_system.calendar.is_business_day(current_date);
+        let (trades, snapshots) = self.state.financial_system.exchange.clear_markets(business_day);
 
         // New: Process trades and snapshots into MarketTicks and update history (Point 1)
         self.update_market_history(&trades, &snapshots);
+        let cpi = self.state.cpi_view().cpi;
+        self.state.history.cpi.push((current_date, cpi));
 
         // Settle the resulting trades
-        let settlement_effects = self.settle_trades(&trades);
-        if let Err(e) = self.state.apply_effects(&settlement_effects) {
-            println!("[ERROR] applying settlement effects: {}", e);
+        self.settle_trades(&trades);
+
+        // Firms' job offers are matched with the day's applications
+        let market_id = LabourMarketId::GeneralLabour;
+        let labour_action = SimAction::Labour(LabourAction::ClearLabourMarket { market_id });
+        let labour_effects = self.domain_registry.execute(&labour_action, &self.state);
+        if let Err(e) = self.state.apply_effects(&labour_effects) {
+            println!("[ERROR] applying labour market effects: {}", e);
+        }
+        actions.push(labour_action);
+
+        // End of day: settle the interbank payment queue net in reserves. On holidays the queue rolls
+        // to the next business day.
+        if business_day {
+            let clearing_action = SimAction::Clearing(ClearingAction::SettleNetPositions);
+            let clearing_effects = self.domain_registry.execute(&clearing_action, &self.state);
+            if let Err(e) = self.state.apply_effects(&clearing_effects) {
+                println!("[ERROR] applying clearing effects: {}", e);
+            }
+            actions.push(clearing_action);
+
+            // Reserves are held against the requirement on average over each maintenance period
+            let compliance_action = SimAction::Monetary(MonetaryAction::AssessReserveCompliance);
+            let compliance_effects = self.domain_registry.execute(&compliance_action, &self.state);
+            if let Err(e) = self.state.apply_effects(&compliance_effects) {
+                println!("[ERROR] applying reserve compliance effects: {}", e);
+            }
+            actions.push(compliance_action);
         }
 
+        // Instruments still delinquent after the grace period put their debtors into default
+        let credit_action = SimAction::Credit(CreditAction::ReviewDelinquencies);
+        let credit_effects = self.domain_registry.execute(&credit_action, &self.state);
+        if let Err(e) = self.state.apply_effects(&credit_effects) {
+            println!("[ERROR] applying credit effects: {}", e);
+        }
+        actions.push(credit_action);
+
+        let central_bank_balance_sheet = self.state.financial_system.central_bank_balance_sheet(current_date);
+        self.state.history.central_bank_balance_sheets.push(central_bank_balance_sheet);
+
         self.state.advance_time();
 
         TickResult { tick_number: self.state.ticknum, actions, effects, trades }
@@ -169,77 +216,144 @@ impl SimulationEngine {
     }
 
 
-    fn execute_actions(&self, actions: &[SimAction]) -> Vec<StateEffect> {
+    /// Executes each action against the books left by the ones before it, so that an agent making
+    /// several payments in a tick is debited for every one of them and cannot spend the same money
+    /// twice. An action whose effects cannot be applied leaves the rest of the batch to run.
+    fn execute_actions(&mut self, actions: &[SimAction]) -> Vec<StateEffect> {
         let mut all_effects = Vec::new();
         for action in actions {
             let effects = self.domain_registry.execute(action, &self.state);
+            if let Err(e) = self.state.apply_effects(&effects) {
+                println!("[ERROR] applying effects of {}: {}", action.name(), e);
+            }
             all_effects.extend(effects);
         }
         all_effects
     }
 
-    // Updated to use the general settle_trade method
-    fn settle_trades(&self, trades: &[Trade]) -> Vec<StateEffect> {
-        let mut all_effects = Vec::new();
+    /// Settles each trade against the books left by the ones before it, so that an agent on several
+    /// trades in a day pays or delivers for every one of them.
+    fn settle_trades(&mut self, trades: &[Trade]) {
         for trade in trades {
             // Use the TradingDomain for settlement (handles goods and financial)
             let result = self.domain_registry.settle_trade(trade, &self.state);
 
             if result.success {
-                all_effects.extend(result.effects);
+                let mut effects = result.effects;
                 // Point 7: Record trades in history explicitly during settlement
-                all_effects.push(StateEffect::Market(MarketEffect::ExecuteTrade(trade.clone())));
+                effects.push(StateEffect::Market(MarketEffect::ExecuteTrade(trade.clone())));
+                if let Err(e) = self.state.apply_effects(&effects) {
+                    println!("[ERROR] applying settlement effects: {}", e);
+                }
             } else {
                 println!("[Executor] Trade settlement failed: {:?}", result.errors);
             }
         }
-        all_effects
     }
     fn process_financial_updates(&self) -> Vec<SimAction> {
         let mut actions = Vec::new();
         let current_date = self.state.current_date;
+        let calendar = &self.state.financial_system.calendar;
+        // Payments settle on business days only: anything that fell due since the last one is paid today
+        let business_day = calendar.is_business_day(current_date);
+        let last_business_day = previous_business_day(current_date, calendar);
 
         for (instrument_id, instrument) in &self.state.financial_system.instruments {
+            // Defaulted claims are in workout: they no longer accrue or pay
+            if self.state.financial_system.credit.is_defaulted(instrument_id) {
+                continue;
+            }
+
             if self.is_interest_bearing(instrument) {
                 actions.push(SimAction::Settlement(SettlementAction::AccrueInterest {
                     instrument_id: *instrument_id,
                 }));
             }
 
-            if self.is_interest_payment_date(current_date) && instrument.accrued_interest > 0.0 {
+            // Floating rates are refixed after today's interest has accrued at the old rate
+            if business_day && instrument.floating_rate().is_some_and(|floating| floating.is_due(current_date)) {
+                actions.push(SimAction::Settlement(SettlementAction::ResetRate { instrument_id: *instrument_id }));
+            }
+
+            // Bonds, government and corporate, pay their interest as coupons on their schedule.
+            // Other instruments pay on their schedule too, or at each month end if open-ended.
+            let is_bond = instrument.details.as_any().is::<BondDetails>();
+            let scheduled_payment_due =
+                business_day && instrument.has_payment_between(last_business_day, current_date);
+            let interest_due = if instrument.payment_schedule.is_empty() {
+                self.is_interest_payment_date(current_date)
+            } else {
+                scheduled_payment_due
+            };
+            if interest_due && instrument.accrued_interest > 0.0 && !is_bond {
                 actions.push(SimAction::Settlement(SettlementAction::PayInterest {
                     instrument_id: *instrument_id,
                 }));
             }
-            
-            if let Some(bond_details) = instrument.details.as_any().downcast_ref::<BondDetails>() {
-                if self.is_coupon_payment_date(current_date, instrument, bond_details) {
-                     actions.push(SimAction::Settlement(SettlementAction::ProcessCouponPayment { instrument_id: *instrument_id }));
+
+            if let Some(bond) = instrument.details.as_any().downcast_ref::<BondDetails>() {
+                if scheduled_payment_due && !bond.is_zero_coupon() {
+                    let coupon = SettlementAction::ProcessCouponPayment { instrument_id: *instrument_id };
+                    actions.push(SimAction::Settlement(coupon));
                 }
+                // Bonds and bills are redeemed at par on the first business day from maturity
+                if business_day && bond.maturity_date <= current_date {
+                    actions.push(SimAction::Settlement(SettlementAction::RedeemBond { instrument_id: *instrument_id }));
+                }
+            }
+
+            let overnight_loan_due = business_day
+                && instrument
+                    .details
+                    .as_any()
+                    .downcast_ref::<LoanDetails>()
+                    .is_some_and(|loan| loan.is_overnight_funding() && loan.maturity_date <= current_date);
+            if overnight_loan_due {
+                let instrument_id = *instrument_id;
+                actions.push(SimAction::Settlement(SettlementAction::RepayInterbankLoan { instrument_id }));
             }
         }
 
+        // Ratings are reviewed each month end, on the accounts before the quarter is closed
+        if self.is_interest_payment_date(current_date) {
+            actions.push(SimAction::Credit(CreditAction::RateBorrowers));
+        }
+
+        if self.is_dividend_date(current_date) {
+            let mut firm_ids: Vec<AgentId> = self.state.agents.firms.keys().copied().collect();
+            firm_ids.sort_by_key(|id| id.0);
+            actions.extend(
+                firm_ids
+                    .into_iter()
+                    .map(|firm_id| SimAction::Settlement(SettlementAction::DistributeDividends { firm_id })),
+            );
+        }
+
         actions
     }
 
+    /// Overnight loans are repaid with their interest in one payment, so they do not accrue.
     fn is_interest_bearing(&self, instrument: &FinancialInstrument) -> bool {
         instrument.details.as_any().is::<DemandDepositDetails>()
             || instrument.details.as_any().is::<SavingsDepositDetails>()
             || instrument.details.as_any().is::<BondDetails>()
+            || instrument.details.as_any().is::<CentralBankReservesDetails>()
+            || instrument
+                .details
+                .as_any()
+                .downcast_ref::<LoanDetails>()
+                .is_some_and(|loan| !loan.is_overnight_funding())
     }
 
+    /// Month-end payments fall on the last business day of the month.
     fn is_interest_payment_date(&self, date: NaiveDate) -> bool {
-        let next_day = date + chrono::Duration::days(1);
-        date.month() != next_day.month()
+        let calendar = &self.state.financial_system.calendar;
+        calendar.is_business_day(date) && next_business_day(date, calendar).month() != date.month()
     }
     
-    fn is_coupon_payment_date(&self, date: NaiveDate, instrument: &FinancialInstrument, bond_details: &BondDetails) -> bool {
-        let months_between_payments = (12 / bond_details.frequency) as u32;
-        let months_since_origination = (date.year() - instrument.originated_date.year()) * 12 + (date.month() as i32 - instrument.originated_date.month() as i32);
-        
-        instrument.originated_date.day() == date.day() &&
-        months_since_origination > 0 &&
-        months_since_origination as u32 % months_between_payments == 0
+    /// Firms close their books and pay dividends at the end of each quarter.
+    fn is_dividend_date(&self, date: NaiveDate) -> bool {
+        self.is_interest_payment_date(date) && date.month().is_multiple_of(3)
     }
 }
 
@@ -249,4 +363,49 @@ pub struct TickResult {
     pub actions: Vec<SimAction>,
     pub effects: Vec<StateEffect>,
     pub trades: Vec<Trade>,
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_payments_in_one_tick_each_debit_the_payer() {
+        let mut state = SimState::default();
+        let cb_id = state.financial_system.central_bank.id;
+        let bank = Bank::new("Test Bank".to_string(), 0.0, 0.0);
+        let bank_id = bank.id;
+        state.agents.banks.insert(bank_id, bank);
+        let [payer, first, second] = [(); 3].map(|_| AgentId(Uuid::new_v4()));
+        for id in [payer, first, second] {
+            let consumer = Consumer::new(40, bank_id, PersonalityArchetype::Balanced);
+            state.agents.consumers.insert(id, Consumer { id, ..consumer });
+        }
+        for id in [bank_id, payer, first, second] {
+            state.financial_system.balance_sheets.insert(id, BalanceSheet::new(id));
+        }
+        let date = state.current_date;
+        state.financial_system.create_instrument(reserves!(bank_id, cb_id, 1_000.0, date)).unwrap();
+        state.financial_system.create_instrument(deposit!(payer, bank_id, 100.0, 0.0, date)).unwrap();
+
+        let mut engine = SimulationEngine::new(state);
+        let pay = |to| SimAction::Banking(BankingAction::Transfer { from: payer, to, amount: 80.0 });
+        engine.execute_actions(&[pay(first), pay(second)]);
+
+        let fs = &engine.state.financial_system;
+        assert!((fs.get_liquid_assets(&payer) - 20.0).abs() < 1e-6);
+        assert!((fs.get_liquid_assets(&first) - 80.0).abs() < 1e-6);
+        assert!(fs.get_liquid_assets(&second).abs() < 1e-6, "The second payment is refused, not made from nothing");
+
+        // Two payments that would each drain the same cash: the second is refused and the actions after
+        // it still run
+        let cash = cash!(payer, 20.0, cb_id, engine.state.current_date);
+        engine.state.financial_system.create_instrument(cash).unwrap();
+        let pay = |from, to, amount| SimAction::Banking(BankingAction::Transfer { from, to, amount });
+        engine.execute_actions(&[pay(payer, first, 40.0), pay(payer, second, 40.0), pay(first, second, 10.0)]);
+        let fs = &engine.state.financial_system;
+        assert!(fs.get_liquid_assets(&payer).abs() < 1e-6);
+        assert!((fs.get_liquid_assets(&first) - 110.0).abs() < 1e-6);
+        assert!((fs.get_liquid_assets(&second) - 10.0).abs() < 1e-6);
+    }
+}
@@ -30,24 +30,32 @@ impl<'a> AgentFactory<'a> {
         for bond_conf in &config.initial_bonds {
             let tenor = Tenor::from_str(&bond_conf.tenor).unwrap();
             let maturity_date = tenor.add_to_date(self.state.current_date);
-            let coupon_rate = policy_rate;
+            // Bills pay no coupon: they are bought at a discount and redeemed at par
+            let coupon_rate = if tenor.is_bill() { 0.0 } else { policy_rate };
             let quantity = bond_conf.quantity as u64;
 
+            let details = BondDetails {
+                bond_type: BondType::Government,
+                coupon_rate,
+                face_value: STANDARD_BOND_FACE_VALUE,
+                maturity_date,
+                frequency: 2,
+                tenor,
+                quantity,
+                floating: None,
+                inflation_linked: None,
+            };
+            let calendar = &self.state.financial_system.calendar;
+            let payment_schedule = details.coupon_schedule(self.state.current_date, calendar);
             let bond_instrument = FinancialInstrument {
                 id: InstrumentId(uuid::Uuid::new_v4()),
                 creditor: bank.id,
                 debtor: government_id,
 
                 principal: STANDARD_BOND_FACE_VALUE * quantity as f64,
-                details: Box::new(BondDetails {
-                    bond_type: BondType::Government,
-                    coupon_rate,
-                    face_value: STANDARD_BOND_FACE_VALUE,
-                    maturity_date,
-                    frequency: 2,
-                    tenor,
-                    quantity,
-                }),
+                day_count: details.bond_type.day_count(),
+                payment_schedule,
+                details: Box::new(details),
                 originated_date: self.state.current_date,
                 accrued_interest: 0.0,
                 last_accrual_date: self.state.current_date,
@@ -101,4 +109,20 @@ impl<'a> AgentFactory<'a> {
         self.state.agents.firms.insert(firm.id, firm.clone());
         firm
     }
+
+    /// Issues a firm's shares to its initial holders. Without an issue price the shares are priced at
+    /// book value, so the firm's net worth passes entirely to its shareholders.
+    pub fn issue_equity(&mut self, firm_id: AgentId, allocations: &[(AgentId, u64)], issue_price: Option<f64>) {
+        let shares_outstanding: u64 = allocations.iter().map(|(_, shares)| shares).sum();
+        if shares_outstanding == 0 {
+            return;
+        }
+        let book_value = self.state.financial_system.get_bs_by_id(&firm_id).map_or(0.0, |bs| bs.net_worth());
+        let price = issue_price.unwrap_or(book_value / shares_outstanding as f64);
+
+        for (holder, shares) in allocations.iter().filter(|(_, shares)| *shares > 0) {
+            let holding = equity!(*holder, firm_id, *shares, shares_outstanding, price, self.state.current_date);
+            self.state.financial_system.create_or_consolidate_instrument(holding).unwrap();
+        }
+    }
 }
\ No newline at end of file
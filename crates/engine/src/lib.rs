@@ -17,8 +17,9 @@
 //! - **`tick()` method**: The heart of the simulation. In each tick, it performs the following steps:
 //!   1.  **Process Financial Updates**: Handles time-based events like interest accrual.
 //!   2.  **Collect Actions**: Queries the `DecisionModel` of every agent to gather their desired `SimAction`s.
-//!   3.  **Execute Actions**: Passes the collected actions to the `DomainRegistry` to be validated and executed, producing a set of `StateEffect`s.
-//!   4.  **Apply Effects**: Applies the generated effects to the `SimState`.
+//!   3.  **Execute Actions**: Passes the collected actions to the `DomainRegistry` one at a time to be
+//!       validated and executed, producing a set of `StateEffect`s.
+//!   4.  **Apply Effects**: Applies each action's effects to the `SimState` before the next action is executed.
 //!   5.  **Clear Markets**: Calls the `Exchange` to match bids and asks, generating `Trade`s.
 //!   6.  **Settle Trades**: Passes the `Trade`s to the `TradingDomain` to generate settlement effects.
 //!   7.  **Apply Settlement Effects**: Applies the final settlement effects.
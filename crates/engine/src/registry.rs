@@ -26,6 +26,9 @@ impl DomainRegistry {
             SimAction::Settlement(_) => "Settlement",
             SimAction::Trading(_) => "Trading",
             SimAction::Labour(_) => "Labour",
+            SimAction::Clearing(_) => "Clearing",
+            SimAction::Credit(_) => "Credit",
+            SimAction::Monetary(_) => "Monetary",
         };
 
         if let Some(domain) = self.domains.get(domain_name) {
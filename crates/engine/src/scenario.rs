@@ -2,12 +2,25 @@ use crate::factory::AgentFactory;
 use serde::Deserialize;
 use sim_core::*;
 use std::{collections::HashMap, str::FromStr};
+use thiserror::Error;
 use uuid::Uuid;
 use crate::*;
 use domains::prelude::*;
 use domains::consumption::CESConsumerDecisionModel;
 
 const _SCENARIO_NAMESPACE: Uuid = uuid::uuid!("6E62B743-2623-404B-84C8-45F48A85189A");
+const DEFAULT_SHARES_OUTSTANDING: u64 = 100_000;
+
+/// A scenario that parses but does not describe a consistent economy.
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum ScenarioError {
+    #[error("Unknown holiday calendar {0}")]
+    UnknownCalendar(String),
+    #[error("{agent} refers to unknown agent {reference}")]
+    UnknownAgent { agent: String, reference: String },
+    #[error("Shareholdings of {firm} add up to {allocated} shares, not its {shares_outstanding} outstanding")]
+    UnallocatedShares { firm: String, allocated: u64, shares_outstanding: u64 },
+}
 
 #[derive(Debug, Deserialize)]
 pub struct Scenario {
@@ -17,6 +30,9 @@ pub struct Scenario {
     banks: Vec<BankConfig>,
     firms: Vec<FirmConfig>,
     consumers: Vec<ConsumerConfig>,
+    /// Holiday calendars defined by the scenario, in addition to the built-in ones.
+    #[serde(default)]
+    calendars: Vec<RuleCalendar>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -24,6 +40,31 @@ pub struct Scenario {
 pub struct ScenarioConfig {
     iterations: u32,
     treasury_tenors_to_register: Vec<String>,
+    /// Name of the holiday calendar markets and payments follow: one of the scenario's `calendars`
+    /// or a built-in calendar. Weekends only if unset.
+    #[serde(default)]
+    calendar: Option<String>,
+    /// The central bank's reaction function, meeting schedule and asset purchases.
+    #[serde(default)]
+    monetary_policy: TaylorRuleDecisionModel,
+    /// Spreads of the central bank's standing facilities around the policy rate.
+    #[serde(default)]
+    standing_facilities: StandingFacilities,
+    /// The government's budget and the rules it spends and borrows by.
+    #[serde(default)]
+    fiscal_policy: FiscalPolicyConfig,
+}
+
+/// The government's annual spending targets, the policy it steers the budget by and its
+/// unemployment insurance scheme, alongside the decision model's transfer rules and auction calendar.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct FiscalPolicyConfig {
+    pub spending_targets: SpendingTargets,
+    pub policy: FiscalPolicy,
+    pub unemployment_insurance: UnemploymentInsurance,
+    #[serde(flatten)]
+    pub model: BasicGovernmentDecisionModel,
 }
 
 #[derive(Debug, Deserialize)]
@@ -44,6 +85,26 @@ pub struct FirmConfig {
     pub recipe_name: String,
     pub initial_cash: f64,
     pub initial_inventory: Vec<InventoryConfig>,
+    #[serde(default)]
+    pub equity: Option<EquityConfig>,
+}
+
+/// A firm's share issue. Without explicit `shareholders` the shares are split evenly across every
+/// consumer and bank; without an `issuePrice` they are issued at book value.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EquityConfig {
+    pub shares_outstanding: u64,
+    pub issue_price: Option<f64>,
+    #[serde(default)]
+    pub shareholders: Vec<ShareholderConfig>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShareholderConfig {
+    pub holder_id: String,
+    pub shares: u64,
 }
 
 #[derive(Debug, Deserialize)]
@@ -75,10 +136,62 @@ impl Scenario {
         toml::from_str(toml_str)
     }
 
-    pub fn initialize_engine(&self) -> SimulationEngine {
+    /// Who holds each of a firm's shares at load, as `(holder, shares)`.
+    fn share_allocations(
+        &self, firm_conf: &FirmConfig, agent_ids: &HashMap<String, AgentId>, default_holders: &[AgentId],
+    ) -> Result<Vec<(AgentId, u64)>, ScenarioError> {
+        match &firm_conf.equity {
+            Some(equity) if !equity.shareholders.is_empty() => {
+                let allocations = equity
+                    .shareholders
+                    .iter()
+                    .map(|holder| Ok((lookup(agent_ids, &firm_conf.id, &holder.holder_id)?, holder.shares)))
+                    .collect::<Result<Vec<(AgentId, u64)>, ScenarioError>>()?;
+                let allocated: u64 = allocations.iter().map(|(_, shares)| shares).sum();
+                if allocated != equity.shares_outstanding {
+                    return Err(ScenarioError::UnallocatedShares {
+                        firm: firm_conf.id.clone(),
+                        allocated,
+                        shares_outstanding: equity.shares_outstanding,
+                    });
+                }
+                Ok(allocations)
+            }
+            equity => {
+                let shares_outstanding = equity.as_ref().map_or(DEFAULT_SHARES_OUTSTANDING, |e| e.shares_outstanding);
+                let holders = default_holders.len().max(1) as u64;
+                Ok(default_holders
+                    .iter()
+                    .enumerate()
+                    .map(|(i, holder)| {
+                        let odd_share = u64::from((i as u64) < shares_outstanding % holders);
+                        (*holder, shares_outstanding / holders + odd_share)
+                    })
+                    .collect())
+            }
+        }
+    }
+
+    pub fn initialize_engine(&self) -> Result<SimulationEngine, ScenarioError> {
         let mut state = SimState::default();
         state.config.iterations = self.config.iterations;
         state.financial_system.goods = goods::CATALOGUE.clone();
+        if let Some(name) = &self.config.calendar {
+            state.financial_system.calendar = self
+                .calendars
+                .iter()
+                .find(|calendar| calendar.name == *name)
+                .cloned()
+                .or_else(|| RuleCalendar::builtin(name))
+                .ok_or_else(|| ScenarioError::UnknownCalendar(name.clone()))?;
+        }
+
+        state.financial_system.central_bank.facilities = self.config.standing_facilities.clone();
+        let fiscal = &self.config.fiscal_policy;
+        let government = &mut state.financial_system.government;
+        government.spending_targets = fiscal.spending_targets.clone();
+        government.fiscal_policy = fiscal.policy.clone();
+        government.unemployment_insurance = fiscal.unemployment_insurance.clone();
 
         let cb_id = state.financial_system.central_bank.id;
         let mut rng = rand::rng();
@@ -92,17 +205,30 @@ impl Scenario {
         }
 
         for consumer_conf in &self.consumers {
-            let bank_id = *agent_ids.get(&consumer_conf.bank_id).expect("Bank not found for consumer");
+            let bank_id = lookup(&agent_ids, &consumer_conf.id, &consumer_conf.bank_id)?;
             let consumer = factory.create_consumer(consumer_conf, bank_id, cb_id);
             agent_ids.insert(consumer_conf.id.clone(), consumer.id);
         }
 
         for firm_conf in &self.firms {
-            let bank_id = *agent_ids.get(&firm_conf.bank_id).expect("Bank not found for firm");
+            let bank_id = lookup(&agent_ids, &firm_conf.id, &firm_conf.bank_id)?;
             let firm = factory.create_firm(firm_conf, bank_id, cb_id);
             agent_ids.insert(firm_conf.id.clone(), firm.id);
         }
 
+        let default_holders: Vec<AgentId> = self
+            .consumers
+            .iter()
+            .map(|c| &c.id)
+            .chain(self.banks.iter().map(|b| &b.id))
+            .map(|id| agent_ids[id])
+            .collect();
+        for firm_conf in &self.firms {
+            let firm_id = agent_ids[&firm_conf.id];
+            let allocations = self.share_allocations(firm_conf, &agent_ids, &default_holders)?;
+            factory.issue_equity(firm_id, &allocations, firm_conf.equity.as_ref().and_then(|e| e.issue_price));
+        }
+
         let goods_ref = &state.financial_system.goods;
         state.financial_system.exchange.register_goods_market(good_id!("petrol"), goods_ref);
         state.financial_system.exchange.register_goods_market(good_id!("oil"), goods_ref);
@@ -111,25 +237,49 @@ impl Scenario {
             state.financial_system.exchange.register_financial_market(FinancialMarketId::Treasury { tenor });
         }
         state.financial_system.exchange.register_financial_market(FinancialMarketId::SecuredOvernightFinancing);
+        for rating in CreditRating::PERFORMING {
+            state.financial_system.exchange.register_financial_market(FinancialMarketId::CorporateBond { rating });
+        }
+        for firm_conf in &self.firms {
+            let firm = agent_ids[&firm_conf.id];
+            state.financial_system.exchange.register_financial_market(FinancialMarketId::Equity { firm });
+        }
 
         state.financial_system.exchange.register_labour_market(LabourMarketId::GeneralLabour);
 
         let mut engine = SimulationEngine::new(state);
 
+        let with_investor = |model: Box<dyn DecisionModel>| -> Box<dyn DecisionModel> {
+            Box::new(CompositeDecisionModel {
+                models: vec![model, Box::new(EquityInvestorDecisionModel::default())],
+            })
+        };
         for bank_id in engine.state.agents.banks.keys() {
-            engine.decision_models.insert(*bank_id, Box::new(BasicBankDecisionModel::default()));
+            engine.decision_models.insert(*bank_id, with_investor(Box::<BasicBankDecisionModel>::default()));
         }
         for consumer_id in engine.state.agents.consumers.keys() {
-            engine.decision_models.insert(*consumer_id, Box::new(CESConsumerDecisionModel::default()));
+            engine.decision_models.insert(*consumer_id, with_investor(Box::<CESConsumerDecisionModel>::default()));
         }
         for firm_id in engine.state.agents.firms.keys() {
             engine.decision_models.insert(*firm_id, Box::new(BasicFirmDecisionModel::default()));
         }
         engine.decision_models.insert(
             engine.state.financial_system.government.id,
-            Box::new(BasicGovernmentDecisionModel::default()),
-        ); 
+            Box::new(self.config.fiscal_policy.model.clone()),
+        );
+        engine.decision_models.insert(
+            engine.state.financial_system.central_bank.id,
+            Box::new(self.config.monetary_policy.clone()),
+        );
         engine.run_initialization();
-        engine
+        Ok(engine)
     }
+}
+
+/// The id a scenario agent was given at load, for a reference made by `agent`.
+fn lookup(agent_ids: &HashMap<String, AgentId>, agent: &str, reference: &str) -> Result<AgentId, ScenarioError> {
+    agent_ids
+        .get(reference)
+        .copied()
+        .ok_or_else(|| ScenarioError::UnknownAgent { agent: agent.to_string(), reference: reference.to_string() })
 }
\ No newline at end of file
@@ -6,9 +6,15 @@ pub enum BankingAction {
     Deposit { agent_id: AgentId, bank: AgentId, amount: f64 },
     Withdraw { agent_id: AgentId, bank: AgentId, amount: f64 },
     Transfer { from: AgentId, to: AgentId, amount: f64 },
-    PayWages { agent_id: AgentId, employee: AgentId, amount: f64 },
-    UpdateReserves { bank: AgentId, amount_change: f64 },
+    /// Pays every employee of the firm a week's wages.
+    PayPayroll { agent_id: AgentId },
+    /// Borrows reserves overnight from the central bank's lending facility against Treasuries.
+    BorrowFromFacility { bank: AgentId, amount: f64 },
+    /// Places reserves overnight at the central bank's deposit facility.
+    DepositAtFacility { bank: AgentId, amount: f64 },
     InjectLiquidity,
+    /// Asks every bank for the loan, taking the cheapest offer.
+    RequestLoan { request: LoanRequest },
 }
 
 impl BankingAction {
@@ -17,9 +23,11 @@ impl BankingAction {
             BankingAction::Deposit { .. } => "Deposit",
             BankingAction::Withdraw { .. } => "Withdraw", 
             BankingAction::Transfer { .. } => "Transfer",
-            BankingAction::PayWages { .. } => "PayWages",
-            BankingAction::UpdateReserves { .. } => "UpdateReserves",
+            BankingAction::PayPayroll { .. } => "PayPayroll",
+            BankingAction::BorrowFromFacility { .. } => "BorrowFromFacility",
+            BankingAction::DepositAtFacility { .. } => "DepositAtFacility",
             BankingAction::InjectLiquidity => "InjectLiquidity",
+            BankingAction::RequestLoan { .. } => "RequestLoan",
         }
     }
 
@@ -28,9 +36,11 @@ impl BankingAction {
             BankingAction::Deposit { agent_id, .. } => *agent_id,
             BankingAction::Withdraw { agent_id, .. } => *agent_id,
             BankingAction::Transfer { from, .. } => *from,
-            BankingAction::PayWages { agent_id, .. } => *agent_id,
-            BankingAction::UpdateReserves { bank, .. } => *bank,
+            BankingAction::PayPayroll { agent_id } => *agent_id,
+            BankingAction::BorrowFromFacility { bank, .. } => *bank,
+            BankingAction::DepositAtFacility { bank, .. } => *bank,
             BankingAction::InjectLiquidity => AgentId::default(), // System action
+            BankingAction::RequestLoan { request } => request.borrower,
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,19 @@
+use crate::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum ClearingAction {
+    SettleNetPositions,
+}
+
+impl ClearingAction {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ClearingAction::SettleNetPositions => "SettleNetPositions",
+        }
+    }
+
+    pub fn agent_id(&self) -> AgentId {
+        AgentId::default()
+    }
+}
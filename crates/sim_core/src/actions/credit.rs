@@ -0,0 +1,22 @@
+use crate::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum CreditAction {
+    ReviewDelinquencies,
+    /// Rate every firm from its latest accounts.
+    RateBorrowers,
+}
+
+impl CreditAction {
+    pub fn name(&self) -> &'static str {
+        match self {
+            CreditAction::ReviewDelinquencies => "ReviewDelinquencies",
+            CreditAction::RateBorrowers => "RateBorrowers",
+        }
+    }
+
+    pub fn agent_id(&self) -> AgentId {
+        AgentId::default()
+    }
+}
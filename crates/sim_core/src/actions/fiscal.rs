@@ -1,4 +1,5 @@
 use crate::*;
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -8,31 +9,71 @@ pub enum FiscalAction {
         tax_type: TaxType,
         new_rate: f64,
     },
-    IssueDebt {
+    /// Announces an auction of `offering` new bonds of `tenor` for `auction_date`, within the debt
+    /// ceiling.
+    AnnounceAuction {
         government_id: AgentId,
         tenor: Tenor,
-        face_value: f64,
+        inflation_linked: bool,
+        offering: u64,
+        auction_date: NaiveDate,
+        format: AuctionFormat,
+    },
+    /// Tenders a bid into an open auction.
+    SubmitAuctionBid {
+        auction_id: AuctionId,
+        bid: AuctionBid,
+    },
+    /// Allots the auction on its date, issuing the bonds against payment.
+    HoldAuction {
+        government_id: AgentId,
+        auction_id: AuctionId,
     },
     SetSpendingTarget {
         government_id: AgentId,
-        target: SpendingTargets,
+        category: SpendingCategory,
         new_level: f64,
-    }
+    },
+    /// Makes the government's transfer and investment payments for the day in one go.
+    Disburse {
+        government_id: AgentId,
+        payments: Vec<Disbursement>,
+    },
+    CollectTax {
+        government_id: AgentId,
+        taxpayer: AgentId,
+        tax_type: TaxType,
+        amount: f64,
+    },
+    /// Closes the government's books for the period into a `FiscalReport`.
+    CloseFiscalPeriod {
+        government_id: AgentId,
+    },
 }
 
 impl FiscalAction {
     pub fn name(&self) -> &'static str {
         match self {
             FiscalAction::ChangeTaxRate { .. } => "ChangeTaxRate",
-            FiscalAction::IssueDebt { .. } => "IssueDebt",
+            FiscalAction::AnnounceAuction { .. } => "AnnounceAuction",
+            FiscalAction::SubmitAuctionBid { .. } => "SubmitAuctionBid",
+            FiscalAction::HoldAuction { .. } => "HoldAuction",
             FiscalAction::SetSpendingTarget { .. } => "SetSpendingTarget",
+            FiscalAction::Disburse { .. } => "Disburse",
+            FiscalAction::CollectTax { .. } => "CollectTax",
+            FiscalAction::CloseFiscalPeriod { .. } => "CloseFiscalPeriod",
         }
     }
     pub fn agent_id(&self) -> AgentId {
         match self {
             FiscalAction::ChangeTaxRate { government_id, .. } => *government_id,
-            FiscalAction::IssueDebt { government_id, .. } => *government_id,
+            FiscalAction::AnnounceAuction { government_id, .. } => *government_id,
+            FiscalAction::SubmitAuctionBid { bid, .. } => bid.bidder,
+            FiscalAction::HoldAuction { government_id, .. } => *government_id,
             FiscalAction::SetSpendingTarget { government_id, .. } => *government_id,
+            FiscalAction::Disburse { government_id, .. } => *government_id,
+            FiscalAction::CollectTax { government_id, .. } => *government_id,
+            FiscalAction::CloseFiscalPeriod { government_id } => *government_id,
         }
     }
-}
\ No newline at end of file
+}
@@ -14,10 +14,13 @@
 //! The crate is structured into several modules, each corresponding to a specific economic domain:
 //!
 //! - **`banking.rs`**: Defines `BankingAction` for deposits, withdrawals, and transfers.
+//! - **`clearing.rs`**: Defines `ClearingAction` for end-of-day interbank net settlement.
+//! - **`credit.rs`**: Defines `CreditAction` for reviewing delinquencies and declaring defaults.
 //! - **`consumption.rs`**: Defines `ConsumptionAction` for purchasing and consuming goods.
 //! - **`fiscal.rs`**: Defines `FiscalAction` for government-related activities like taxation.
+//! - **`monetary.rs`**: Defines `MonetaryAction` for the central bank's policy decisions.
 //! - **`production.rs`**: Defines `ProductionAction` for firm activities like producing goods and hiring.
-//! - **`settlement.rs`**: Defines `SettlementAction` for financial processes like paying interest.
+//! - **`settlement.rs`**: Defines `SettlementAction` for financial processes like paying interest and dividends.
 //! - **`trading.rs`**: Defines `TradingAction` for market activities like placing bids and asks.
 //! - **`validation.rs`**: Provides helper functions for validating action parameters (e.g., ensuring amounts are positive).
 //!
@@ -30,8 +33,11 @@
 //!   agent who initiated the action. This is crucial for logging, validation, and execution routing.
 
 pub mod banking;
+pub mod clearing;
 pub mod consumption;
+pub mod credit;
 pub mod fiscal;
+pub mod monetary;
 pub mod production;
 pub mod settlement;
 pub mod trading;
@@ -40,8 +46,11 @@ pub mod labour;
 
 pub use labour::*;
 pub use banking::*;
+pub use clearing::*;
 pub use consumption::*;
+pub use credit::*;
 pub use fiscal::*;
+pub use monetary::*;
 pub use production::*;
 pub use settlement::*;
 pub use trading::*;
@@ -59,6 +68,9 @@ pub enum SimAction {
     Settlement(SettlementAction),
     Trading(TradingAction),
     Labour(LabourAction),
+    Clearing(ClearingAction),
+    Credit(CreditAction),
+    Monetary(MonetaryAction),
 }
 
 impl SimAction {
@@ -72,6 +84,9 @@ impl SimAction {
             SimAction::Settlement(action) => format!("Settlement::{}", action.name()),
             SimAction::Trading(action) => format!("Trading::{}", action.name()),
             SimAction::Labour(action) => format!("Labour::{}", action.name()),
+            SimAction::Clearing(action) => format!("Clearing::{}", action.name()),
+            SimAction::Credit(action) => format!("Credit::{}", action.name()),
+            SimAction::Monetary(action) => format!("Monetary::{}", action.name()),
         }
     }
 
@@ -85,6 +100,9 @@ impl SimAction {
             SimAction::Settlement(action) => action.agent_id(),
             SimAction::Trading(action) => action.agent_id(),
             SimAction::Labour(action) => action.agent_id(),
+            SimAction::Clearing(action) => action.agent_id(),
+            SimAction::Credit(action) => action.agent_id(),
+            SimAction::Monetary(action) => action.agent_id(),
         }
     }
 }
\ No newline at end of file
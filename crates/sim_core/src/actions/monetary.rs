@@ -0,0 +1,31 @@
+use crate::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum MonetaryAction {
+    /// Moves the policy rate to the decision's `new_rate` and records the decision.
+    SetPolicyRate { central_bank_id: AgentId, decision: PolicyDecision },
+    /// Buys or sells Treasuries against reserves.
+    OpenMarketOperation { central_bank_id: AgentId, operation: OpenMarketOperation },
+    /// Checks each bank's average reserves against the requirement once a maintenance period has run,
+    /// and charges penalty interest on any shortfall. Issued by the engine after clearing.
+    AssessReserveCompliance,
+}
+
+impl MonetaryAction {
+    pub fn name(&self) -> &'static str {
+        match self {
+            MonetaryAction::SetPolicyRate { .. } => "SetPolicyRate",
+            MonetaryAction::OpenMarketOperation { .. } => "OpenMarketOperation",
+            MonetaryAction::AssessReserveCompliance => "AssessReserveCompliance",
+        }
+    }
+
+    pub fn agent_id(&self) -> AgentId {
+        match self {
+            MonetaryAction::SetPolicyRate { central_bank_id, .. } => *central_bank_id,
+            MonetaryAction::OpenMarketOperation { central_bank_id, .. } => *central_bank_id,
+            MonetaryAction::AssessReserveCompliance => AgentId::default(), // System action
+        }
+    }
+}
@@ -5,6 +5,8 @@ use serde::{Deserialize, Serialize};
 pub enum ProductionAction {
     Hire { agent_id: AgentId, count: u32 },
     Produce { agent_id: AgentId, recipe_id: RecipeId, batches: u32 },
+    /// Sell `quantity` new bonds of `tenor` to the firm's bank, at par.
+    IssueBonds { agent_id: AgentId, quantity: u64, tenor: Tenor },
 }
 
 impl ProductionAction {
@@ -12,6 +14,7 @@ impl ProductionAction {
         match self {
             ProductionAction::Hire { .. } => "Hire",
             ProductionAction::Produce { .. } => "Produce",
+            ProductionAction::IssueBonds { .. } => "IssueBonds",
         }
     }
 
@@ -19,6 +22,7 @@ impl ProductionAction {
         match self {
             ProductionAction::Hire { agent_id, .. } => *agent_id,
             ProductionAction::Produce { agent_id, .. } => *agent_id,
+            ProductionAction::IssueBonds { agent_id, .. } => *agent_id,
         }
     }
 }
\ No newline at end of file
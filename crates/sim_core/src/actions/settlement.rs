@@ -6,6 +6,10 @@ pub enum SettlementAction {
     AccrueInterest { instrument_id: InstrumentId },
     PayInterest { instrument_id: InstrumentId },
     ProcessCouponPayment { instrument_id: InstrumentId },
+    ResetRate { instrument_id: InstrumentId },
+    RepayInterbankLoan { instrument_id: InstrumentId },
+    RedeemBond { instrument_id: InstrumentId },
+    DistributeDividends { firm_id: AgentId },
 }
 
 impl SettlementAction {
@@ -14,6 +18,10 @@ impl SettlementAction {
             SettlementAction::AccrueInterest { .. } => "AccrueInterest",
             SettlementAction::PayInterest { .. } => "PayInterest",
             SettlementAction::ProcessCouponPayment { .. } => "ProcessCouponPayment",
+            SettlementAction::ResetRate { .. } => "ResetRate",
+            SettlementAction::RepayInterbankLoan { .. } => "RepayInterbankLoan",
+            SettlementAction::RedeemBond { .. } => "RedeemBond",
+            SettlementAction::DistributeDividends { .. } => "DistributeDividends",
         }
     }
 
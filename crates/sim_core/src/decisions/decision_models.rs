@@ -19,6 +19,20 @@ impl Debug for dyn DecisionModel {
     }
 }
 
+/// Runs several decision models for the same agent, e.g. its spending model alongside a portfolio model,
+/// and returns all of their actions in order.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CompositeDecisionModel {
+    pub models: Vec<Box<dyn DecisionModel>>,
+}
+
+#[typetag::serde]
+impl DecisionModel for CompositeDecisionModel {
+    fn decide(&self, agent: &dyn Any, state: &SimState, rng: &mut dyn RngCore) -> Vec<SimAction> {
+        self.models.iter().flat_map(|model| model.decide(agent, state, rng)).collect()
+    }
+}
+
 // TODO reintgrate ML decision model once basic economy loop working
 pub trait FeatureSource {
     fn get_age(&self) -> u32;
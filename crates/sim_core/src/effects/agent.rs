@@ -16,6 +16,8 @@ pub enum AgentEffect {
     RecordDividendIncome { recipient: AgentId, amount: f64 },
     UpdateRevenue { id: AgentId, revenue: f64 },
     Produce { firm: AgentId, good_id: GoodId, amount: f64 },
+    /// Adds `amount` to the firm's capital stock and `productivity_gain` to its productivity.
+    AddCapital { firm_id: AgentId, amount: f64, productivity_gain: f64 },
 }
 
 impl AgentEffect {
@@ -27,6 +29,7 @@ impl AgentEffect {
             AgentEffect::RecordDividendIncome { .. } => "RecordDividendIncome",
             AgentEffect::UpdateRevenue { .. } => "UpdateRevenue",
             AgentEffect::Produce { .. } => "Produce",
+            AgentEffect::AddCapital { .. } => "AddCapital",
         }
     }
 }
\ No newline at end of file
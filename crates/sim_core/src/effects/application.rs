@@ -46,6 +46,7 @@ impl StateEffectApplicator {
             StateEffect::Inventory(inventory_effect) => Self::apply_inventory_effect(state, inventory_effect),
             StateEffect::Market(market_effect) => Self::apply_market_effect(state, market_effect),
             StateEffect::Agent(agent_effect) => Self::apply_agent_effect(state, agent_effect),
+            StateEffect::Credit(credit_effect) => Self::apply_credit_effect(state, credit_effect),
         }
     }
 
@@ -125,6 +126,118 @@ impl StateEffectApplicator {
                     Err(EffectError::InstrumentNotFound { id: *instrument_id })
                 }
             }
+            FinancialEffect::ResetRate { instrument_id, rate, next_reset } => {
+                let instrument = state
+                    .financial_system
+                    .instruments
+                    .get_mut(instrument_id)
+                    .ok_or(EffectError::InstrumentNotFound { id: *instrument_id })?;
+                instrument.reset_rate(*rate, *next_reset);
+                let (creditor, debtor) = (instrument.creditor, instrument.debtor);
+                let balance_sheets = &mut state.financial_system.balance_sheets;
+                if let Some(asset) = balance_sheets.get_mut(&creditor).and_then(|bs| bs.assets.get_mut(instrument_id)) {
+                    asset.reset_rate(*rate, *next_reset);
+                }
+                if let Some(liability) =
+                    balance_sheets.get_mut(&debtor).and_then(|bs| bs.liabilities.get_mut(instrument_id))
+                {
+                    liability.reset_rate(*rate, *next_reset);
+                }
+                Ok(())
+            }
+            FinancialEffect::QueueInterbankObligation(obligation) => {
+                state.financial_system.payment_system.queue.push(obligation.clone());
+                Ok(())
+            }
+            FinancialEffect::ClearPaymentQueue => {
+                state.financial_system.payment_system.queue.clear();
+                Ok(())
+            }
+            FinancialEffect::RecordClearingReport(report) => {
+                state.history.clearing_reports.push(report.clone());
+                Ok(())
+            }
+            FinancialEffect::RecordPaymentFailure(failure) => {
+                state.history.payment_failures.push(failure.clone());
+                Ok(())
+            }
+            FinancialEffect::RecordIncome { agent_id, line, amount } => {
+                let bs = state
+                    .financial_system
+                    .get_bs_mut_by_id(agent_id)
+                    .ok_or(EffectError::AgentNotFound { id: *agent_id })?;
+                bs.income_statement.record(*line, *amount);
+                Ok(())
+            }
+            FinancialEffect::CloseIncomeStatement { agent_id } => {
+                let bs = state
+                    .financial_system
+                    .get_bs_mut_by_id(agent_id)
+                    .ok_or(EffectError::AgentNotFound { id: *agent_id })?;
+                bs.income_statement = IncomeStatement::default();
+                Ok(())
+            }
+            FinancialEffect::RecordEarnings(report) => {
+                state.history.earnings_reports.push(report.clone());
+                Ok(())
+            }
+            FinancialEffect::SetPolicyRate(decision) => {
+                state.financial_system.central_bank.policy_rate = decision.new_rate;
+                state.history.policy_decisions.push(decision.clone());
+                Ok(())
+            }
+            FinancialEffect::RecordReserveCompliance(report) => {
+                state.history.reserve_compliance.push(report.clone());
+                Ok(())
+            }
+            FinancialEffect::SetTaxRate { tax_type, rate } => {
+                state.financial_system.government.tax_rates.set_rate(tax_type, *rate);
+                Ok(())
+            }
+            FinancialEffect::SetSpendingTarget { category, level } => {
+                state.financial_system.government.spending_targets.set_level(category, *level);
+                Ok(())
+            }
+            FinancialEffect::RecordFiscalReport(report) => {
+                state.history.fiscal_reports.push(report.clone());
+                Ok(())
+            }
+            FinancialEffect::AnnounceTreasuryAuction(auction) => {
+                state.financial_system.treasury_auctions.push(auction.clone());
+                Ok(())
+            }
+            FinancialEffect::SubmitAuctionBid { auction_id, bid } => {
+                let auction = state.financial_system.treasury_auctions.iter_mut().find(|a| a.id == *auction_id);
+                match auction {
+                    Some(auction) => {
+                        auction.bids.push(bid.clone());
+                        Ok(())
+                    }
+                    None => Err(EffectError::InvalidState(format!("No open Treasury auction {}", auction_id))),
+                }
+            }
+            FinancialEffect::RecordAuctionResult(result) => {
+                state.financial_system.treasury_auctions.retain(|auction| auction.id != result.auction_id);
+                state.history.auction_results.push(result.clone());
+                Ok(())
+            }
+            FinancialEffect::RecordAcquisition { holder, market_id, units, cost } => {
+                let ledger = state.financial_system.cost_basis.entry(*holder).or_default();
+                ledger.entry(market_id.clone()).or_default().add(*units, *cost);
+                Ok(())
+            }
+            FinancialEffect::RecordDisposal { holder, market_id, units } => {
+                if let Some(basis) =
+                    state.financial_system.cost_basis.get_mut(holder).and_then(|ledger| ledger.get_mut(market_id))
+                {
+                    basis.remove(*units);
+                }
+                Ok(())
+            }
+            FinancialEffect::RecordLoanDecision(decision) => {
+                state.history.loan_decisions.push(decision.clone());
+                Ok(())
+            }
         }
     }
 
@@ -217,6 +330,32 @@ impl StateEffectApplicator {
                 println!("[EFFECT] Cleared order book for market: {:?}", market_id);
                 Ok(())
             }
+            MarketEffect::FillOrder { market_id, order, quantity } => {
+                let MarketId::Financial(id) = market_id else {
+                    let error = "Only financial market orders are filled directly.".to_string();
+                    return Err(EffectError::InvalidState(error));
+                };
+                let order_book = &mut state
+                    .financial_system
+                    .exchange
+                    .financial_market_mut(id)
+                    .ok_or_else(|| EffectError::MarketNotFound { market: format!("{:?}", market_id) })?
+                    .order_book;
+                let not_found = || EffectError::InvalidState(format!("Order {:?} is not on the book", order));
+                match order {
+                    Order::Bid(bid) => {
+                        let resting = order_book.bids.iter_mut().find(|resting| *resting == bid).ok_or_else(not_found)?;
+                        resting.quantity -= quantity;
+                        order_book.bids.retain(|resting| resting.quantity > 1e-6);
+                    }
+                    Order::Ask(ask) => {
+                        let resting = order_book.asks.iter_mut().find(|resting| *resting == ask).ok_or_else(not_found)?;
+                        resting.quantity -= quantity;
+                        order_book.asks.retain(|resting| resting.quantity > 1e-6);
+                    }
+                }
+                Ok(())
+            }
             MarketEffect::UpdateLabourMarket { market_id, update } => {
                 let market = state
                     .financial_system
@@ -245,15 +384,14 @@ impl StateEffectApplicator {
     fn apply_agent_effect(state: &mut SimState, effect: &AgentEffect) -> Result<(), EffectError> {
         match effect {
             AgentEffect::UpdateRevenue { id, revenue } => {
-                let tx = Transaction {
-                    id: uuid::Uuid::new_v4(),
-                    date: state.ticknum,
-                    qty: *revenue,
-                    from: *id,
-                    to: *id,
-                    tx_type: TransactionType::Transfer { from: *id, to: *id, amount: *revenue },
-                    instrument_id: None,
-                };
+                let tx = Transaction::new(
+                    state.ticknum,
+                    *id,
+                    *id,
+                    *revenue,
+                    TransactionType::Transfer { from: *id, to: *id, amount: *revenue },
+                    None,
+                );
                 state.history.transactions.push(tx);
                 Ok(())
             }
@@ -261,6 +399,12 @@ impl StateEffectApplicator {
                 println!("[EFFECT] Firm {} producing {} of {:?}", firm, amount, good_id);
                 Ok(())
             }
+            AgentEffect::AddCapital { firm_id, amount, productivity_gain } => {
+                let firm = state.agents.firms.get_mut(firm_id).ok_or(EffectError::AgentNotFound { id: *firm_id })?;
+                firm.capital_stock += *amount;
+                firm.productivity += *productivity_gain;
+                Ok(())
+            }
             AgentEffect::EstablishEmployment { firm_id, consumer_id, contract } => {
                 let firm = state.agents.firms.get_mut(firm_id);
                 let consumer = state.agents.consumers.get_mut(consumer_id);
@@ -283,8 +427,15 @@ impl StateEffectApplicator {
 
                 match (firm, consumer) {
                     (Some(firm), Some(consumer)) => {
-                        if firm.employees.contains_key(consumer_id) && consumer.employed_by == Some(*firm_id) {
-                            firm.employees.remove(consumer_id);
+                        if consumer.employed_by == Some(*firm_id)
+                            && let Some(contract) = firm.employees.remove(consumer_id)
+                        {
+                            consumer.employment_history.push(PastEmployment {
+                                employer: *firm_id,
+                                weekly_wage: contract.wage_rate * contract.hours,
+                                start_date: contract.start_date,
+                                end_date: state.current_date,
+                            });
                             consumer.employed_by = None;
                             consumer.income = 0.0;
                             consumer.hours_worked = 0.0;
@@ -311,12 +462,83 @@ impl StateEffectApplicator {
             AgentEffect::RecordDividendIncome { recipient, amount } => {
                 if let Some(consumer) = state.agents.get_consumer_mut(recipient) {
                     consumer.income += *amount;
-                    Ok(())
-                } else if let Some(_firm) = state.agents.get_firm_mut(recipient) {
-                    Ok(())
+                }
+                let line = IncomeLine::DividendIncome;
+                Self::apply_financial_effect(
+                    state,
+                    &FinancialEffect::RecordIncome { agent_id: *recipient, line, amount: *amount },
+                )
+            }
+        }
+    }
+
+    fn apply_credit_effect(state: &mut SimState, effect: &CreditEffect) -> Result<(), EffectError> {
+        let credit = &mut state.financial_system.credit;
+        match effect {
+            CreditEffect::RecordMissedPayment { instrument_id, amount, date } => {
+                match credit.status.get_mut(instrument_id) {
+                    Some(CreditStatus::Delinquent { missed_payments, amount_past_due, .. }) => {
+                        *missed_payments += 1;
+                        *amount_past_due += *amount;
+                    }
+                    Some(CreditStatus::Defaulted { .. }) => {}
+                    None => {
+                        credit.status.insert(
+                            *instrument_id,
+                            CreditStatus::Delinquent { since: *date, missed_payments: 1, amount_past_due: *amount },
+                        );
+                    }
+                }
+                Ok(())
+            }
+            CreditEffect::Cure { instrument_id } => {
+                if credit.is_delinquent(instrument_id) {
+                    credit.status.remove(instrument_id);
+                }
+                Ok(())
+            }
+            CreditEffect::MarkDefaulted { instrument_id, date, recovery_rate } => {
+                credit
+                    .status
+                    .insert(*instrument_id, CreditStatus::Defaulted { date: *date, recovery_rate: *recovery_rate });
+                Ok(())
+            }
+            CreditEffect::WriteDown { instrument_id, new_principal, loss } => {
+                let creditor = state
+                    .financial_system
+                    .instruments
+                    .get(instrument_id)
+                    .map(|inst| inst.creditor)
+                    .ok_or(EffectError::InstrumentNotFound { id: *instrument_id })?;
+                if *new_principal < 1e-6 {
+                    state.financial_system.remove_instrument(instrument_id).map_err(EffectError::FinancialSystemError)?;
                 } else {
-                    Err(EffectError::AgentNotFound { id: *recipient })
+                    state
+                        .financial_system
+                        .update_instrument(instrument_id, *new_principal)
+                        .map_err(EffectError::FinancialSystemError)?;
+                    Self::apply_financial_effect(
+                        state,
+                        &FinancialEffect::ResetAccruedInterest { instrument_id: *instrument_id },
+                    )?;
                 }
+                if let Some(bs) = state.financial_system.get_bs_mut_by_id(&creditor) {
+                    bs.income_statement.credit_losses += *loss;
+                    bs.income_statement.net_income -= *loss;
+                }
+                Ok(())
+            }
+            CreditEffect::SetRating { agent_id, rating } => {
+                credit.ratings.insert(*agent_id, rating.clone());
+                Ok(())
+            }
+            CreditEffect::RecordAssessment(assessment) => {
+                credit.rating_history.push(assessment.clone());
+                Ok(())
+            }
+            CreditEffect::RecordEvent(event) => {
+                credit.events.push(event.clone());
+                Ok(())
             }
         }
     }
@@ -409,6 +631,31 @@ mod eff_tests {
         assert!(state.financial_system.get_bs_by_id(&agent_c).unwrap().assets.contains_key(&instrument.id));
         assert!(state.financial_system.get_bs_by_id(&agent_b).unwrap().liabilities.contains_key(&instrument.id));
     }
+    #[test]
+    fn test_apply_split_and_transfer_shares() {
+        let (mut state, agent_a, agent_b, firm) = setup_test_state();
+        let date = state.current_date;
+        let holding = equity!(agent_a, firm, 100u64, 150u64, 2.0, date);
+        state.financial_system.create_instrument(holding.clone()).unwrap();
+        state.financial_system.create_instrument(equity!(agent_b, firm, 50u64, 150u64, 2.0, date)).unwrap();
+
+        let effect = StateEffect::Financial(FinancialEffect::SplitAndTransferInstrument {
+            id: holding.id,
+            buyer: agent_b,
+            quantity: 40,
+        });
+        StateEffectApplicator::apply_to_state(&mut state, &effect).unwrap();
+
+        let fs = &state.financial_system;
+        assert_eq!(fs.shares_held(&agent_a, &firm), 60);
+        assert_eq!(fs.shares_held(&agent_b, &firm), 90);
+        assert_eq!(fs.shares_outstanding(&firm), 150);
+        assert_eq!(fs.instruments[&holding.id].principal, 120.0);
+        let buyer_book: f64 = fs.get_bs_by_id(&agent_b).unwrap().assets.values().map(|inst| inst.principal).sum();
+        assert_eq!(buyer_book, 180.0);
+        assert_eq!(fs.get_bs_by_id(&firm).unwrap().liabilities.len(), 2);
+    }
+
     #[test]
     fn test_apply_add_and_remove_inventory() {
         let (mut state, agent_a, _, _) = setup_test_state();
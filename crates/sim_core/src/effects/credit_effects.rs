@@ -0,0 +1,28 @@
+use crate::*;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum CreditEffect {
+    RecordMissedPayment { instrument_id: InstrumentId, amount: f64, date: NaiveDate },
+    Cure { instrument_id: InstrumentId },
+    MarkDefaulted { instrument_id: InstrumentId, date: NaiveDate, recovery_rate: f64 },
+    WriteDown { instrument_id: InstrumentId, new_principal: f64, loss: f64 },
+    SetRating { agent_id: AgentId, rating: CreditRating },
+    RecordAssessment(RatingAssessment),
+    RecordEvent(CreditEvent),
+}
+
+impl CreditEffect {
+    pub fn name(&self) -> &'static str {
+        match self {
+            CreditEffect::RecordMissedPayment { .. } => "RecordMissedPayment",
+            CreditEffect::Cure { .. } => "Cure",
+            CreditEffect::MarkDefaulted { .. } => "MarkDefaulted",
+            CreditEffect::WriteDown { .. } => "WriteDown",
+            CreditEffect::SetRating { .. } => "SetRating",
+            CreditEffect::RecordAssessment(_) => "RecordAssessment",
+            CreditEffect::RecordEvent(_) => "RecordEvent",
+        }
+    }
+}
@@ -17,6 +17,29 @@ pub enum FinancialEffect {
         accrual_date: NaiveDate,
     },
     ResetAccruedInterest { instrument_id: InstrumentId },
+    ResetRate { instrument_id: InstrumentId, rate: f64, next_reset: NaiveDate },
+    QueueInterbankObligation(InterbankObligation),
+    ClearPaymentQueue,
+    RecordClearingReport(ClearingReport),
+    RecordPaymentFailure(PaymentFailure),
+    RecordIncome { agent_id: AgentId, line: IncomeLine, amount: f64 },
+    CloseIncomeStatement { agent_id: AgentId },
+    RecordEarnings(EarningsReport),
+    /// Moves the central bank's policy rate and records the decision behind it.
+    SetPolicyRate(PolicyDecision),
+    RecordReserveCompliance(ReserveComplianceReport),
+    SetTaxRate { tax_type: TaxType, rate: f64 },
+    SetSpendingTarget { category: SpendingCategory, level: f64 },
+    RecordFiscalReport(FiscalReport),
+    AnnounceTreasuryAuction(TreasuryAuction),
+    SubmitAuctionBid { auction_id: AuctionId, bid: AuctionBid },
+    /// Closes the auction and records its result.
+    RecordAuctionResult(AuctionResult),
+    /// Adds units bought for `cost` to `holder`'s cost basis in `market_id`.
+    RecordAcquisition { holder: AgentId, market_id: MarketId, units: u64, cost: f64 },
+    /// Takes units sold or redeemed out of `holder`'s cost basis at their average cost.
+    RecordDisposal { holder: AgentId, market_id: MarketId, units: u64 },
+    RecordLoanDecision(LoanDecision),
 }
 
 impl FinancialEffect {
@@ -31,6 +54,25 @@ impl FinancialEffect {
             FinancialEffect::SplitAndTransferInstrument { .. } => "SplitAndTransferInstrument",
             FinancialEffect::AccrueInterest { .. } => "AccrueInterest",
             FinancialEffect::ResetAccruedInterest { .. } => "ResetAccruedInterest",
+            FinancialEffect::ResetRate { .. } => "ResetRate",
+            FinancialEffect::QueueInterbankObligation(_) => "QueueInterbankObligation",
+            FinancialEffect::ClearPaymentQueue => "ClearPaymentQueue",
+            FinancialEffect::RecordClearingReport(_) => "RecordClearingReport",
+            FinancialEffect::RecordPaymentFailure(_) => "RecordPaymentFailure",
+            FinancialEffect::RecordIncome { .. } => "RecordIncome",
+            FinancialEffect::CloseIncomeStatement { .. } => "CloseIncomeStatement",
+            FinancialEffect::RecordEarnings(_) => "RecordEarnings",
+            FinancialEffect::SetPolicyRate(_) => "SetPolicyRate",
+            FinancialEffect::RecordReserveCompliance(_) => "RecordReserveCompliance",
+            FinancialEffect::SetTaxRate { .. } => "SetTaxRate",
+            FinancialEffect::SetSpendingTarget { .. } => "SetSpendingTarget",
+            FinancialEffect::RecordFiscalReport(_) => "RecordFiscalReport",
+            FinancialEffect::AnnounceTreasuryAuction(_) => "AnnounceTreasuryAuction",
+            FinancialEffect::SubmitAuctionBid { .. } => "SubmitAuctionBid",
+            FinancialEffect::RecordAuctionResult(_) => "RecordAuctionResult",
+            FinancialEffect::RecordAcquisition { .. } => "RecordAcquisition",
+            FinancialEffect::RecordDisposal { .. } => "RecordDisposal",
+            FinancialEffect::RecordLoanDecision(_) => "RecordLoanDecision",
         }
     }
 }
\ No newline at end of file
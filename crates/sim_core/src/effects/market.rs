@@ -8,6 +8,8 @@ pub enum MarketEffect {
     ExecuteTrade(Trade),
     UpdatePrice { market_id: MarketId, new_price: f64 },
     ClearMarket { market_id: MarketId },
+    /// Takes `quantity` off a resting order, which leaves the book once it is filled.
+    FillOrder { market_id: MarketId, order: Order, quantity: f64 },
 
     UpdateLabourMarket {
         market_id: LabourMarketId,
@@ -32,6 +34,7 @@ impl MarketEffect {
             MarketEffect::ExecuteTrade(_) => "ExecuteTrade",
             MarketEffect::UpdatePrice { .. } => "UpdatePrice",
             MarketEffect::ClearMarket { .. } => "ClearMarket",
+            MarketEffect::FillOrder { .. } => "FillOrder",
             MarketEffect::UpdateLabourMarket { .. } => "UpdateLabourMarket",
             MarketEffect::ClearLabourMarketOrders { .. } => "ClearLabourMarketOrders",
         }
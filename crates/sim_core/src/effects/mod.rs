@@ -17,6 +17,7 @@
 //! The crate is structured into domain-specific effect modules and a central application module:
 //!
 //! - **`agent.rs`**: Effects that directly modify agent properties (e.g., hiring, changing income).
+//! - **`credit_effects.rs`**: Effects on the credit registry (e.g., delinquencies, defaults, write-downs, ratings).
 //! - **`financial.rs`**: Effects on financial instruments (e.g., creating/updating instruments, accruing interest).
 //! - **`inventory.rs`**: Effects that change an agent's inventory of goods.
 //! - **`market.rs`**: Effects on market order books (e.g., placing orders, executing trades).
@@ -36,12 +37,14 @@
 //!   complex matching logic separate from the state struct itself.
 pub mod agent;
 pub mod application;
+pub mod credit_effects;
 pub mod financial;
 pub mod inventory;
 pub mod market;
 
 pub use agent::*;
 pub use application::*;
+pub use credit_effects::*;
 pub use financial::*;
 pub use inventory::*;
 pub use market::*;
@@ -54,6 +57,7 @@ pub enum StateEffect {
     Inventory(InventoryEffect),
     Market(MarketEffect),
     Agent(AgentEffect),
+    Credit(CreditEffect),
 }
 
 impl StateEffect {
@@ -64,6 +68,7 @@ impl StateEffect {
             StateEffect::Inventory(effect) => format!("Inventory::{}", effect.name()),
             StateEffect::Market(effect) => format!("Market::{}", effect.name()),
             StateEffect::Agent(effect) => format!("Agent::{}", effect.name()),
+            StateEffect::Credit(effect) => format!("Credit::{}", effect.name()),
         }
     }
 }
\ No newline at end of file
@@ -6,7 +6,9 @@ use std::collections::HashMap;
 pub struct Bank {
     pub id: AgentId,
     pub name: String,
+    /// Margin over the reference rate on the bank's loans, in basis points.
     pub lending_spread: f64,
+    /// Margin on the bank's deposit rates, in basis points.
     pub deposit_spread: f64,
 }
 
@@ -44,6 +46,9 @@ pub struct Consumer {
     pub expectations: ConsumerExpectations, // Added field
     pub employed_by: Option<AgentId>,
     pub hours_worked: f64,
+    /// Jobs held before, oldest first.
+    #[serde(default)]
+    pub employment_history: Vec<PastEmployment>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -54,6 +59,23 @@ pub struct EmploymentContract {
     pub start_date: chrono::NaiveDate,
 }
 
+/// A job that has ended, kept for the unemployment insurance eligibility test.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct PastEmployment {
+    pub employer: AgentId,
+    pub weekly_wage: f64,
+    pub start_date: chrono::NaiveDate,
+    pub end_date: chrono::NaiveDate,
+}
+
+impl PastEmployment {
+    /// Whole weeks of the job that fell between `from` and `to`.
+    pub fn weeks_between(&self, from: chrono::NaiveDate, to: chrono::NaiveDate) -> i64 {
+        let days = (self.end_date.min(to) - self.start_date.max(from)).num_days();
+        days.max(0) / 7
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Firm {
     pub id: AgentId,
@@ -74,9 +96,23 @@ pub struct Government {
     pub spending_targets: SpendingTargets,
     pub debt_ceiling: Option<f64>,
     pub fiscal_policy: FiscalPolicy,
+    #[serde(default)]
+    pub unemployment_insurance: UnemploymentInsurance,
 }
 
 impl Bank {
+    /// Lowest rating a bank will still lend to or underwrite for.
+    pub const MIN_LENDING_RATING: CreditRating = CreditRating::B;
+
+    pub fn lends_to(&self, rating: &CreditRating) -> bool {
+        rating.notch() <= Self::MIN_LENDING_RATING.notch()
+    }
+
+    /// What the bank pays on demand deposits: the policy rate plus its `deposit_spread`, never below zero.
+    pub fn deposit_rate(&self, policy_rate: f64) -> f64 {
+        (policy_rate + self.deposit_spread / 10_000.0).max(0.0)
+    }
+
     pub fn new(name: String, lending_spread: f64, deposit_spread: f64) -> Self {
         Self { 
             id: AgentId(uuid::Uuid::new_v4()), 
@@ -101,6 +137,7 @@ impl Consumer {
             expectations: ConsumerExpectations::default(),
             employed_by: None,
             hours_worked: 0.0,
+            employment_history: Vec::new(),
         }
     }
 
@@ -130,9 +167,22 @@ impl Firm {
     pub fn get_employees(&self) -> Vec<AgentId> {
         self.employees.keys().cloned().collect()
     }
-    pub fn calculate_profits(&self, revenues: f64, costs: f64) -> FirmProfits {
+    /// Each employee's gross pay for a week, ordered by employee so that payroll runs are
+    /// reproducible. Employees with no pay due are left out.
+    pub fn weekly_wages(&self) -> Vec<(AgentId, f64)> {
+        let mut wages: Vec<(AgentId, f64)> = self
+            .employees
+            .iter()
+            .map(|(employee_id, contract)| (*employee_id, contract.wage_rate * contract.hours))
+            .filter(|(_, wage)| *wage > 0.0)
+            .collect();
+        wages.sort_by_key(|(employee_id, _)| employee_id.0);
+        wages
+    }
+    /// Profit for a period taxed at `tax_rate`; a loss pays no tax.
+    pub fn calculate_profits(&self, revenues: f64, costs: f64, tax_rate: f64) -> FirmProfits {
         let gross_profit = revenues - costs;
-        let tax_liability = gross_profit * 0.21;
+        let tax_liability = gross_profit.max(0.0) * tax_rate;
         let net_profit = gross_profit - tax_liability;
         
         FirmProfits {
@@ -152,11 +202,68 @@ pub struct FirmProfits {
     pub retained_earnings_ratio: f64,
 }
 
+impl FirmProfits {
+    /// The part of net profit paid out to shareholders; losses pay nothing.
+    pub fn dividend(&self) -> f64 {
+        (self.net * (1.0 - self.retained_earnings_ratio)).max(0.0)
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct CentralBank {
     pub id: AgentId,
     pub policy_rate: f64,
     pub reserve_requirement: f64,
+    /// Days over which reserves are averaged against the requirement; 1 checks every day.
+    pub maintenance_period_days: u32,
+    #[serde(default)]
+    pub facilities: StandingFacilities,
+}
+
+impl CentralBank {
+    /// Spread over the lending facility rate charged on a reserve requirement shortfall.
+    pub const RESERVE_PENALTY_SPREAD: f64 = 0.01;
+
+    pub fn lending_facility_rate(&self) -> f64 {
+        self.policy_rate + self.facilities.lending_spread
+    }
+
+    /// Never below zero: the facility does not charge for deposits.
+    pub fn deposit_facility_rate(&self) -> f64 {
+        (self.policy_rate - self.facilities.deposit_spread).max(0.0)
+    }
+
+    /// Never below zero.
+    pub fn interest_on_excess_reserves_rate(&self) -> f64 {
+        (self.policy_rate - self.facilities.excess_reserves_spread).max(0.0)
+    }
+
+    /// Charged on the average shortfall over a maintenance period, so that meeting the requirement
+    /// with facility borrowing is always cheaper than missing it.
+    pub fn reserve_penalty_rate(&self) -> f64 {
+        self.lending_facility_rate() + Self::RESERVE_PENALTY_SPREAD
+    }
+
+    /// An overnight loan to `bank` from the lending facility, secured on `collateral_value` of Treasuries.
+    pub fn lending_facility_loan(
+        &self, bank: AgentId, amount: f64, collateral_value: f64, date: chrono::NaiveDate,
+        calendar: &dyn HolidayCalendar,
+    ) -> FinancialInstrument {
+        let collateral = CollateralInfo { collateral_type: CollateralType::Treasury, value: collateral_value };
+        let maturity = date + chrono::Duration::days(1);
+        let rate = self.lending_facility_rate();
+        loan!(self.id, bank, amount, LoanType::StandingFacility, rate, maturity, date, calendar)
+            .with_collateral(collateral)
+    }
+
+    /// An overnight placement of `bank`'s reserves at the deposit facility.
+    pub fn deposit_facility_placement(
+        &self, bank: AgentId, amount: f64, date: chrono::NaiveDate, calendar: &dyn HolidayCalendar,
+    ) -> FinancialInstrument {
+        let maturity = date + chrono::Duration::days(1);
+        let rate = self.deposit_facility_rate();
+        loan!(bank, self.id, amount, LoanType::DepositFacility, rate, maturity, date, calendar)
+    }
 }
 
 #[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -174,6 +281,7 @@ impl Government {
             spending_targets,
             debt_ceiling: None,
             fiscal_policy,
+            unemployment_insurance: UnemploymentInsurance::default(),
         }
     }
     pub fn get_id(&self) -> &AgentId {
@@ -188,6 +296,7 @@ impl Default for Government {
             spending_targets: SpendingTargets::default(),
             debt_ceiling: None,
             fiscal_policy: FiscalPolicy::default(),
+            unemployment_insurance: UnemploymentInsurance::default(),
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,159 @@
+use crate::*;
+use chrono::{Datelike, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+
+/// How winning bids are priced. At a single-price auction every winner pays the price at the
+/// stop-out yield; at a multiple-price auction each competitive bid pays the price at its own yield,
+/// and non-competitive bids the price at the average accepted yield.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum AuctionFormat {
+    #[default]
+    SinglePrice,
+    MultiplePrice,
+}
+
+/// A tender for `quantity` bonds. A competitive bid names the lowest yield the bidder will accept; a
+/// non-competitive one takes whatever yield the auction sets and is filled first.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct AuctionBid {
+    pub bidder: AgentId,
+    pub quantity: u64,
+    pub yield_bid: Option<f64>,
+}
+
+impl AuctionBid {
+    pub fn is_competitive(&self) -> bool {
+        self.yield_bid.is_some()
+    }
+}
+
+/// An announced auction of `offering` new bonds of `tenor`, collecting bids until `auction_date`.
+/// An `inflation_linked` auction issues bonds whose principal is indexed to the CPI.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct TreasuryAuction {
+    pub id: AuctionId,
+    pub tenor: Tenor,
+    pub inflation_linked: bool,
+    pub offering: u64,
+    pub announced: NaiveDate,
+    pub auction_date: NaiveDate,
+    pub format: AuctionFormat,
+    pub bids: Vec<AuctionBid>,
+}
+
+impl TreasuryAuction {
+    /// Bids are taken from the announcement up to the day before the auction.
+    pub fn is_open(&self, date: NaiveDate) -> bool {
+        self.announced <= date && date < self.auction_date
+    }
+
+    pub fn tendered(&self) -> u64 {
+        self.bids.iter().map(|bid| bid.quantity).sum()
+    }
+
+    pub fn has_bid_from(&self, bidder: &AgentId) -> bool {
+        self.bids.iter().any(|bid| bid.bidder == *bidder)
+    }
+
+    /// The bonds sold, issued on `date`. Notes and bonds carry the stop-out yield as their coupon,
+    /// rounded down to an eighth of a point; bills pay none. Linked bonds take `cpi` as their base.
+    pub fn security(&self, stop_out_yield: f64, date: NaiveDate, cpi: f64) -> BondDetails {
+        let coupon_rate = if self.tenor.is_bill() { 0.0 } else { ((stop_out_yield * 800.0).floor() / 800.0).max(0.0) };
+        let inflation_linked = self.inflation_linked.then_some(InflationLinkage { base_cpi: cpi });
+        BondDetails { coupon_rate, inflation_linked, ..BondDetails::benchmark(self.tenor, date) }
+    }
+}
+
+/// Bonds allotted to one bidder and what it paid for them.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct AuctionAward {
+    pub bidder: AgentId,
+    pub quantity: u64,
+    pub amount: f64,
+    pub instrument_id: InstrumentId,
+}
+
+/// The outcome of a Treasury auction. `accepted` counts the bonds actually issued, which falls
+/// short of the allotment when a winner could not pay. `allotted_at_stop` is the fraction of bids at
+/// the stop-out yield that was filled.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct AuctionResult {
+    pub auction_id: AuctionId,
+    pub date: NaiveDate,
+    pub tenor: Tenor,
+    pub format: AuctionFormat,
+    pub offering: u64,
+    pub tendered: u64,
+    pub non_competitive: u64,
+    pub accepted: u64,
+    pub stop_out_yield: Option<f64>,
+    pub allotted_at_stop: f64,
+    pub coupon_rate: f64,
+    pub awards: Vec<AuctionAward>,
+}
+
+impl AuctionResult {
+    /// Bonds bid for per bond offered.
+    pub fn bid_to_cover(&self) -> f64 {
+        if self.offering == 0 { 0.0 } else { self.tendered as f64 / self.offering as f64 }
+    }
+
+    pub fn proceeds(&self) -> f64 {
+        self.awards.iter().map(|award| award.amount).sum()
+    }
+}
+
+/// A regular auction of `tenor`, held on `weekday` in every `interval_weeks`-th ISO week.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AuctionSlot {
+    pub tenor: Tenor,
+    pub weekday: Weekday,
+    pub interval_weeks: u32,
+    #[serde(default)]
+    pub inflation_linked: bool,
+}
+
+/// The government's auction calendar. Each auction is announced `lead_days` before it is held, and
+/// one falling on a holiday is skipped.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct AuctionCalendar {
+    pub slots: Vec<AuctionSlot>,
+    pub lead_days: u32,
+    pub format: AuctionFormat,
+}
+
+impl Default for AuctionCalendar {
+    fn default() -> Self {
+        Self {
+            slots: vec![
+                AuctionSlot { tenor: Tenor::T4W, weekday: Weekday::Tue, interval_weeks: 1, inflation_linked: false },
+                AuctionSlot { tenor: Tenor::T13W, weekday: Weekday::Thu, interval_weeks: 1, inflation_linked: false },
+                AuctionSlot { tenor: Tenor::T2Y, weekday: Weekday::Wed, interval_weeks: 4, inflation_linked: false },
+                AuctionSlot { tenor: Tenor::T10Y, weekday: Weekday::Wed, interval_weeks: 4, inflation_linked: false },
+                AuctionSlot { tenor: Tenor::T10Y, weekday: Weekday::Thu, interval_weeks: 8, inflation_linked: true },
+            ],
+            lead_days: 3,
+            format: AuctionFormat::SinglePrice,
+        }
+    }
+}
+
+impl AuctionCalendar {
+    /// The auctions to announce on `date`: the slots falling `lead_days` later, with that date.
+    pub fn due_for_announcement(
+        &self, date: NaiveDate, calendar: &dyn HolidayCalendar,
+    ) -> Vec<(&AuctionSlot, NaiveDate)> {
+        let auction_date = date + chrono::Duration::days(self.lead_days.max(1) as i64);
+        if !calendar.is_business_day(auction_date) {
+            return vec![];
+        }
+        let week = auction_date.iso_week().week();
+        self.slots
+            .iter()
+            .filter(|slot| slot.weekday == auction_date.weekday() && week.is_multiple_of(slot.interval_weeks.max(1)))
+            .map(|slot| (slot, auction_date))
+            .collect()
+    }
+}
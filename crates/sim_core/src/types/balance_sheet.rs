@@ -24,9 +24,45 @@ pub struct IncomeStatement {
     pub operating_expenses: f64, // e.g., Wages
     pub interest_income: f64,
     pub interest_expense: f64,
+    pub dividend_income: f64,
+    pub credit_losses: f64,
     pub net_income: f64,
 }
 
+/// A line of the income statement that flows through `net_income`.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum IncomeLine {
+    Revenue,
+    CostOfGoodsSold,
+    OperatingExpense,
+    InterestIncome,
+    InterestExpense,
+    DividendIncome,
+}
+
+impl IncomeStatement {
+    pub fn record(&mut self, line: IncomeLine, amount: f64) {
+        let (item, sign) = match line {
+            IncomeLine::Revenue => (&mut self.revenue, 1.0),
+            IncomeLine::CostOfGoodsSold => (&mut self.cost_of_goods_sold, -1.0),
+            IncomeLine::OperatingExpense => (&mut self.operating_expenses, -1.0),
+            IncomeLine::InterestIncome => (&mut self.interest_income, 1.0),
+            IncomeLine::InterestExpense => (&mut self.interest_expense, -1.0),
+            IncomeLine::DividendIncome => (&mut self.dividend_income, 1.0),
+        };
+        *item += amount;
+        self.net_income += sign * amount;
+    }
+
+    pub fn total_revenues(&self) -> f64 {
+        self.revenue + self.interest_income + self.dividend_income
+    }
+
+    pub fn total_costs(&self) -> f64 {
+        self.cost_of_goods_sold + self.operating_expenses + self.interest_expense + self.credit_losses
+    }
+}
+
 impl BalanceSheet {
     pub fn new(owner: AgentId) -> Self {
         Self { agent_id: owner, assets: HashMap::new(), liabilities: HashMap::new(), real_assets: HashMap::new(), income_statement: IncomeStatement::default() }
@@ -71,8 +107,18 @@ impl BalanceSheet {
         self.liabilities.values().map(|inst| inst.principal).sum()
     }
 
+    /// Liabilities owed to creditors, leaving out the shares the agent has issued.
+    pub fn total_debt(&self) -> f64 {
+        self.liabilities
+            .values()
+            .filter(|inst| !inst.details.as_any().is::<EquityDetails>())
+            .map(|inst| inst.principal)
+            .sum()
+    }
+
+    /// What the agent's owners have a claim on. Issued shares are that claim, not a debt against it.
     pub fn net_worth(&self) -> f64 {
-        self.total_assets() - self.total_liabilities()
+        self.total_assets() - self.total_debt()
     }
 }
 
@@ -0,0 +1,214 @@
+use crate::*;
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+
+/// Which days are holidays. Weekends are never business days, whatever the calendar says.
+pub trait HolidayCalendar {
+    fn is_holiday(&self, date: NaiveDate) -> bool;
+
+    fn is_business_day(&self, date: NaiveDate) -> bool {
+        !is_weekend(date) && !self.is_holiday(date)
+    }
+}
+
+/// How a holiday's date is found in a given year.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum HolidayRule {
+    /// The same date every year. An `observed` holiday falling on a Saturday is kept on the Friday
+    /// before, and one falling on a Sunday on the Monday after.
+    Fixed {
+        month: u32,
+        day: u32,
+        #[serde(default)]
+        observed: bool,
+    },
+    /// The `nth` `weekday` of `month`, counting from the end of the month when `nth` is negative.
+    NthWeekday { month: u32, weekday: Weekday, nth: i32 },
+    /// `offset` days from Easter Sunday: Good Friday is -2 and Easter Monday 1.
+    Easter { offset: i64 },
+}
+
+impl HolidayRule {
+    /// The day the holiday is kept in `year`, after any observance shift.
+    pub fn date_in(&self, year: i32) -> Option<NaiveDate> {
+        match self {
+            HolidayRule::Fixed { month, day, observed } => {
+                let date = NaiveDate::from_ymd_opt(year, *month, *day)?;
+                Some(match (observed, date.weekday()) {
+                    (true, Weekday::Sat) => date - Duration::days(1),
+                    (true, Weekday::Sun) => date + Duration::days(1),
+                    _ => date,
+                })
+            }
+            HolidayRule::NthWeekday { month, weekday, nth } => match *nth {
+                n if n > 0 => NaiveDate::from_weekday_of_month_opt(year, *month, *weekday, n as u8),
+                n if n < 0 => {
+                    let last = NaiveDate::from_ymd_opt(year, *month, days_in_month(year, *month))?;
+                    let back = (7 + last.weekday().num_days_from_monday() - weekday.num_days_from_monday()) % 7;
+                    let date = last - Duration::days(back as i64 + 7 * (-n as i64 - 1));
+                    (date.month() == *month).then_some(date)
+                }
+                _ => None,
+            },
+            HolidayRule::Easter { offset } => Some(easter_sunday(year)? + Duration::days(*offset)),
+        }
+    }
+}
+
+/// Easter Sunday in the Gregorian calendar, by the anonymous Gregorian algorithm.
+pub fn easter_sunday(year: i32) -> Option<NaiveDate> {
+    let a = year % 19;
+    let (b, c) = (year / 100, year % 100);
+    let (d, e) = (b / 4, b % 4);
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let (i, k) = (c / 4, c % 4);
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+    NaiveDate::from_ymd_opt(year, month as u32, day as u32)
+}
+
+/// A named calendar whose holidays are given by rules, so it can be written in TOML:
+///
+/// ```toml
+/// name = "Example"
+/// rules = [
+///     { type = "fixed", month = 1, day = 1, observed = true },
+///     { type = "nthWeekday", month = 5, weekday = "Mon", nth = -1 },
+///     { type = "easter", offset = -2 },
+/// ]
+/// ```
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct RuleCalendar {
+    pub name: String,
+    #[serde(default)]
+    pub rules: Vec<HolidayRule>,
+}
+
+impl Default for RuleCalendar {
+    fn default() -> Self {
+        Self::weekends_only()
+    }
+}
+
+impl HolidayCalendar for RuleCalendar {
+    fn is_holiday(&self, date: NaiveDate) -> bool {
+        // An observed New Year's Day can fall on 31 December of the year before
+        let years = [date.year(), date.year() + 1];
+        self.rules.iter().any(|rule| years.iter().any(|year| rule.date_in(*year) == Some(date)))
+    }
+}
+
+impl RuleCalendar {
+    pub fn from_toml(toml_str: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(toml_str)
+    }
+
+    /// The built-in calendar called `name`: `WeekendsOnly`, `US` or `TARGET`.
+    pub fn builtin(name: &str) -> Option<Self> {
+        match name {
+            "WeekendsOnly" => Some(Self::weekends_only()),
+            "US" => Some(Self::us_settlement()),
+            "TARGET" => Some(Self::target()),
+            _ => None,
+        }
+    }
+
+    pub fn weekends_only() -> Self {
+        Self { name: "WeekendsOnly".to_string(), rules: Vec::new() }
+    }
+
+    /// US federal holidays, which close the Treasury market and the payment system.
+    pub fn us_settlement() -> Self {
+        let fixed = |month, day| HolidayRule::Fixed { month, day, observed: true };
+        let nth = |month, weekday, nth| HolidayRule::NthWeekday { month, weekday, nth };
+        Self {
+            name: "US".to_string(),
+            rules: vec![
+                fixed(1, 1),
+                nth(1, Weekday::Mon, 3),
+                nth(2, Weekday::Mon, 3),
+                nth(5, Weekday::Mon, -1),
+                fixed(6, 19),
+                fixed(7, 4),
+                nth(9, Weekday::Mon, 1),
+                nth(10, Weekday::Mon, 2),
+                fixed(11, 11),
+                nth(11, Weekday::Thu, 4),
+                fixed(12, 25),
+            ],
+        }
+    }
+
+    /// The euro area's TARGET2 closing days.
+    pub fn target() -> Self {
+        let fixed = |month, day| HolidayRule::Fixed { month, day, observed: false };
+        Self {
+            name: "TARGET".to_string(),
+            rules: vec![
+                fixed(1, 1),
+                HolidayRule::Easter { offset: -2 },
+                HolidayRule::Easter { offset: 1 },
+                fixed(5, 1),
+                fixed(12, 25),
+                fixed(12, 26),
+            ],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_us_holidays() {
+        let us = RuleCalendar::us_settlement();
+        // Thanksgiving, Memorial Day and an observed Independence Day (4 July 2026 is a Saturday)
+        for holiday in [date(2026, 11, 26), date(2026, 5, 25), date(2026, 7, 3), date(2026, 1, 19)] {
+            assert!(us.is_holiday(holiday), "{}", holiday);
+            assert!(!us.is_business_day(holiday));
+        }
+        // New Year's Day 2028 is a Saturday and is observed on 31 December 2027
+        assert!(us.is_holiday(date(2027, 12, 31)));
+        assert!(us.is_business_day(date(2026, 11, 27)));
+        assert!(!us.is_business_day(date(2026, 11, 28)), "Saturdays are never business days");
+    }
+
+    #[test]
+    fn test_easter_relative_holidays() {
+        assert_eq!(easter_sunday(2026), Some(date(2026, 4, 5)));
+        assert_eq!(easter_sunday(2027), Some(date(2027, 3, 28)));
+        let target = RuleCalendar::target();
+        assert!(target.is_holiday(date(2026, 4, 3)) && target.is_holiday(date(2026, 4, 6)));
+        assert!(!target.is_holiday(date(2026, 4, 7)));
+    }
+
+    #[test]
+    fn test_calendar_from_toml() {
+        let calendar = RuleCalendar::from_toml(
+            r#"
+            name = "Custom"
+            rules = [
+                { type = "fixed", month = 3, day = 17 },
+                { type = "nthWeekday", month = 8, weekday = "Mon", nth = -1 },
+                { type = "easter", offset = 1 },
+            ]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(calendar.name, "Custom");
+        assert!(calendar.is_holiday(date(2026, 3, 17)));
+        assert!(calendar.is_holiday(date(2026, 8, 31)));
+        assert!(calendar.is_holiday(date(2026, 4, 6)));
+        assert!(!calendar.is_holiday(date(2026, 8, 24)));
+    }
+}
@@ -0,0 +1,138 @@
+use crate::*;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use serde_with::{DisplayFromStr, serde_as};
+use std::collections::HashMap;
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub enum CreditStatus {
+    Delinquent { since: NaiveDate, missed_payments: u32, amount_past_due: f64 },
+    Defaulted { date: NaiveDate, recovery_rate: f64 },
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum CreditEventKind {
+    MissedPayment { amount: f64 },
+    Cured,
+    Default { exposure: f64 },
+    Recovery { creditor: AgentId, exposure: f64, recovered: f64, written_off: f64 },
+    RatingChange { from: Option<CreditRating>, to: CreditRating },
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CreditEvent {
+    pub id: uuid::Uuid,
+    pub tick: u32,
+    pub date: NaiveDate,
+    pub debtor: AgentId,
+    pub instrument_id: Option<InstrumentId>,
+    pub kind: CreditEventKind,
+}
+
+impl CreditEvent {
+    pub fn new(
+        tick: u32, date: NaiveDate, debtor: AgentId, instrument_id: Option<InstrumentId>, kind: CreditEventKind,
+    ) -> Self {
+        Self { id: uuid::Uuid::new_v4(), tick, date, debtor, instrument_id, kind }
+    }
+
+    pub fn is_default(&self) -> bool {
+        matches!(self.kind, CreditEventKind::Default { .. })
+    }
+}
+
+/// One rating agency review of a borrower and the ratios it was rated on. `interest_coverage` is
+/// `None` for a borrower with no interest expense in the period, `liquidity` for one with no debt.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RatingAssessment {
+    pub tick: u32,
+    pub date: NaiveDate,
+    pub agent_id: AgentId,
+    pub rating: CreditRating,
+    pub leverage: f64,
+    pub interest_coverage: Option<f64>,
+    pub liquidity: Option<f64>,
+}
+
+/// Delinquency and default state for the whole system. Instruments without an entry in `status` are
+/// performing; agents without an entry in `ratings` are unrated.
+#[serde_as]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CreditRegistry {
+    pub grace_period_days: i64,
+    #[serde_as(as = "HashMap<DisplayFromStr, _>")]
+    pub status: HashMap<InstrumentId, CreditStatus>,
+    #[serde_as(as = "HashMap<DisplayFromStr, _>")]
+    pub ratings: HashMap<AgentId, CreditRating>,
+    pub rating_history: Vec<RatingAssessment>,
+    pub events: Vec<CreditEvent>,
+}
+
+impl Default for CreditRegistry {
+    fn default() -> Self {
+        Self {
+            grace_period_days: 30,
+            status: HashMap::new(),
+            ratings: HashMap::new(),
+            rating_history: Vec::new(),
+            events: Vec::new(),
+        }
+    }
+}
+
+impl CreditRegistry {
+    pub fn is_defaulted(&self, instrument_id: &InstrumentId) -> bool {
+        matches!(self.status.get(instrument_id), Some(CreditStatus::Defaulted { .. }))
+    }
+
+    pub fn is_delinquent(&self, instrument_id: &InstrumentId) -> bool {
+        matches!(self.status.get(instrument_id), Some(CreditStatus::Delinquent { .. }))
+    }
+
+    /// Rating assumed for a borrower no agency has rated yet.
+    pub const UNRATED: CreditRating = CreditRating::BBB;
+
+    pub fn rating(&self, agent_id: &AgentId) -> Option<&CreditRating> {
+        self.ratings.get(agent_id)
+    }
+
+    /// The agent's rating, or `UNRATED` if it has none.
+    pub fn rating_or_unrated(&self, agent_id: &AgentId) -> CreditRating {
+        self.rating(agent_id).cloned().unwrap_or(Self::UNRATED)
+    }
+
+    /// Every assessment of `agent_id`, oldest first.
+    pub fn rating_history_for<'a>(
+        &'a self, agent_id: &'a AgentId,
+    ) -> impl DoubleEndedIterator<Item = &'a RatingAssessment> {
+        self.rating_history.iter().filter(move |assessment| assessment.agent_id == *agent_id)
+    }
+
+    pub fn latest_assessment<'a>(&'a self, agent_id: &'a AgentId) -> Option<&'a RatingAssessment> {
+        self.rating_history_for(agent_id).next_back()
+    }
+
+    /// Delinquent instruments whose grace period has run out by `date`.
+    pub fn past_grace_period(&self, date: NaiveDate) -> Vec<InstrumentId> {
+        self.status
+            .iter()
+            .filter_map(|(id, status)| match status {
+                CreditStatus::Delinquent { since, .. } if (date - *since).num_days() >= self.grace_period_days => {
+                    Some(*id)
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub fn events_for<'a>(&'a self, agent_id: &'a AgentId) -> impl DoubleEndedIterator<Item = &'a CreditEvent> {
+        self.events.iter().filter(move |event| {
+            event.debtor == *agent_id
+                || matches!(event.kind, CreditEventKind::Recovery { creditor, .. } if creditor == *agent_id)
+        })
+    }
+
+    pub fn defaults(&self) -> impl DoubleEndedIterator<Item = &CreditEvent> {
+        self.events.iter().filter(|event| event.is_default())
+    }
+}
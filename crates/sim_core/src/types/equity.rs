@@ -0,0 +1,34 @@
+use crate::*;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// Periods per year for firms' quarterly accounts.
+pub const REPORTING_PERIODS_PER_YEAR: f64 = 4.0;
+
+/// A firm's results for one closed accounting period and the dividend paid out of them.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EarningsReport {
+    pub tick: u32,
+    pub date: NaiveDate,
+    pub firm: AgentId,
+    pub revenues: f64,
+    pub costs: f64,
+    pub interest_expense: f64,
+    pub net_profit: f64,
+    pub dividends: f64,
+    pub shares_outstanding: u64,
+}
+
+impl EarningsReport {
+    /// Net profit per share, annualised.
+    pub fn earnings_per_share(&self) -> Option<f64> {
+        (self.shares_outstanding > 0)
+            .then(|| self.net_profit * REPORTING_PERIODS_PER_YEAR / self.shares_outstanding as f64)
+    }
+
+    /// Dividend per share, annualised.
+    pub fn dividend_per_share(&self) -> Option<f64> {
+        (self.shares_outstanding > 0)
+            .then(|| self.dividends * REPORTING_PERIODS_PER_YEAR / self.shares_outstanding as f64)
+    }
+}
@@ -20,4 +20,7 @@ pserde!(GoodId, Uuid);
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, Copy, Default)]
 pub struct RecipeId(pub Uuid);
-pserde!(RecipeId, Uuid);
\ No newline at end of file
+pserde!(RecipeId, Uuid);
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, Copy, Default)]
+pub struct AuctionId(pub Uuid);
+pserde!(AuctionId, Uuid);
@@ -27,6 +27,11 @@ pub struct FinancialInstrument {
     pub details: Box<dyn InstrumentDetails>,
     pub accrued_interest: f64,
     pub last_accrual_date: NaiveDate,
+    #[serde(default)]
+    pub day_count: DayCount,
+    /// Dates interest or coupons fall due, fixed at origination. Empty for open-ended instruments.
+    #[serde(default)]
+    pub payment_schedule: Vec<NaiveDate>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -90,6 +95,11 @@ pub struct BondDetails {
     pub frequency: usize,
     pub tenor: Tenor,
     pub quantity: u64,
+    /// Floating-rate terms; `coupon_rate` is then the rate fixed at the last reset.
+    #[serde(default)]
+    pub floating: Option<FloatingRate>,
+    #[serde(default)]
+    pub inflation_linked: Option<InflationLinkage>,
 }
 #[typetag::serde]
 impl InstrumentDetails for BondDetails {
@@ -107,6 +117,9 @@ pub struct LoanDetails {
     pub interest_rate: f64,
     pub maturity_date: NaiveDate,
     pub collateral: Option<CollateralInfo>,
+    /// Floating-rate terms; `interest_rate` is then the rate fixed at the last reset.
+    #[serde(default)]
+    pub floating: Option<FloatingRate>,
 }
 #[typetag::serde]
 impl InstrumentDetails for LoanDetails {
@@ -118,12 +131,96 @@ impl InstrumentDetails for LoanDetails {
     }
 }
 
+/// The reference rate a floating-rate instrument fixes against.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum RateIndex {
+    PolicyRate,
+    /// The rate the SOFR market last traded or quoted at.
+    Sofr,
+}
+
+/// A rate refixed at `index` plus `spread` every `reset_period`. `next_reset` is the date of the next
+/// fixing; the rate in force until then is the instrument's own rate field.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct FloatingRate {
+    pub index: RateIndex,
+    pub spread: f64,
+    pub reset_period: TimePeriod,
+    pub next_reset: NaiveDate,
+}
+
+impl FloatingRate {
+    pub fn new(index: RateIndex, spread: f64, reset_period: TimePeriod, originated: NaiveDate) -> Self {
+        let next_reset = reset_period.add_to_date(originated);
+        Self { index, spread, reset_period, next_reset }
+    }
+
+    pub fn rate(&self, fixing: f64) -> f64 {
+        fixing + self.spread
+    }
+
+    pub fn is_due(&self, date: NaiveDate) -> bool {
+        self.next_reset <= date
+    }
+}
+
+/// Principal indexed to the CPI: coupons and redemption are paid on the principal scaled by the CPI
+/// over `base_cpi`, the index when the bond was issued. Redemption is floored at par.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct InflationLinkage {
+    pub base_cpi: f64,
+}
+
+impl InflationLinkage {
+    /// How far the CPI has moved since issue. 1 while either index is not yet known.
+    pub fn index_ratio(&self, cpi: f64) -> f64 {
+        if self.base_cpi > 0.0 && cpi > 0.0 { cpi / self.base_cpi } else { 1.0 }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub enum BondType {
     Corporate { spread: f64 },
     Government,
 }
 
+impl BondType {
+    /// Treasuries accrue actual/actual, corporate bonds on the 30/360 bond basis.
+    pub fn day_count(&self) -> DayCount {
+        match self {
+            BondType::Corporate { .. } => DayCount::Thirty360,
+            BondType::Government => DayCount::ActAct,
+        }
+    }
+}
+
+impl BondDetails {
+    pub fn is_zero_coupon(&self) -> bool {
+        self.coupon_rate == 0.0 && self.floating.is_none()
+    }
+
+    /// The CPI index ratio the bond's principal is scaled by; 1 for a nominal bond.
+    pub fn index_ratio(&self, cpi: f64) -> f64 {
+        self.inflation_linked.as_ref().map_or(1.0, |linkage| linkage.index_ratio(cpi))
+    }
+
+    /// Coupon dates of a bond issued on `issued`, rolled back from maturity every `12 / frequency`
+    /// months and moved to the following business day of `calendar`. A zero-coupon bond's only
+    /// payment is its redemption.
+    pub fn coupon_schedule(&self, issued: NaiveDate, calendar: &dyn HolidayCalendar) -> Vec<NaiveDate> {
+        if self.is_zero_coupon() {
+            return vec![BusinessDayConvention::Following.adjust(self.maturity_date, calendar)];
+        }
+        payment_schedule(
+            issued,
+            self.maturity_date,
+            &TimePeriod::from_frequency(self.frequency),
+            &BusinessDayConvention::Following,
+            calendar,
+        )
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub enum LoanType {
     Mortgage,
@@ -131,14 +228,111 @@ pub enum LoanType {
     Auto,
     Student,
     CreditCard,
+    /// Lent to a firm by a bank.
+    Commercial,
+    Interbank,
+    /// Borrowed overnight from the central bank's lending facility.
+    StandingFacility,
+    /// Reserves placed overnight at the central bank's deposit facility; the bank is the lender.
+    DepositFacility,
+}
+
+impl LoanDetails {
+    /// Principal plus simple interest over the life of the loan, on the money-market ACT/360 basis.
+    pub fn repayment_amount(&self, principal: f64, originated: NaiveDate) -> f64 {
+        let days = (self.maturity_date - originated).num_days().max(1) as f64;
+        principal * (1.0 + self.interest_rate * days / 360.0)
+    }
+
+    pub fn is_overnight_funding(&self) -> bool {
+        matches!(self.loan_type, LoanType::Interbank | LoanType::StandingFacility | LoanType::DepositFacility)
+    }
+
+    /// Interest dates of a loan made on `originated`, rolled back from maturity a month at a time and
+    /// moved to the following business day of `calendar`. An overnight loan pays once, at maturity.
+    pub fn interest_schedule(&self, originated: NaiveDate, calendar: &dyn HolidayCalendar) -> Vec<NaiveDate> {
+        let convention = BusinessDayConvention::Following;
+        payment_schedule(originated, self.maturity_date, &TimePeriod::Monthly, &convention, calendar)
+    }
+}
+
+impl FinancialInstrument {
+    /// Floating-rate terms of a bond or loan.
+    pub fn floating_rate(&self) -> Option<&FloatingRate> {
+        if let Some(bond) = self.details.as_any().downcast_ref::<BondDetails>() {
+            bond.floating.as_ref()
+        } else {
+            self.details.as_any().downcast_ref::<LoanDetails>().and_then(|loan| loan.floating.as_ref())
+        }
+    }
+
+    /// Turns a bond or loan into a floater refixed at `index` plus `spread` every `reset_period`. Its
+    /// current rate stands until the first reset. A floating loan pays its interest at each reset.
+    pub fn with_floating_rate(mut self, index: RateIndex, spread: f64, reset_period: TimePeriod) -> Self {
+        let floating = FloatingRate::new(index, spread, reset_period.clone(), self.originated_date);
+        if let Some(bond) = self.details.as_any_mut().downcast_mut::<BondDetails>() {
+            bond.floating = Some(floating);
+        } else if let Some(loan) = self.details.as_any_mut().downcast_mut::<LoanDetails>() {
+            let schedule = payment_schedule(
+                self.originated_date,
+                loan.maturity_date,
+                &reset_period,
+                &BusinessDayConvention::None,
+                &RuleCalendar::weekends_only(),
+            );
+            loan.floating = Some(floating);
+            self.payment_schedule = schedule;
+        }
+        self
+    }
+
+    /// Secures a loan on `collateral`.
+    pub fn with_collateral(mut self, collateral: CollateralInfo) -> Self {
+        if let Some(loan) = self.details.as_any_mut().downcast_mut::<LoanDetails>() {
+            loan.collateral = Some(collateral);
+        }
+        self
+    }
+
+    /// Links a bond's principal to the CPI, taking `base_cpi` as the index at issue.
+    pub fn with_inflation_linkage(mut self, base_cpi: f64) -> Self {
+        if let Some(bond) = self.details.as_any_mut().downcast_mut::<BondDetails>() {
+            bond.inflation_linked = Some(InflationLinkage { base_cpi });
+        }
+        self
+    }
+
+    /// Refixes a floating rate to `rate`, with the following reset on `next_reset`.
+    pub fn reset_rate(&mut self, rate: f64, next_reset: NaiveDate) {
+        if let Some(bond) = self.details.as_any_mut().downcast_mut::<BondDetails>()
+            && let Some(floating) = bond.floating.as_mut()
+        {
+            bond.coupon_rate = rate;
+            floating.next_reset = next_reset;
+        } else if let Some(loan) = self.details.as_any_mut().downcast_mut::<LoanDetails>()
+            && let Some(floating) = loan.floating.as_mut()
+        {
+            loan.interest_rate = rate;
+            floating.next_reset = next_reset;
+        }
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct CollateralInfo {
-    pub collateral_type: String,
+    pub collateral_type: CollateralType,
     pub value: f64,
 }
 
+/// What a secured loan is secured on.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum CollateralType {
+    /// Pledged Treasuries, which the lender takes over in a default.
+    Treasury,
+    Property,
+    Vehicle,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum CreditRating {
     AAA,
@@ -153,6 +347,42 @@ pub enum CreditRating {
     D,
 }
 
+impl CreditRating {
+    /// Every rating a borrower can issue debt under, best first.
+    pub const PERFORMING: [CreditRating; 9] = [
+        CreditRating::AAA,
+        CreditRating::AA,
+        CreditRating::A,
+        CreditRating::BBB,
+        CreditRating::BB,
+        CreditRating::B,
+        CreditRating::CCC,
+        CreditRating::CC,
+        CreditRating::C,
+    ];
+
+    /// Position on the rating scale, from 0 for AAA down to 9 for D.
+    pub fn notch(&self) -> usize {
+        Self::PERFORMING.iter().position(|rating| rating == self).unwrap_or(Self::PERFORMING.len())
+    }
+
+    /// Annual credit spread over the risk-free rate for debt issued at this rating.
+    pub fn spread(&self) -> f64 {
+        match self {
+            CreditRating::AAA => 0.005,
+            CreditRating::AA => 0.0075,
+            CreditRating::A => 0.01,
+            CreditRating::BBB => 0.015,
+            CreditRating::BB => 0.03,
+            CreditRating::B => 0.05,
+            CreditRating::CCC => 0.08,
+            CreditRating::CC => 0.10,
+            CreditRating::C => 0.12,
+            CreditRating::D => 0.20,
+        }
+    }
+}
+
 impl fmt::Display for CreditRating {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{:?}", self)
@@ -193,6 +423,8 @@ impl Default for FinancialInstrument {
             details: Box::new(CashDetails),
             accrued_interest: 0.0,
             last_accrual_date: chrono::NaiveDate::from_ymd_opt(2026, 1, 1).unwrap(),
+            day_count: DayCount::default(),
+            payment_schedule: Vec::new(),
         }
     }
 }
@@ -243,10 +475,47 @@ impl Consolidatable for FinancialInstrument {
                 subtype: Some(format!("{:?}_{}", details.tenor, (details.coupon_rate * 10000.0) as i32,)),
             });
         }
+        if self.details.as_any().is::<EquityDetails>() {
+            return Some(ConsolidationKey {
+                creditor: self.creditor,
+                debtor: self.debtor,
+                instrument_type: "Equity".to_string(),
+                subtype: None,
+            });
+        }
         None
     }
 }
 
+impl FinancialInstrument {
+    /// Units held, for instruments that trade by the unit (bonds and shares).
+    pub fn quantity(&self) -> Option<u64> {
+        if let Some(bond) = self.details.as_any().downcast_ref::<BondDetails>() {
+            Some(bond.quantity)
+        } else {
+            self.details.as_any().downcast_ref::<EquityDetails>().map(|equity| equity.quantity)
+        }
+    }
+
+    pub fn set_quantity(&mut self, quantity: u64) {
+        if let Some(bond) = self.details.as_any_mut().downcast_mut::<BondDetails>() {
+            bond.quantity = quantity;
+        } else if let Some(equity) = self.details.as_any_mut().downcast_mut::<EquityDetails>() {
+            equity.quantity = quantity;
+        }
+    }
+
+    /// Whether a scheduled payment falls after `after` and on or before `through`, so payments
+    /// scheduled on days the payment system is shut roll to the next day it opens.
+    pub fn has_payment_between(&self, after: NaiveDate, through: NaiveDate) -> bool {
+        self.payment_schedule.iter().any(|date| *date > after && *date <= through)
+    }
+
+    pub fn is_equity_of(&self, issuer: &AgentId) -> bool {
+        self.debtor == *issuer && self.details.as_any().is::<EquityDetails>()
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct RealAsset {
     pub id: AssetId,
@@ -287,6 +556,19 @@ pub struct Transaction {
     pub instrument_id: Option<InstrumentId>,
 }
 
+impl Transaction {
+    pub fn new(
+        date: u32, from: AgentId, to: AgentId, amount: f64, tx_type: TransactionType,
+        instrument_id: Option<InstrumentId>,
+    ) -> Self {
+        Self { id: uuid::Uuid::new_v4(), date, qty: amount, from, to, tx_type, instrument_id }
+    }
+
+    pub fn involves(&self, agent_id: &AgentId) -> bool {
+        self.from == *agent_id || self.to == *agent_id
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum TransactionType {
     Deposit { holder: AgentId, bank: AgentId, amount: f64 },
@@ -294,7 +576,16 @@ pub enum TransactionType {
     Transfer { from: AgentId, to: AgentId, amount: f64 },
     InterestPayment { payer: AgentId, receiver: AgentId, amount: f64 },
     DividendPayment { payer: AgentId, receiver: AgentId, amount: f64 },
+    LoanRepayment { borrower: AgentId, lender: AgentId, principal: f64, interest: f64 },
     TaxPayment { payer: AgentId, tax_type: TaxType, period: NaiveDate },
+    BondRedemption { issuer: AgentId, holder: AgentId, amount: f64 },
+    GovernmentPayment { recipient: AgentId, purpose: SpendingPurpose },
+    /// Reserves lent overnight to `bank` by the central bank's lending facility.
+    FacilityLoan { bank: AgentId, amount: f64 },
+    /// Reserves placed overnight by `bank` at the central bank's deposit facility.
+    FacilityDeposit { bank: AgentId, amount: f64 },
+    /// What a defaulted debtor pays a creditor out of its funds in settlement of its claim.
+    DefaultRecovery { debtor: AgentId, creditor: AgentId, amount: f64 },
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -303,6 +594,8 @@ pub struct EquityDetails {
     pub par_value: f64,
     pub voting_rights: bool,
     pub dividend_yield: Option<f64>,
+    /// Shares in this holding; `shares_outstanding` is the issuer's total.
+    pub quantity: u64,
 }
 
 #[typetag::serde]
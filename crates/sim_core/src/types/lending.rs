@@ -0,0 +1,98 @@
+use crate::*;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A borrower's request for a term loan, put to every bank.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct LoanRequest {
+    pub borrower: AgentId,
+    pub amount: f64,
+    pub loan_type: LoanType,
+    pub tenor: Tenor,
+}
+
+/// A borrower's credit score, the weighted average of its components. Each is between 0 and 1,
+/// higher being better.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+pub struct CreditScore {
+    pub score: f64,
+    /// From debt, the new loan included, over annual income.
+    pub income: f64,
+    /// From debt over total assets before the loan.
+    pub leverage: f64,
+    /// From missed payments and defaults in the credit registry.
+    pub payment_history: f64,
+}
+
+/// Why a bank turned down a loan request.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub enum RejectionReason {
+    /// The borrower is rated below `Bank::MIN_LENDING_RATING`, or is in default.
+    RatedBelowMinimum {
+        rating: CreditRating,
+    },
+    ScoreBelowCutoff {
+        score: f64,
+        cutoff: f64,
+    },
+    /// The loan would take the bank's capital over total assets below the minimum.
+    InsufficientCapital {
+        capital_ratio: f64,
+        minimum: f64,
+    },
+    /// The bank's reserves above the requirement would not cover what the loan draws from them.
+    InsufficientLiquidity {
+        excess_reserves: f64,
+        needed: f64,
+    },
+}
+
+impl fmt::Display for RejectionReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RejectionReason::RatedBelowMinimum { rating } => write!(f, "borrower rated {}", rating),
+            RejectionReason::ScoreBelowCutoff { score, cutoff } => {
+                write!(f, "credit score {:.2} below the cutoff of {:.2}", score, cutoff)
+            }
+            RejectionReason::InsufficientCapital { capital_ratio, minimum } => {
+                write!(f, "capital ratio would fall to {:.3}, under the minimum of {:.3}", capital_ratio, minimum)
+            }
+            RejectionReason::InsufficientLiquidity { excess_reserves, needed } => {
+                write!(f, "excess reserves of ${:.2} do not cover the ${:.2} needed", excess_reserves, needed)
+            }
+        }
+    }
+}
+
+/// A bank's answer to a loan request: the rate it offers, or its reasons for refusing.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub enum LoanOutcome {
+    Offered { rate: f64 },
+    Rejected { reasons: Vec<RejectionReason> },
+}
+
+/// One bank's decision on a loan request. Every bank asked records its decision; `accepted` marks
+/// the offer the borrower took.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct LoanDecision {
+    pub tick: u32,
+    pub date: NaiveDate,
+    pub borrower: AgentId,
+    pub bank: AgentId,
+    pub amount: f64,
+    pub loan_type: LoanType,
+    pub score: CreditScore,
+    pub outcome: LoanOutcome,
+    pub accepted: bool,
+}
+
+impl LoanDecision {
+    /// The rate offered, if the bank made an offer.
+    pub fn offered_rate(&self) -> Option<f64> {
+        match self.outcome {
+            LoanOutcome::Offered { rate } => Some(rate),
+            LoanOutcome::Rejected { .. } => None,
+        }
+    }
+}
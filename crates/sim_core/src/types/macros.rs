@@ -28,6 +28,8 @@ macro_rules! cash {
             originated_date: $originated,
             accrued_interest: 0.0,
             last_accrual_date: $originated,
+            day_count: $crate::DayCount::default(),
+            payment_schedule: Vec::new(),
         }
     };
 }
@@ -44,6 +46,8 @@ macro_rules! deposit {
             originated_date: $originated,
             accrued_interest: 0.0,
             last_accrual_date: $originated,
+            day_count: $crate::DayCount::Act365Fixed,
+            payment_schedule: Vec::new(),
         }
     };
 }
@@ -60,32 +64,111 @@ macro_rules! reserves {
             originated_date: $originated,
             accrued_interest: 0.0,
             last_accrual_date: $originated,
+            day_count: $crate::DayCount::default(),
+            payment_schedule: Vec::new(),
+        }
+    };
+}
+
+/// A loan paying interest monthly on its `interest_schedule`, against the business days of `$calendar`
+/// or weekends only if none is given.
+#[macro_export]
+macro_rules! loan {
+    (
+        $lender:expr, $borrower:expr, $principal:expr, $loan_type:expr, $rate:expr, $maturity_date:expr,
+        $originated:expr
+    ) => {
+        $crate::loan!(
+            $lender,
+            $borrower,
+            $principal,
+            $loan_type,
+            $rate,
+            $maturity_date,
+            $originated,
+            &$crate::RuleCalendar::weekends_only()
+        )
+    };
+    (
+        $lender:expr, $borrower:expr, $principal:expr, $loan_type:expr, $rate:expr, $maturity_date:expr,
+        $originated:expr, $calendar:expr
+    ) => {{
+        let details = $crate::LoanDetails {
+            loan_type: $loan_type,
+            interest_rate: $rate,
+            maturity_date: $maturity_date,
+            collateral: None,
+            floating: None,
+        };
+        $crate::FinancialInstrument {
+            id: $crate::InstrumentId(uuid::Uuid::new_v4()),
+            creditor: $lender,
+            debtor: $borrower,
+            principal: $principal,
+            payment_schedule: details.interest_schedule($originated, $calendar),
+            details: Box::new(details),
+            originated_date: $originated,
+            accrued_interest: 0.0,
+            last_accrual_date: $originated,
+            day_count: $crate::DayCount::Act360,
+        }
+    }};
+}
+
+#[macro_export]
+macro_rules! equity {
+    ($holder:expr, $issuer:expr, $shares:expr, $shares_outstanding:expr, $price:expr, $originated:expr) => {
+        $crate::FinancialInstrument {
+            id: $crate::InstrumentId(uuid::Uuid::new_v4()),
+            creditor: $holder,
+            debtor: $issuer,
+            principal: $shares as f64 * $price,
+            details: Box::new($crate::EquityDetails {
+                shares_outstanding: $shares_outstanding,
+                par_value: $price,
+                voting_rights: true,
+                dividend_yield: None,
+                quantity: $shares,
+            }),
+            originated_date: $originated,
+            accrued_interest: 0.0,
+            last_accrual_date: $originated,
+            day_count: $crate::DayCount::default(),
+            payment_schedule: Vec::new(),
         }
     };
 }
 
 #[macro_export]
 macro_rules! bond {
-    ($investor:expr, $issuer:expr, $principal:expr, $coupon_rate:expr, $maturity_date:expr, $face_value:expr, $bond_type:expr, $frequency:expr, $tenor:expr, $originated:expr) => {
+    (
+        $investor:expr, $issuer:expr, $principal:expr, $coupon_rate:expr, $maturity_date:expr, $face_value:expr,
+        $bond_type:expr, $frequency:expr, $tenor:expr, $originated:expr
+    ) => {{
+        let details = $crate::BondDetails {
+            bond_type: $bond_type,
+            coupon_rate: $coupon_rate,
+            face_value: $face_value,
+            maturity_date: $maturity_date,
+            frequency: $frequency,
+            tenor: $tenor, // Pass tenor
+            quantity: 1,
+            floating: None,
+            inflation_linked: None,
+        };
         $crate::FinancialInstrument {
             id: $crate::InstrumentId(uuid::Uuid::new_v4()),
             creditor: $investor,
             debtor: $issuer,
             principal: $principal,
-            details: Box::new($crate::BondDetails {
-                bond_type: $bond_type,
-                coupon_rate: $coupon_rate,
-                face_value: $face_value,
-                maturity_date: $maturity_date,
-                frequency: $frequency,
-                tenor: $tenor, // Pass tenor
-                quantity: 1,
-            }),
+            day_count: details.bond_type.day_count(),
+            payment_schedule: details.coupon_schedule($originated, &$crate::RuleCalendar::weekends_only()),
+            details: Box::new(details),
             originated_date: $originated,
             accrued_interest: 0.0,
             last_accrual_date: $originated,
         }
-    };
+    }};
 }
 
 #[macro_export]
@@ -62,7 +62,25 @@ impl Tradable for FinancialMarketId {
                     Ok(())
                 }
             }
-            FinancialMarketId::CorporateBond { .. } => Ok(()), // Placeholder
+            FinancialMarketId::CorporateBond { rating } => {
+                let held_quantity = fs.corporate_bonds_held(agent_id, rating) as f64;
+                if held_quantity < quantity {
+                    Err(format!(
+                        "Insufficient corporate bond holdings ({}): need {:.0}, has {:.0}",
+                        rating, quantity, held_quantity
+                    ))
+                } else {
+                    Ok(())
+                }
+            }
+            FinancialMarketId::Equity { firm } => {
+                let held_shares = fs.shares_held(agent_id, firm) as f64;
+                if held_shares < quantity {
+                    Err(format!("Insufficient shares of {}: need {:.0}, has {:.0}", firm, quantity, held_shares))
+                } else {
+                    Ok(())
+                }
+            }
         }
     }
 }
@@ -105,6 +123,10 @@ pub struct MarketView {
     pub vwap_5: Option<f64>,
     pub ma_20: Option<f64>,
     pub realized_vol_20: Option<f64>,
+    /// Equity markets only: share price times shares outstanding.
+    pub market_cap: Option<f64>,
+    /// Equity markets only: share price over annualised earnings per share, when earnings are positive.
+    pub pe_ratio: Option<f64>,
 }
 
 impl MarketView {
@@ -124,31 +146,60 @@ impl Tradable for MarketId {
     }
 }
 
+/// Time to maturity of a Treasury issue. Bills run up to a year and pay no coupon; notes and bonds
+/// pay semi-annual coupons. `Days` covers any tenor without a name of its own.
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, Copy)]
 pub enum Tenor {
+    T4W,
+    T13W,
+    T26W,
+    T52W,
     T2Y,
     T5Y,
     T10Y,
     T30Y,
+    Days(u32),
 }
 impl Tenor {
+    const NAMED: [Tenor; 8] =
+        [Tenor::T4W, Tenor::T13W, Tenor::T26W, Tenor::T52W, Tenor::T2Y, Tenor::T5Y, Tenor::T10Y, Tenor::T30Y];
+
+    /// The named tenor `days` long, or `Days(days)` if there is none.
+    pub fn from_days(days: u32) -> Self {
+        Self::NAMED.into_iter().find(|tenor| tenor.to_days() == days).unwrap_or(Tenor::Days(days))
+    }
+
     pub fn to_days(&self) -> u32 {
         match self {
+            Tenor::T4W => 28,
+            Tenor::T13W => 91,
+            Tenor::T26W => 182,
+            Tenor::T52W => 364,
             Tenor::T2Y => 730,
             Tenor::T5Y => 1825,
             Tenor::T10Y => 3650,
             Tenor::T30Y => 10950,
+            Tenor::Days(days) => *days,
         }
     }
+
+    /// Treasuries of a year or less are bills, issued at a discount and redeemed at par.
+    pub fn is_bill(&self) -> bool {
+        self.to_days() < 365
+    }
     pub fn add_to_date(&self, date: chrono::NaiveDate) -> chrono::NaiveDate {
         date + chrono::Duration::days(self.to_days() as i64)
     }
+    pub fn years(&self) -> f64 {
+        self.to_days() as f64 / 365.0
+    }
 }
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum FinancialMarketId {
     SecuredOvernightFinancing,
     Treasury { tenor: Tenor },
     CorporateBond { rating: CreditRating },
+    Equity { firm: AgentId },
 }
 
 impl fmt::Display for FinancialMarketId {
@@ -157,6 +208,7 @@ impl fmt::Display for FinancialMarketId {
             FinancialMarketId::SecuredOvernightFinancing => write!(f, "SOFR"),
             FinancialMarketId::Treasury { tenor } => write!(f, "Treasury_{}", tenor),
             FinancialMarketId::CorporateBond { rating } => write!(f, "CorpBond_{}", rating),
+            FinancialMarketId::Equity { firm } => write!(f, "Equity_{}", firm),
         }
     }
 }
@@ -169,6 +221,8 @@ pub enum ParseFinancialMarketIdError {
     ParseTenor(#[from] ParseTenorError),
     #[error("Failed to parse credit rating: {0}")]
     ParseRating(#[from] ParseCreditRatingError),
+    #[error("Failed to parse issuer id: {0}")]
+    ParseIssuer(#[from] uuid::Error),
 }
 
 impl FromStr for FinancialMarketId {
@@ -186,10 +240,16 @@ impl FromStr for FinancialMarketId {
             let rating = rating_str.parse()?;
             return Ok(FinancialMarketId::CorporateBond { rating });
         }
+        if let Some(firm_str) = s.strip_prefix("Equity_") {
+            let firm = firm_str.parse()?;
+            return Ok(FinancialMarketId::Equity { firm });
+        }
         Err(ParseFinancialMarketIdError::InvalidFormat(s.to_string()))
     }
 }
 
+/// Money-market conventions, where a price is the one-day discount factor `1 / (1 + daily rate)`.
+/// Only the SOFR market quotes this way; bond markets quote clean prices, priced with `BondPricer`.
 pub trait RatesMarket {
     fn price_to_daily_rate(&self, price: f64) -> f64;
     fn daily_rate_to_annual_bps(&self, daily_rate: f64) -> f64;
@@ -325,7 +385,10 @@ impl OrderBook {
 
 impl fmt::Display for Tenor {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{:?}", self)
+        match self {
+            Tenor::Days(days) => write!(f, "T{}D", days),
+            named => write!(f, "{:?}", named),
+        }
     }
 }
 
@@ -336,13 +399,21 @@ pub struct ParseTenorError(String);
 impl FromStr for Tenor {
     type Err = ParseTenorError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "T2Y" => Ok(Tenor::T2Y),
-            "T5Y" => Ok(Tenor::T5Y),
-            "T10Y" => Ok(Tenor::T10Y),
-            "T30Y" => Ok(Tenor::T30Y),
-            _ => Err(ParseTenorError(s.to_string())),
+        // Any `T<n>D`, `T<n>W` or `T<n>Y`; a length with a name of its own parses to the named tenor
+        let error = || ParseTenorError(s.to_string());
+        let body = s.strip_prefix('T').ok_or_else(error)?;
+        let (count, unit) = body.split_at(body.len().saturating_sub(1));
+        let count: u32 = count.parse().map_err(|_| error())?;
+        let days = match unit {
+            "D" => count,
+            "W" => count * 7,
+            "Y" => count * 365,
+            _ => return Err(error()),
+        };
+        if days == 0 {
+            return Err(error());
         }
+        Ok(Tenor::from_days(days))
     }
 }
 
@@ -380,6 +451,7 @@ impl Exchange {
             FinancialMarketId::SecuredOvernightFinancing => "Secured Overnight Financing".to_string(),
             FinancialMarketId::Treasury { tenor } => format!("Treasury {}", tenor),
             FinancialMarketId::CorporateBond { rating } => format!("Corporate Bond {:?}", rating),
+            FinancialMarketId::Equity { firm } => format!("Equity {}", firm),
         };
         self.financial_markets.entry(market_id.clone()).or_insert_with(|| FinancialMarket::new(market_id, name));
     }
@@ -400,7 +472,9 @@ impl Exchange {
         self.financial_markets.get_mut(market_id)
     }
 
-    pub fn clear_markets(&mut self) -> (Vec<Trade>, HashMap<MarketId, MarketSnapshot>) {
+    /// Matches every open book. Financial markets only open on business days; their orders rest until
+    /// the next one.
+    pub fn clear_markets(&mut self, financial_markets_open: bool) -> (Vec<Trade>, HashMap<MarketId, MarketSnapshot>) {
         let mut all_trades = Vec::new();
         let mut snapshots = HashMap::new();
 
@@ -409,10 +483,12 @@ impl Exchange {
             snapshots.insert(market_id.clone(), market.snapshot());
             all_trades.extend(market.order_book.clear_and_match(&market_id));
         }
-        for (id, market) in self.financial_markets.iter_mut() {
-            let market_id = MarketId::Financial(id.clone());
-            snapshots.insert(market_id.clone(), market.snapshot());
-            all_trades.extend(market.order_book.clear_and_match(&market_id));
+        if financial_markets_open {
+            for (id, market) in self.financial_markets.iter_mut() {
+                let market_id = MarketId::Financial(id.clone());
+                snapshots.insert(market_id.clone(), market.snapshot());
+                all_trades.extend(market.order_book.clear_and_match(&market_id));
+            }
         }
         (all_trades, snapshots)
     }
@@ -522,4 +598,23 @@ pub struct LabourMarket {
     pub name: String,
     pub job_offers: Vec<JobOffer>,
     pub job_applications: Vec<JobApplication>,
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tenor_parsing_round_trips_and_names_known_lengths() {
+        for tenor in Tenor::NAMED.into_iter().chain([Tenor::Days(91 * 2 + 1)]) {
+            assert_eq!(tenor.to_string().parse::<Tenor>().unwrap(), tenor);
+        }
+        assert_eq!("T91D".parse::<Tenor>().unwrap(), Tenor::T13W);
+        assert_eq!("T8W".parse::<Tenor>().unwrap(), Tenor::Days(56));
+        assert_eq!(Tenor::Days(56).to_string(), "T56D");
+        assert!("T0D".parse::<Tenor>().is_err() && "13W".parse::<Tenor>().is_err() && "T13M".parse::<Tenor>().is_err());
+
+        let market: FinancialMarketId = "Treasury_T26W".parse().unwrap();
+        assert_eq!(market, FinancialMarketId::Treasury { tenor: Tenor::T26W });
+        assert!(Tenor::T52W.is_bill() && !Tenor::T2Y.is_bill());
+    }
+}
@@ -18,41 +18,73 @@
 //!   financial instruments, balance sheets, and market data.
 //! - **`agents.rs`**: Defines the primary agent types: `Bank`, `Consumer`, `Firm`, `Government`,
 //!   and `CentralBank`.
+//! - **`auctions.rs`**: Defines Treasury auctions: the government's `AuctionCalendar`, announced
+//!   `TreasuryAuction`s with their competitive and non-competitive `AuctionBid`s, and `AuctionResult`s.
 //! - **`balance_sheet.rs`**: Defines the `BalanceSheet` structure, which tracks an agent's assets
 //!   and liabilities.
+//! - **`calendar.rs`**: Defines the `HolidayCalendar` trait and the rule-based `RuleCalendar`s (fixed-date,
+//!   nth-weekday and Easter-relative holidays) that decide business days, loadable from TOML.
+//! - **`credit_registry.rs`**: Defines the `CreditRegistry` of delinquencies, defaults, ratings and credit events.
+//! - **`equity.rs`**: Defines the `EarningsReport` firms publish when they close a period and pay dividends.
 //! - **`instruments.rs`**: Defines `FinancialInstrument` and its various concrete types (e.g.,
 //!   `CashDetails`, `BondDetails`, `LoanDetails`).
+//! - **`lending.rs`**: Defines the `LoanDecision` each bank makes on a loan request, with the
+//!   borrower's `CreditScore` and the offered rate or the `RejectionReason`s.
 //! - **`goods.rs`**: Defines goods, inventories, and production recipes (`ProductionRecipe`), loading
 //!   them from a TOML configuration.
 //! - **`markets.rs`**: Defines market structures like `Exchange`, `OrderBook`, `Trade`, `Bid`, and `Ask`.
+//! - **`pricing.rs`**: Bond analytics (`BondPricer`): clean and dirty prices, accrued interest, yield to
+//!   maturity, duration, convexity and DV01, plus the benchmark issues the Treasury markets quote.
 //! - **`ids.rs`**: Defines strongly-typed unique identifiers used throughout the simulation (e.g.,
 //!   `AgentId`, `InstrumentId`).
 //! - **`macros.rs`**: Contains convenience macros for creating financial instruments (e.g., `cash!`, `deposit!`).
 //! - **`traits.rs`**: Defines core traits for interacting with the financial system, like `InstrumentManager`.
 //! - **`policy.rs`**: Defines structures related to fiscal and monetary policy.
+//! - **`payments.rs`**: Defines the interbank `PaymentSystem` queue and end-of-day `ClearingReport`s.
+//! - **`tax.rs`**: Defines the `CostBasis` of holdings, against which capital gains are taxed.
 //! - **`time.rs`**: Provides time and date-related utility functions.
+//! - **`yield_curve.rs`**: Defines the Treasury `YieldCurve`: stale-aware benchmark points, bootstrapped
+//!   zero rates, a Nelson–Siegel–Svensson fit and interpolation to any maturity.
 //!
 pub mod agents;
+pub mod auctions;
 pub mod balance_sheet;
+pub mod calendar;
+pub mod credit_registry;
+pub mod equity;
 pub mod goods;
 pub mod ids;
 pub mod instruments;
+pub mod lending;
 pub mod macros;
 pub mod markets;
+pub mod payments;
 pub mod policy;
+pub mod pricing;
 pub mod state;
 pub mod system;
+pub mod tax;
 pub mod time;
 pub mod traits;
+pub mod yield_curve;
 
 pub use agents::*;
+pub use auctions::*;
 pub use balance_sheet::*;
+pub use calendar::*;
+pub use credit_registry::*;
+pub use equity::*;
 pub use goods::*;
 pub use ids::*;
 pub use instruments::*;
+pub use lending::*;
 pub use markets::*;
+pub use payments::*;
 pub use policy::*;
+pub use pricing::*;
 pub use state::*;
 pub use system::*;
+pub use tax::*;
 pub use time::*;
-pub use traits::*;
\ No newline at end of file
+pub use traits::*;
+pub use yield_curve::*;
\ No newline at end of file
@@ -0,0 +1,105 @@
+use crate::*;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A reserve payment owed by one bank to another, created when bank money moves between banks.
+/// Obligations accumulate over the tick and are settled net at end of day.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct InterbankObligation {
+    pub from_bank: AgentId,
+    pub to_bank: AgentId,
+    pub amount: f64,
+    pub date: NaiveDate,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct PaymentSystem {
+    pub queue: Vec<InterbankObligation>,
+}
+
+impl PaymentSystem {
+    pub fn gross_flows(&self) -> f64 {
+        self.queue.iter().fold(0.0, |total, o| total + o.amount)
+    }
+
+    /// Multilateral net position of every participant: positive for net receivers, negative for net payers.
+    pub fn net_positions(&self) -> HashMap<AgentId, f64> {
+        let mut positions: HashMap<AgentId, f64> = HashMap::new();
+        for obligation in &self.queue {
+            *positions.entry(obligation.from_bank).or_insert(0.0) -= obligation.amount;
+            *positions.entry(obligation.to_bank).or_insert(0.0) += obligation.amount;
+        }
+        positions
+    }
+}
+
+/// A payment the payer could not fund from cash, deposits or reserves. Nothing moves, so whatever the
+/// payment was meant to discharge stays outstanding.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PaymentFailure {
+    pub tick: u32,
+    pub date: NaiveDate,
+    pub payer: AgentId,
+    pub payee: AgentId,
+    pub amount: f64,
+    pub available: f64,
+    pub instrument_id: Option<InstrumentId>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BankClearingPosition {
+    pub bank_id: AgentId,
+    pub gross_sent: f64,
+    pub gross_received: f64,
+    pub net_position: f64,
+    pub reserves_after: f64,
+    /// Reserves the bank had to hold against its deposits that day.
+    #[serde(default)]
+    pub required_reserves: f64,
+    pub facility_borrowing: f64,
+    /// Payments the bank could not settle even with the lending facility, left in the queue.
+    #[serde(default)]
+    pub deferred: f64,
+}
+
+/// End-of-day clearing summary. `net_flows` is the reserve volume that actually changed hands,
+/// so `gross_flows - net_flows` is the liquidity saved by netting.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ClearingReport {
+    pub tick: u32,
+    pub date: NaiveDate,
+    pub obligations: usize,
+    pub gross_flows: f64,
+    pub net_flows: f64,
+    pub facility_borrowing: f64,
+    pub positions: Vec<BankClearingPosition>,
+}
+
+impl ClearingReport {
+    pub fn netting_ratio(&self) -> f64 {
+        if self.gross_flows > 1e-9 { 1.0 - self.net_flows / self.gross_flows } else { 0.0 }
+    }
+}
+
+/// A bank's compliance with the reserve requirement over one maintenance period, averaged over the
+/// period's settlement days. `penalty` is charged on the `shortfall` for the period's calendar `days`
+/// at `penalty_rate`, ACT/360.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ReserveComplianceReport {
+    pub bank_id: AgentId,
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+    pub days: u32,
+    pub average_reserves: f64,
+    pub average_required: f64,
+    pub shortfall: f64,
+    pub penalty_rate: f64,
+    pub penalty: f64,
+}
+
+impl ReserveComplianceReport {
+    pub fn is_compliant(&self) -> bool {
+        self.shortfall <= 1e-6
+    }
+}
@@ -1,11 +1,32 @@
+use crate::*;
 use serde::{Deserialize, Serialize};
+use serde_with::{DisplayFromStr, serde_as};
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+/// Flat rates for each tax, plus the income tax schedule. Income above the `personal_allowance` is
+/// taxed at `income_tax`, and each of the `income_brackets` adds its surcharge on income above its
+/// threshold, so a change to `income_tax` moves the whole schedule.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct TaxRates {
     pub income_tax: f64,
     pub corporate_tax: f64,
     pub capital_gains: f64,
     pub consumption_tax: f64,
+    /// Withheld from wages to fund unemployment insurance.
+    #[serde(default)]
+    pub payroll_contribution: f64,
+    #[serde(default)]
+    pub personal_allowance: f64,
+    /// In ascending order of threshold.
+    #[serde(default)]
+    pub income_brackets: Vec<IncomeBracket>,
+}
+
+/// A band of the income tax schedule: annual income above `threshold` is taxed at the basic rate
+/// plus `surcharge`.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct IncomeBracket {
+    pub threshold: f64,
+    pub surcharge: f64,
 }
 
 impl Default for TaxRates {
@@ -15,11 +36,52 @@ impl Default for TaxRates {
             corporate_tax: 0.2,
             capital_gains: 0.15,
             consumption_tax: 0.1,
+            payroll_contribution: 0.02,
+            personal_allowance: 12_000.0,
+            income_brackets: vec![
+                IncomeBracket { threshold: 100_000.0, surcharge: 0.1 },
+                IncomeBracket { threshold: 250_000.0, surcharge: 0.2 },
+            ],
         }
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+impl TaxRates {
+    pub fn rate(&self, tax_type: &TaxType) -> f64 {
+        match tax_type {
+            TaxType::Income => self.income_tax,
+            TaxType::Corporate => self.corporate_tax,
+            TaxType::CapitalGains => self.capital_gains,
+            TaxType::Consumption => self.consumption_tax,
+            TaxType::PayrollContribution => self.payroll_contribution,
+        }
+    }
+
+    pub fn set_rate(&mut self, tax_type: &TaxType, rate: f64) {
+        match tax_type {
+            TaxType::Income => self.income_tax = rate,
+            TaxType::Corporate => self.corporate_tax = rate,
+            TaxType::CapitalGains => self.capital_gains = rate,
+            TaxType::Consumption => self.consumption_tax = rate,
+            TaxType::PayrollContribution => self.payroll_contribution = rate,
+        }
+    }
+
+    /// Income tax due on a year's income under the schedule.
+    pub fn income_tax_on(&self, annual_income: f64) -> f64 {
+        let mut tax = (annual_income - self.personal_allowance).max(0.0) * self.income_tax;
+        let mut surcharge = 0.0;
+        for bracket in &self.income_brackets {
+            tax += (annual_income - bracket.threshold).max(0.0) * (bracket.surcharge - surcharge);
+            surcharge = bracket.surcharge;
+        }
+        tax.max(0.0)
+    }
+}
+
+/// What the government plans to spend in a year, by category.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(default, rename_all = "camelCase")]
 pub struct SpendingTargets {
     pub transfers: f64,
     pub purchases: f64,
@@ -38,23 +100,401 @@ impl Default for SpendingTargets {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum SpendingCategory {
+    Transfers,
+    Purchases,
+    Investment,
+    DebtService,
+}
+
+impl SpendingTargets {
+    pub fn level(&self, category: &SpendingCategory) -> f64 {
+        match category {
+            SpendingCategory::Transfers => self.transfers,
+            SpendingCategory::Purchases => self.purchases,
+            SpendingCategory::Investment => self.investment,
+            SpendingCategory::DebtService => self.debt_service,
+        }
+    }
+
+    pub fn set_level(&mut self, category: &SpendingCategory, level: f64) {
+        match category {
+            SpendingCategory::Transfers => self.transfers = level,
+            SpendingCategory::Purchases => self.purchases = level,
+            SpendingCategory::Investment => self.investment = level,
+            SpendingCategory::DebtService => self.debt_service = level,
+        }
+    }
+
+    pub fn total(&self) -> f64 {
+        self.transfers + self.purchases + self.investment + self.debt_service
+    }
+}
+
+/// A programme of transfers to consumers.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum TransferProgram {
+    /// Paid equally to every consumer.
+    Universal,
+    /// Paid to consumers on low incomes, more the lower the income.
+    MeansTested,
+    /// Paid to consumers who have lost their job, under the `UnemploymentInsurance` scheme.
+    UnemploymentInsurance,
+}
+
+/// The government's unemployment insurance scheme. A consumer out of work qualifies with at least
+/// `qualifying_weeks` of work in the `lookback_weeks` before their last job ended, and is paid
+/// `replacement_rate` of that job's weekly wage for up to `max_weeks` after it ended. Benefits are
+/// an entitlement, paid on top of the transfers target.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct UnemploymentInsurance {
+    pub replacement_rate: f64,
+    pub max_weeks: i64,
+    pub qualifying_weeks: i64,
+    pub lookback_weeks: i64,
+}
+
+impl Default for UnemploymentInsurance {
+    fn default() -> Self {
+        Self { replacement_rate: 0.5, max_weeks: 26, qualifying_weeks: 13, lookback_weeks: 52 }
+    }
+}
+
+impl UnemploymentInsurance {
+    /// The benefit `consumer` is due for the week of `date`, if any.
+    pub fn weekly_benefit(&self, consumer: &Consumer, date: chrono::NaiveDate) -> Option<f64> {
+        if consumer.employed_by.is_some() {
+            return None;
+        }
+        let last_job = consumer.employment_history.last()?;
+        if (date - last_job.end_date).num_weeks() >= self.max_weeks {
+            return None;
+        }
+        let lookback_start = last_job.end_date - chrono::Duration::weeks(self.lookback_weeks);
+        let weeks_worked: i64 =
+            consumer.employment_history.iter().map(|job| job.weeks_between(lookback_start, last_job.end_date)).sum();
+        let benefit = self.replacement_rate * last_job.weekly_wage;
+        (weeks_worked >= self.qualifying_weeks && benefit > 0.0).then_some(benefit)
+    }
+}
+
+/// What a government payment is for.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum SpendingPurpose {
+    Benefit(TransferProgram),
+    /// A grant to a firm for public capital, which raises its productivity.
+    PublicInvestment,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct Disbursement {
+    pub recipient: AgentId,
+    pub amount: f64,
+    pub purpose: SpendingPurpose,
+}
+
+/// What the government steers its budget towards. Targets are annual amounts.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub enum FiscalPolicy {
     Balanced,
     Expansionary { deficit_target: f64 },
     Contractionary { surplus_target: f64 },
+    /// No discretionary changes: tax rates and spending targets stay put and the budget moves with
+    /// the economy, through tax receipts and unemployment insurance claims. The other policies steer
+    /// the budget net of the insurance scheme, so its benefits and contributions still move freely.
     Automatic,
 }
+
+impl FiscalPolicy {
+    /// The annual deficit aimed for, negative for a surplus. `None` when the budget is left to move.
+    pub fn target_deficit(&self) -> Option<f64> {
+        match self {
+            FiscalPolicy::Balanced => Some(0.0),
+            FiscalPolicy::Expansionary { deficit_target } => Some(*deficit_target),
+            FiscalPolicy::Contractionary { surplus_target } => Some(-surplus_target),
+            FiscalPolicy::Automatic => None,
+        }
+    }
+}
 impl Default for FiscalPolicy {
     fn default() -> Self {
         FiscalPolicy::Balanced
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub enum TaxType {
     Income,
     Corporate,
     CapitalGains,
     Consumption,
+    PayrollContribution,
+}
+
+/// The government's budget outturn for the period closing on `date`: tax `revenue`, programme
+/// `spending` and net `interest` on its debt. `debt` is the face value outstanding at the close.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct FiscalReport {
+    pub date: chrono::NaiveDate,
+    pub revenue: f64,
+    pub spending: f64,
+    pub interest: f64,
+    pub debt: f64,
+}
+
+impl FiscalReport {
+    /// Negative for a surplus.
+    pub fn deficit(&self) -> f64 {
+        self.spending + self.interest - self.revenue
+    }
+}
+
+/// The central bank's standing facilities, priced as spreads around the policy rate.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct StandingFacilities {
+    /// Spread of the overnight lending facility over the policy rate; the ceiling of the rate corridor.
+    pub lending_spread: f64,
+    /// Spread of the overnight deposit facility under the policy rate; the floor of the rate corridor.
+    pub deposit_spread: f64,
+    /// Spread under the policy rate paid on reserves above the requirement. It is below the deposit
+    /// facility, so a bank does better lending surplus reserves overnight or placing them at the facility.
+    pub excess_reserves_spread: f64,
+    /// Haircut on the market value of Treasuries pledged to the lending facility.
+    pub collateral_haircut: f64,
+}
+
+impl Default for StandingFacilities {
+    fn default() -> Self {
+        Self {
+            lending_spread: 0.0025,
+            deposit_spread: 0.0025,
+            excess_reserves_spread: 0.0050,
+            collateral_haircut: 0.02,
+        }
+    }
+}
+
+/// The central bank's reaction function: a Taylor rule on CPI inflation and the unemployment gap,
+/// smoothed towards the current rate and floored at an effective lower bound.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct TaylorRule {
+    /// The real rate that is neither expansionary nor contractionary.
+    pub neutral_real_rate: f64,
+    pub inflation_target: f64,
+    pub inflation_weight: f64,
+    /// Weight on the natural rate less actual unemployment. By Okun's law a point of unemployment gap
+    /// is about two points of output gap, so 1.0 matches the classic 0.5 on the output gap.
+    pub unemployment_weight: f64,
+    pub natural_unemployment: f64,
+    /// Share of the current rate carried into the new one; 0 moves straight to the prescribed rate.
+    pub inertia: f64,
+    pub lower_bound: f64,
+}
+
+impl Default for TaylorRule {
+    fn default() -> Self {
+        Self {
+            neutral_real_rate: 0.01,
+            inflation_target: 0.02,
+            inflation_weight: 0.5,
+            unemployment_weight: 1.0,
+            natural_unemployment: 0.045,
+            inertia: 0.8,
+            lower_bound: 0.0,
+        }
+    }
+}
+
+impl TaylorRule {
+    /// The rate the rule calls for, before smoothing and the lower bound. Without an unemployment rate
+    /// the gap term is left out.
+    pub fn prescribe(&self, inflation: f64, unemployment: Option<f64>) -> f64 {
+        self.neutral_real_rate
+            + inflation
+            + self.inflation_weight * (inflation - self.inflation_target)
+            + unemployment.map_or(0.0, |rate| self.unemployment_weight * (self.natural_unemployment - rate))
+    }
+
+    pub fn next_rate(&self, current_rate: f64, prescribed_rate: f64) -> f64 {
+        (self.inertia * current_rate + (1.0 - self.inertia) * prescribed_rate).max(self.lower_bound)
+    }
+}
+
+/// When the policy committee meets. Meetings are held on the given `dates`, a date that is not a
+/// business day moving to the next one, or every `interval_days` when no dates are given.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct MeetingCalendar {
+    pub dates: Vec<chrono::NaiveDate>,
+    pub interval_days: i64,
+}
+
+impl Default for MeetingCalendar {
+    /// Eight meetings a year, as the FOMC holds.
+    fn default() -> Self {
+        Self { dates: Vec::new(), interval_days: 45 }
+    }
+}
+
+impl MeetingCalendar {
+    /// Whether the committee meets on business day `date`, given when it last met.
+    pub fn is_meeting(
+        &self, date: chrono::NaiveDate, last_meeting: Option<chrono::NaiveDate>, calendar: &dyn HolidayCalendar,
+    ) -> bool {
+        if self.dates.is_empty() {
+            return last_meeting.is_none_or(|last| (date - last).num_days() >= self.interval_days);
+        }
+        let previous_business_day = previous_business_day(date, calendar);
+        self.dates.iter().any(|meeting| *meeting > previous_business_day && *meeting <= date)
+    }
+}
+
+/// The outcome of a policy meeting and the data it was taken on.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct PolicyDecision {
+    pub date: chrono::NaiveDate,
+    pub previous_rate: f64,
+    pub new_rate: f64,
+    /// The rate the reaction function called for before smoothing and the lower bound.
+    pub prescribed_rate: f64,
+    pub inflation: f64,
+    /// `None` when there was no labour force to measure.
+    pub unemployment: Option<f64>,
+}
+
+/// Which way an open market operation moves reserves: purchases add them, sales drain them.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum OperationSide {
+    Purchase,
+    Sale,
+}
+
+/// How the central bank deals in the Treasury market. `Outright` posts a limit order on the book,
+/// which rests until it clears. `Auction` takes the best orders already on the book, each at its own
+/// price, and settles at once: a reverse auction of dealers' offers for a purchase, an auction of their
+/// bids for a sale.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum OperationMethod {
+    Outright,
+    Auction,
+}
+
+/// One open market operation in the Treasury market for `tenor`. `limit_price` is the clean price per
+/// bond the central bank pays at most when buying and accepts at least when selling.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct OpenMarketOperation {
+    pub tenor: Tenor,
+    pub side: OperationSide,
+    pub method: OperationMethod,
+    pub quantity: f64,
+    pub limit_price: f64,
+}
+
+/// Treasuries of one tenor the central bank aims to hold, in bonds.
+#[serde_as]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct HoldingTarget {
+    #[serde_as(as = "DisplayFromStr")]
+    pub tenor: Tenor,
+    pub quantity: f64,
+}
+
+/// Shrinks every target by `monthly_cap` bonds in each month from `start`, the first cut falling on
+/// `start` itself. Holdings above the shrunken target run off as they mature, or are also auctioned
+/// off when `sell` is set.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RunoffSchedule {
+    pub start: chrono::NaiveDate,
+    pub monthly_cap: f64,
+    #[serde(default)]
+    pub sell: bool,
+}
+
+impl RunoffSchedule {
+    /// Cuts made by `date`.
+    fn months_run(&self, date: chrono::NaiveDate) -> f64 {
+        use chrono::Datelike;
+        if date < self.start {
+            return 0.0;
+        }
+        let months = (date.year() - self.start.year()) * 12 + date.month() as i32 - self.start.month() as i32;
+        (months + i32::from(date.day() >= self.start.day())) as f64
+    }
+}
+
+/// A balance-sheet policy: Treasury holdings the central bank buys up to through daily auctions (QE),
+/// and the schedule they run off on (QT). With no targets the central bank holds no Treasuries.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct AssetPurchaseProgram {
+    pub targets: Vec<HoldingTarget>,
+    /// Most bonds of a tenor bought or sold in one day's auction.
+    pub max_daily_operation: f64,
+    /// How far above the curve's price the central bank pays when buying, and below it accepts when
+    /// selling, as a fraction of the price.
+    pub price_tolerance: f64,
+    pub runoff: Option<RunoffSchedule>,
+}
+
+impl Default for AssetPurchaseProgram {
+    fn default() -> Self {
+        Self { targets: Vec::new(), max_daily_operation: 100.0, price_tolerance: 0.005, runoff: None }
+    }
+}
+
+impl AssetPurchaseProgram {
+    /// Bonds of `tenor` the program holds on `date`, after any runoff. `None` for a tenor it does not target.
+    pub fn target(&self, tenor: &Tenor, date: chrono::NaiveDate) -> Option<f64> {
+        let target = self.targets.iter().find(|target| target.tenor == *tenor)?;
+        let runoff = self.runoff.as_ref().map_or(0.0, |runoff| runoff.monthly_cap * runoff.months_run(date));
+        Some((target.quantity - runoff).max(0.0))
+    }
+
+    /// Whether holdings above target are sold rather than left to mature.
+    pub fn sells_excess(&self) -> bool {
+        self.runoff.as_ref().is_some_and(|runoff| runoff.sell)
+    }
+}
+
+/// Treasuries of one tenor on the central bank's balance sheet.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct TreasuryHolding {
+    pub tenor: Tenor,
+    pub quantity: u64,
+    pub market_value: f64,
+}
+
+/// The central bank's balance sheet by line at the end of a day, Treasuries marked to market.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct CentralBankBalanceSheet {
+    pub date: chrono::NaiveDate,
+    /// By tenor, shortest first.
+    pub treasuries: Vec<TreasuryHolding>,
+    /// Overnight loans from the lending facility.
+    pub facility_lending: f64,
+    pub other_assets: f64,
+    pub reserves: f64,
+    /// Overnight placements at the deposit facility.
+    pub deposit_facility: f64,
+    pub currency: f64,
+    pub other_liabilities: f64,
+}
+
+impl CentralBankBalanceSheet {
+    pub fn treasury_value(&self) -> f64 {
+        self.treasuries.iter().map(|holding| holding.market_value).sum()
+    }
+
+    pub fn total_assets(&self) -> f64 {
+        self.treasury_value() + self.facility_lending + self.other_assets
+    }
+
+    pub fn total_liabilities(&self) -> f64 {
+        self.reserves + self.deposit_facility + self.currency + self.other_liabilities
+    }
 }
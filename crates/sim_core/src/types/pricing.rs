@@ -0,0 +1,285 @@
+use crate::*;
+use chrono::{Months, NaiveDate};
+
+/// Coupon rate of the benchmark issue each Treasury market quotes.
+pub const BENCHMARK_COUPON_RATE: f64 = 0.04;
+/// Face value of one benchmark Treasury.
+pub const BENCHMARK_FACE_VALUE: f64 = 1_000.0;
+/// Coupons per year on benchmark Treasuries.
+pub const BENCHMARK_FREQUENCY: usize = 2;
+
+impl BondDetails {
+    /// The on-the-run Treasury of `tenor` issued on `date`, which the Treasury market for that tenor
+    /// quotes in clean price per bond. Bills pay no coupon, but their yields still compound
+    /// semi-annually so the short end of the curve is comparable with the notes.
+    pub fn benchmark(tenor: Tenor, date: NaiveDate) -> Self {
+        Self {
+            bond_type: BondType::Government,
+            coupon_rate: if tenor.is_bill() { 0.0 } else { BENCHMARK_COUPON_RATE },
+            face_value: BENCHMARK_FACE_VALUE,
+            maturity_date: tenor.add_to_date(date),
+            frequency: BENCHMARK_FREQUENCY,
+            tenor,
+            quantity: 1,
+            floating: None,
+            inflation_linked: None,
+        }
+    }
+
+    /// A holding of these bonds issued to `holder` by `issuer` on `date`, coupons on `calendar`.
+    pub fn issue(
+        self, holder: AgentId, issuer: AgentId, date: NaiveDate, calendar: &dyn HolidayCalendar,
+    ) -> FinancialInstrument {
+        FinancialInstrument {
+            id: InstrumentId(uuid::Uuid::new_v4()),
+            creditor: holder,
+            debtor: issuer,
+            principal: self.face_value * self.quantity as f64,
+            day_count: self.bond_type.day_count(),
+            payment_schedule: self.coupon_schedule(date, calendar),
+            details: Box::new(self),
+            originated_date: date,
+            accrued_interest: 0.0,
+            last_accrual_date: date,
+        }
+    }
+
+    pub fn pricer(&self, settlement: NaiveDate) -> BondPricer<'_> {
+        BondPricer::new(self, settlement)
+    }
+}
+
+/// Prices and risk measures for one bond of `face_value`, settling on `settlement`. Coupons fall
+/// every `12 / frequency` months counting back from maturity. Yields compound at the coupon
+/// frequency, and the first cash flow is discounted over the fraction of the coupon period left to
+/// run. A bond at or past maturity is worth its face value.
+pub struct BondPricer<'a> {
+    pub bond: &'a BondDetails,
+    pub settlement: NaiveDate,
+}
+
+impl<'a> BondPricer<'a> {
+    pub fn new(bond: &'a BondDetails, settlement: NaiveDate) -> Self {
+        Self { bond, settlement }
+    }
+
+    fn frequency(&self) -> f64 {
+        self.bond.frequency.max(1) as f64
+    }
+
+    /// The `n`th coupon date counting back from maturity, which is the 0th.
+    fn coupon_date(&self, n: u32) -> NaiveDate {
+        let months = 12 / self.bond.frequency.clamp(1, 12) as u32;
+        self.bond.maturity_date.checked_sub_months(Months::new(months * n)).unwrap_or(NaiveDate::MIN)
+    }
+
+    /// Number of coupons still to be paid.
+    fn remaining_coupons(&self) -> u32 {
+        let mut n = 0;
+        while self.coupon_date(n) > self.settlement {
+            n += 1;
+        }
+        n
+    }
+
+    pub fn coupon(&self) -> f64 {
+        self.bond.face_value * self.bond.coupon_rate / self.frequency()
+    }
+
+    /// Share of the current coupon period that has already run.
+    fn elapsed_fraction(&self) -> f64 {
+        let n = self.remaining_coupons();
+        if n == 0 {
+            return 0.0;
+        }
+        let (previous, next) = (self.coupon_date(n), self.coupon_date(n - 1));
+        let period_days = (next - previous).num_days() as f64;
+        if period_days <= 0.0 { 0.0 } else { (self.settlement - previous).num_days() as f64 / period_days }
+    }
+
+    /// `(periods from settlement, amount)` for each remaining coupon and the redemption.
+    fn cash_flows(&self) -> Vec<(f64, f64)> {
+        let n = self.remaining_coupons();
+        let to_next = 1.0 - self.elapsed_fraction();
+        (0..n)
+            .map(|i| {
+                let redemption = if i + 1 == n { self.bond.face_value } else { 0.0 };
+                (to_next + i as f64, self.coupon() + redemption)
+            })
+            .collect()
+    }
+
+    /// `(years from settlement, amount)` for each remaining coupon and the redemption.
+    pub fn cash_flows_in_years(&self) -> Vec<(f64, f64)> {
+        self.cash_flows().into_iter().map(|(t, amount)| (t / self.frequency(), amount)).collect()
+    }
+
+    pub fn accrued_interest(&self) -> f64 {
+        self.coupon() * self.elapsed_fraction()
+    }
+
+    /// Present value of the remaining cash flows, including accrued interest.
+    pub fn dirty_price(&self, ytm: f64) -> f64 {
+        let flows = self.cash_flows();
+        if flows.is_empty() {
+            return self.bond.face_value;
+        }
+        let per_period = 1.0 + ytm / self.frequency();
+        flows.iter().map(|(t, amount)| amount / per_period.powf(*t)).sum()
+    }
+
+    /// Quoted price: the dirty price less accrued interest.
+    pub fn clean_price(&self, ytm: f64) -> f64 {
+        self.dirty_price(ytm) - self.accrued_interest()
+    }
+
+    /// The annual yield, compounded at the coupon frequency, at which the bond's clean price is
+    /// `clean_price`. `None` if the bond has matured or no yield gives that price.
+    pub fn yield_to_maturity(&self, clean_price: f64) -> Option<f64> {
+        if clean_price <= 0.0 || self.cash_flows().is_empty() {
+            return None;
+        }
+        let target = clean_price + self.accrued_interest();
+        let error = |ytm: f64| self.dirty_price(ytm) - target;
+
+        let mut ytm = self.bond.coupon_rate;
+        for _ in 0..50 {
+            let slope = -self.modified_duration(ytm) * self.dirty_price(ytm);
+            if slope.abs() < 1e-12 {
+                break;
+            }
+            let next = ytm - error(ytm) / slope;
+            if !next.is_finite() || next <= -self.frequency() {
+                break;
+            }
+            if (next - ytm).abs() < 1e-12 {
+                return Some(next);
+            }
+            ytm = next;
+        }
+
+        // Price falls as the yield rises, so bisect if Newton's method did not settle
+        let (mut low, mut high) = (-0.99 * self.frequency(), 10.0);
+        if error(low) < 0.0 || error(high) > 0.0 {
+            return None;
+        }
+        for _ in 0..200 {
+            let mid = (low + high) / 2.0;
+            if error(mid) > 0.0 { low = mid } else { high = mid }
+        }
+        Some((low + high) / 2.0)
+    }
+
+    /// Present-value weighted average time to the cash flows, in years.
+    pub fn macaulay_duration(&self, ytm: f64) -> f64 {
+        let flows = self.cash_flows();
+        let per_period = 1.0 + ytm / self.frequency();
+        let price = self.dirty_price(ytm);
+        if flows.is_empty() || price <= 0.0 {
+            return 0.0;
+        }
+        let weighted: f64 = flows.iter().map(|(t, amount)| t * amount / per_period.powf(*t)).sum();
+        weighted / price / self.frequency()
+    }
+
+    /// Relative price change for a unit change in yield.
+    pub fn modified_duration(&self, ytm: f64) -> f64 {
+        self.macaulay_duration(ytm) / (1.0 + ytm / self.frequency())
+    }
+
+    pub fn convexity(&self, ytm: f64) -> f64 {
+        let flows = self.cash_flows();
+        let k = self.frequency();
+        let per_period = 1.0 + ytm / k;
+        let price = self.dirty_price(ytm);
+        if flows.is_empty() || price <= 0.0 {
+            return 0.0;
+        }
+        let weighted: f64 = flows.iter().map(|(t, amount)| t * (t + 1.0) * amount / per_period.powf(*t)).sum();
+        weighted / (price * per_period.powi(2) * k * k)
+    }
+
+    /// Bank discount rate of a bill at `clean_price`: the discount from face value, annualised over a
+    /// 360-day year.
+    pub fn discount_rate(&self, clean_price: f64) -> f64 {
+        let days = (self.bond.maturity_date - self.settlement).num_days().max(1) as f64;
+        (self.bond.face_value - clean_price) / self.bond.face_value * 360.0 / days
+    }
+
+    /// Price of a bill quoted at the bank discount rate `discount_rate`.
+    pub fn price_at_discount_rate(&self, discount_rate: f64) -> f64 {
+        let days = (self.bond.maturity_date - self.settlement).num_days().max(0) as f64;
+        self.bond.face_value * (1.0 - discount_rate * days / 360.0)
+    }
+
+    /// Change in dirty price for a one basis point fall in yield.
+    pub fn dv01(&self, ytm: f64) -> f64 {
+        self.modified_duration(ytm) * self.dirty_price(ytm) * 0.0001
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn five_year(coupon_rate: f64) -> BondDetails {
+        let benchmark = BondDetails::benchmark(Tenor::T5Y, date(2026, 1, 1));
+        BondDetails { coupon_rate, maturity_date: date(2031, 1, 1), ..benchmark }
+    }
+
+    #[test]
+    fn test_bond_prices_at_par_when_yield_equals_coupon() {
+        let bond = five_year(0.04);
+        let pricer = bond.pricer(date(2026, 1, 1));
+        assert!((pricer.clean_price(0.04) - 1_000.0).abs() < 1e-9);
+        assert!(pricer.clean_price(0.05) < 1_000.0 && pricer.clean_price(0.03) > 1_000.0);
+        assert!((pricer.yield_to_maturity(950.0).unwrap() - 0.0515).abs() < 1e-3);
+        assert!((pricer.clean_price(pricer.yield_to_maturity(950.0).unwrap()) - 950.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_accrued_interest_is_removed_from_clean_price() {
+        let bond = five_year(0.04);
+        let pricer = bond.pricer(date(2026, 4, 1));
+        // 90 of the 181 days from 1 January to 1 July have run on a 20.00 coupon
+        assert!((pricer.accrued_interest() - 20.0 * 90.0 / 181.0).abs() < 1e-9);
+        assert!((pricer.clean_price(0.04) - 1_000.0).abs() < 0.1);
+        assert!((pricer.dirty_price(0.04) - pricer.clean_price(0.04) - pricer.accrued_interest()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_duration_convexity_and_dv01() {
+        let zero = five_year(0.0);
+        let pricer = zero.pricer(date(2026, 1, 1));
+        assert!((pricer.macaulay_duration(0.05) - 5.0).abs() < 1e-9);
+        assert!((pricer.modified_duration(0.05) - 5.0 / 1.025).abs() < 1e-9);
+
+        let bond = five_year(0.04);
+        let pricer = bond.pricer(date(2026, 1, 1));
+        let (ytm, bump) = (0.04, 0.0001);
+        let (up, down) = (pricer.dirty_price(ytm + bump), pricer.dirty_price(ytm - bump));
+        let price = pricer.dirty_price(ytm);
+        assert!((pricer.dv01(ytm) - (down - up) / 2.0).abs() < 1e-4);
+        assert!((pricer.convexity(ytm) - (up + down - 2.0 * price) / (price * bump * bump)).abs() < 0.05);
+        assert!(pricer.macaulay_duration(ytm) < 5.0);
+    }
+
+    #[test]
+    fn test_bill_is_issued_at_a_discount_and_redeems_at_par() {
+        let bill = BondDetails::benchmark(Tenor::T13W, date(2026, 1, 1));
+        assert_eq!((bill.coupon_rate, bill.maturity_date), (0.0, date(2026, 4, 2)));
+        assert_eq!(bill.coupon_schedule(date(2026, 1, 1), &RuleCalendar::weekends_only()), vec![date(2026, 4, 2)]);
+
+        let pricer = bill.pricer(date(2026, 1, 1));
+        let price = pricer.clean_price(0.04);
+        assert!(price < 1_000.0 && pricer.accrued_interest() == 0.0);
+        assert!((pricer.yield_to_maturity(price).unwrap() - 0.04).abs() < 1e-9);
+        assert!((pricer.price_at_discount_rate(0.04) - 1_000.0 * (1.0 - 0.04 * 91.0 / 360.0)).abs() < 1e-9);
+        assert!((pricer.discount_rate(pricer.price_at_discount_rate(0.04)) - 0.04).abs() < 1e-12);
+        assert_eq!(bill.pricer(date(2026, 4, 2)).clean_price(0.04), 1_000.0);
+    }
+}
@@ -3,6 +3,11 @@ use serde_with::{serde_as, DisplayFromStr};
 use std::collections::{HashMap, HashSet, VecDeque}; // Import VecDeque
 use crate::*;
 
+/// Days of CPI readings averaged at each end of the span inflation is measured over.
+const CPI_AVERAGING_DAYS: i64 = 30;
+/// Shortest span of CPI history inflation is measured over.
+const MIN_INFLATION_SPAN_DAYS: i64 = 91;
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SimState {
     pub ticknum: u32,
@@ -95,7 +100,7 @@ impl SimState {
             };
 
 
-            MarketView {
+            let mut view = MarketView {
                 last: latest.close, // Use close price as the definitive last price
                 mid: latest.best_bid.and_then(|bid| latest.best_ask.map(|ask| (bid + ask) / 2.0)),
                 spread: latest.spread,
@@ -104,11 +109,57 @@ impl SimState {
                 vwap_5: calculate_vwap(5),
                 ma_20: calculate_ma(20),
                 realized_vol_20: calculate_vol(20),
+                market_cap: None,
+                pe_ratio: None,
+            };
+            if let MarketId::Financial(FinancialMarketId::Equity { firm }) = market_id
+                && let Some(price) = view.last_or_mid()
+            {
+                let shares = self.financial_system.shares_outstanding(firm) as f64;
+                view.market_cap = Some(price * shares);
+                view.pe_ratio = self
+                    .latest_earnings(firm)
+                    .and_then(|report| report.earnings_per_share())
+                    .filter(|eps| *eps > 0.0)
+                    .map(|eps| price / eps);
             }
+            view
         })
     }
 
+    pub fn latest_earnings(&self, firm: &AgentId) -> Option<&EarningsReport> {
+        self.history.earnings_reports.iter().rev().find(|report| report.firm == *firm)
+    }
+
+    /// Book equity per share: what the firm owns less what it owes its creditors, over shares outstanding.
+    pub fn book_value_per_share(&self, firm: &AgentId) -> Option<f64> {
+        let bs = self.financial_system.get_bs_by_id(firm)?;
+        let shares = self.financial_system.shares_outstanding(firm);
+        (shares > 0).then(|| (bs.total_assets() - bs.total_debt()) / shares as f64)
+    }
+
+    /// The CPI and its annual inflation rate, which is 2% until there is enough CPI history to measure it.
     pub fn cpi_view(&self) -> InflationView {
+        let cpi = self.current_cpi();
+        let inflation_rate = self.history.annual_inflation(self.current_date).unwrap_or(0.02);
+        InflationView { cpi, inflation_rate }
+    }
+
+    /// Share of the labour force without an employer, or `None` when there is no labour force. The
+    /// labour force is every consumer in work or who has worked before.
+    pub fn unemployment_rate(&self) -> Option<f64> {
+        let (labour_force, unemployed) = self
+            .agents
+            .consumers
+            .values()
+            .filter(|consumer| consumer.employed_by.is_some() || !consumer.employment_history.is_empty())
+            .fold((0, 0), |(labour_force, unemployed), consumer| {
+                (labour_force + 1, unemployed + usize::from(consumer.employed_by.is_none()))
+            });
+        (labour_force > 0).then(|| unemployed as f64 / labour_force as f64)
+    }
+
+    fn current_cpi(&self) -> f64 {
         let mut current_cpi = 0.0;
         let mut total_weight = 0.0;
         
@@ -128,11 +179,57 @@ impl SimState {
              current_cpi /= total_weight;
         }
 
-        
-        let inflation_rate = 0.02; 
 
-        InflationView { cpi: current_cpi, inflation_rate }
+        current_cpi
+    }
+    /// Today's fixing of `index`, as an annual rate. SOFR is read off the overnight market's last trade
+    /// or quote and falls back to the policy rate before the market has traded.
+    pub fn rate_fixing(&self, index: RateIndex) -> f64 {
+        let policy_rate = self.financial_system.central_bank.policy_rate;
+        match index {
+            RateIndex::PolicyRate => policy_rate,
+            RateIndex::Sofr => {
+                let sofr = FinancialMarketId::SecuredOvernightFinancing;
+                self.market_view(&MarketId::Financial(sofr.clone()))
+                    .and_then(|view| view.last_or_mid())
+                    .map(|price| sofr.daily_rate_to_annual_bps(sofr.price_to_daily_rate(price)) / 10000.0)
+                    .filter(|rate| rate.is_finite())
+                    .unwrap_or(policy_rate)
+            }
+        }
+    }
+
+    /// Last traded (or quoted) price of `firm`'s shares, falling back to book value per share.
+    pub fn share_price(&self, firm: &AgentId) -> Option<f64> {
+        let market_id = MarketId::Financial(FinancialMarketId::Equity { firm: *firm });
+        self.market_view(&market_id)
+            .and_then(|view| view.last_or_mid())
+            .or_else(|| self.book_value_per_share(firm))
+    }
+
+    /// Net worth with share holdings marked to their market price, so household wealth moves with the
+    /// value of the firms it owns.
+    pub fn household_wealth(&self, agent_id: &AgentId) -> f64 {
+        let Some(bs) = self.financial_system.get_bs_by_id(agent_id) else {
+            return 0.0;
+        };
+        let revaluation: f64 = bs
+            .assets
+            .values()
+            .map(|inst| {
+                if inst.details.as_any().is::<EquityDetails>() {
+                    let shares = inst.quantity().unwrap_or(0) as f64;
+                    self.share_price(&inst.debtor).map_or(0.0, |price| price * shares - inst.principal)
+                } else {
+                    self.financial_system
+                        .bond_market_value(inst, self.current_date)
+                        .map_or(0.0, |value| value - inst.principal)
+                }
+            })
+            .sum();
+        bs.net_worth() + revaluation
     }
+
     pub fn all_market_views(&self) -> HashMap<String, MarketView> {
         let mut views = HashMap::new();
         for (market_id, _) in &self.history.market_ticks {
@@ -202,6 +299,90 @@ pub struct SimHistory {
     pub transactions: Vec<Transaction>,
     #[serde_as(as = "HashMap<DisplayFromStr, _>")]
     pub market_ticks: HashMap<MarketId, VecDeque<MarketTick>>,
+    pub clearing_reports: Vec<ClearingReport>,
+    pub payment_failures: Vec<PaymentFailure>,
+    pub earnings_reports: Vec<EarningsReport>,
+    pub yield_curves: Vec<YieldCurve>,
+    /// The CPI at the end of each day.
+    pub cpi: Vec<(chrono::NaiveDate, f64)>,
+    pub policy_decisions: Vec<PolicyDecision>,
+    /// The central bank's balance sheet at the end of each day.
+    pub central_bank_balance_sheets: Vec<CentralBankBalanceSheet>,
+    /// Every bank's reserve compliance at the end of each maintenance period.
+    pub reserve_compliance: Vec<ReserveComplianceReport>,
+    /// The government's budget outturn for each fiscal period.
+    pub fiscal_reports: Vec<FiscalReport>,
+    pub auction_results: Vec<AuctionResult>,
+    /// Every bank's decision on each loan request.
+    pub loan_decisions: Vec<LoanDecision>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LedgerPage {
+    pub agent_id: AgentId,
+    pub total: usize,
+    pub offset: usize,
+    pub limit: usize,
+    pub transactions: Vec<Transaction>,
+}
+
+impl SimHistory {
+    /// The curve as it stood at the end of `date`.
+    pub fn yield_curve_on(&self, date: chrono::NaiveDate) -> Option<&YieldCurve> {
+        self.yield_curves.iter().rev().find(|curve| curve.date == date)
+    }
+
+    /// CPI inflation over the year to `date`. Each end of the year is the average of a month of
+    /// readings, which takes out the day-to-day noise in goods prices. With less than a year of history
+    /// the change since the first month is annualised, once at least a quarter separates the two.
+    /// `None` before then.
+    pub fn annual_inflation(&self, date: chrono::NaiveDate) -> Option<f64> {
+        let first = self.cpi.iter().find(|(_, cpi)| *cpi > 0.0)?.0;
+        let base_date =
+            (date - chrono::Duration::days(365)).max(first + chrono::Duration::days(CPI_AVERAGING_DAYS - 1));
+        let days = (date - base_date).num_days();
+        if days < MIN_INFLATION_SPAN_DAYS {
+            return None;
+        }
+        let (current, base) = (self.average_cpi(date)?, self.average_cpi(base_date)?);
+        Some((current / base).powf(365.0 / days as f64) - 1.0)
+    }
+
+    /// The average CPI over the month to `date`.
+    fn average_cpi(&self, date: chrono::NaiveDate) -> Option<f64> {
+        let readings: Vec<f64> = self
+            .cpi
+            .iter()
+            .filter(|(observed, cpi)| {
+                *observed <= date && (date - *observed).num_days() < CPI_AVERAGING_DAYS && *cpi > 0.0
+            })
+            .map(|(_, cpi)| *cpi)
+            .collect();
+        (!readings.is_empty()).then(|| readings.iter().sum::<f64>() / readings.len() as f64)
+    }
+
+    /// The decisions on `borrower`'s loan requests, oldest first.
+    pub fn loan_decisions_for<'a>(
+        &'a self, borrower: &'a AgentId,
+    ) -> impl DoubleEndedIterator<Item = &'a LoanDecision> {
+        self.loan_decisions.iter().filter(move |decision| decision.borrower == *borrower)
+    }
+
+    /// Whether `borrower` has asked the banks for a loan on or after `since`.
+    pub fn requested_loan_since(&self, borrower: &AgentId, since: chrono::NaiveDate) -> bool {
+        self.loan_decisions_for(borrower).next_back().is_some_and(|decision| decision.date >= since)
+    }
+
+    pub fn transactions_for(&self, agent_id: &AgentId) -> impl DoubleEndedIterator<Item = &Transaction> {
+        self.transactions.iter().filter(move |tx| tx.involves(agent_id))
+    }
+
+    /// Returns one page of an agent's ledger, most recent transactions first.
+    pub fn ledger_page(&self, agent_id: &AgentId, offset: usize, limit: usize) -> LedgerPage {
+        let total = self.transactions_for(agent_id).count();
+        let transactions = self.transactions_for(agent_id).rev().skip(offset).take(limit).cloned().collect();
+        LedgerPage { agent_id: *agent_id, total, offset, limit, transactions }
+    }
 }
 
 
@@ -17,23 +17,35 @@ pub struct FinancialSystem {
     pub exchange: Exchange,
     pub goods: GoodsRegistry,
     pub yield_curve: YieldCurve,
-}
-#[derive(Clone, Debug, Serialize, Deserialize)]
-pub struct YieldCurve {
-    pub date: chrono::NaiveDate,
-    pub yields: HashMap<Tenor, f64>,
+    pub payment_system: PaymentSystem,
+    pub credit: CreditRegistry,
+    /// The holiday calendar that decides when financial markets open and payments settle.
+    pub calendar: RuleCalendar,
+    /// Treasury auctions announced and not yet held.
+    #[serde(default)]
+    pub treasury_auctions: Vec<TreasuryAuction>,
+    /// What each holder paid for its securities, by the market they trade in.
+    #[serde(default)]
+    #[serde_as(as = "HashMap<DisplayFromStr, HashMap<DisplayFromStr, _>>")]
+    pub cost_basis: HashMap<AgentId, HashMap<MarketId, CostBasis>>,
 }
 
 impl Default for FinancialSystem {
     fn default() -> Self {
-        let central_bank =
-            CentralBank { id: AgentId(uuid::Uuid::new_v4()), policy_rate: 0.043, reserve_requirement: 0.1 };
+        let central_bank = CentralBank {
+            id: AgentId(uuid::Uuid::new_v4()),
+            policy_rate: 0.043,
+            reserve_requirement: 0.1,
+            maintenance_period_days: 14,
+            facilities: StandingFacilities::default(),
+        };
         let government = Government {
             id: AgentId(uuid::Uuid::new_v4()),
             tax_rates: TaxRates::default(),
             spending_targets: SpendingTargets::default(),
             debt_ceiling: Some(1_000_000_000.0),
             fiscal_policy: FiscalPolicy::default(),
+            unemployment_insurance: UnemploymentInsurance::default(),
         };
         let mut balance_sheets = HashMap::new();
         balance_sheets.insert(central_bank.id, BalanceSheet::new(central_bank.id));
@@ -45,10 +57,12 @@ impl Default for FinancialSystem {
             government,
             exchange: Exchange::default(),
             goods: GoodsRegistry::new(),
-            yield_curve: YieldCurve {
-                date: chrono::NaiveDate::from_ymd_opt(2026, 1, 1).unwrap(),
-                yields: HashMap::new(),
-            },
+            yield_curve: YieldCurve::new(chrono::NaiveDate::from_ymd_opt(2026, 1, 1).unwrap()),
+            payment_system: PaymentSystem::default(),
+            credit: CreditRegistry::default(),
+            calendar: RuleCalendar::default(),
+            treasury_auctions: vec![],
+            cost_basis: HashMap::new(),
         }
     }
 }
@@ -151,18 +165,25 @@ impl InstrumentManager for FinancialSystem {
     fn create_or_consolidate_instrument(&mut self, instrument: FinancialInstrument) -> Result<InstrumentId, String> {
         if let Some(existing_id) = self.find_consolidatable_instrument(&instrument) {
             let principal_change = instrument.principal;
+            let quantity_change = instrument.quantity();
             let existing =
                 self.instruments.get_mut(&existing_id).ok_or("Consolidatable instrument not found in main registry")?;
-            existing.principal += principal_change;
+            let consolidate = |inst: &mut FinancialInstrument| {
+                inst.principal += principal_change;
+                if let (Some(held), Some(added)) = (inst.quantity(), quantity_change) {
+                    inst.set_quantity(held + added);
+                }
+            };
+            consolidate(existing);
 
             self.balance_sheets
                 .get_mut(&existing.creditor)
                 .and_then(|bs| bs.assets.get_mut(&existing_id))
-                .map(|inst| inst.principal += principal_change);
+                .map(consolidate);
             self.balance_sheets
                 .get_mut(&existing.debtor)
                 .and_then(|bs| bs.liabilities.get_mut(&existing_id))
-                .map(|inst| inst.principal += principal_change);
+                .map(consolidate);
 
             Ok(existing_id)
         } else {
@@ -223,67 +244,53 @@ impl InstrumentManager for FinancialSystem {
     ) -> Result<InstrumentId, String> {
         let seller_instrument = self.instruments.get(instrument_id).ok_or("Instrument not found")?.clone();
 
-        let bond_details =
-            seller_instrument.details.as_any().downcast_ref::<BondDetails>().ok_or("Instrument is not a bond")?;
+        let held_quantity = seller_instrument.quantity().ok_or("Instrument is not a bond or share holding")?;
 
-        if bond_details.quantity < quantity_to_transfer {
-            return Err(format!(
-                "Insufficient bond quantity: have {}, need {}",
-                bond_details.quantity, quantity_to_transfer
-            ));
+        if held_quantity < quantity_to_transfer {
+            return Err(format!("Insufficient quantity: have {}, need {}", held_quantity, quantity_to_transfer));
         }
 
         let seller = seller_instrument.creditor;
-        let remaining_quantity = bond_details.quantity - quantity_to_transfer;
-        let principal_per_bond = seller_instrument.principal / bond_details.quantity as f64;
-        let transfer_principal = principal_per_bond * quantity_to_transfer as f64;
+        let remaining_quantity = held_quantity - quantity_to_transfer;
+        let principal_per_unit = seller_instrument.principal / held_quantity as f64;
+        let transfer_principal = principal_per_unit * quantity_to_transfer as f64;
         let remaining_principal = seller_instrument.principal - transfer_principal;
 
         if remaining_quantity == 0 {
             self.remove_instrument(instrument_id)?;
         } else {
+            let update = |inst: &mut FinancialInstrument| {
+                inst.principal = remaining_principal;
+                inst.set_quantity(remaining_quantity);
+            };
             let updated_instrument =
                 self.instruments.get_mut(instrument_id).ok_or("Instrument not found for update")?;
-            updated_instrument.principal = remaining_principal;
-
-            if let Some(updated_details) = updated_instrument.details.as_any_mut().downcast_mut::<BondDetails>() {
-                updated_details.quantity = remaining_quantity;
-            }
+            update(updated_instrument);
+            let debtor = updated_instrument.debtor;
 
-            if let Some(seller_bs) = self.balance_sheets.get_mut(&seller) {
-                if let Some(asset) = seller_bs.assets.get_mut(instrument_id) {
-                    asset.principal = remaining_principal;
-                    if let Some(asset_details) = asset.details.as_any_mut().downcast_mut::<BondDetails>() {
-                        asset_details.quantity = remaining_quantity;
-                    }
-                }
+            if let Some(asset) = self.balance_sheets.get_mut(&seller).and_then(|bs| bs.assets.get_mut(instrument_id)) {
+                update(asset);
             }
-
-            let debtor = updated_instrument.debtor;
-            if let Some(debtor_bs) = self.balance_sheets.get_mut(&debtor) {
-                if let Some(liability) = debtor_bs.liabilities.get_mut(instrument_id) {
-                    liability.principal = remaining_principal;
-                    if let Some(liability_details) = liability.details.as_any_mut().downcast_mut::<BondDetails>() {
-                        liability_details.quantity = remaining_quantity;
-                    }
-                }
+            if let Some(liability) =
+                self.balance_sheets.get_mut(&debtor).and_then(|bs| bs.liabilities.get_mut(instrument_id))
+            {
+                update(liability);
             }
         }
 
-        let mut buyer_bond_details = bond_details.clone();
-        buyer_bond_details.quantity = quantity_to_transfer;
-
-        let buyer_instrument = FinancialInstrument {
+        let mut buyer_instrument = FinancialInstrument {
             id: InstrumentId(Uuid::new_v4()),
             creditor: buyer,
             debtor: seller_instrument.debtor,
             principal: transfer_principal,
-            details: Box::new(buyer_bond_details),
+            details: seller_instrument.details.clone(),
             originated_date: seller_instrument.originated_date,
-            accrued_interest: (seller_instrument.accrued_interest / bond_details.quantity as f64)
-                * quantity_to_transfer as f64,
+            accrued_interest: (seller_instrument.accrued_interest / held_quantity as f64) * quantity_to_transfer as f64,
             last_accrual_date: seller_instrument.last_accrual_date,
+            day_count: seller_instrument.day_count,
+            payment_schedule: seller_instrument.payment_schedule.clone(),
         };
+        buyer_instrument.set_quantity(quantity_to_transfer);
 
         self.create_or_consolidate_instrument(buyer_instrument)
     }
@@ -359,18 +366,245 @@ impl FinancialStatistics for FinancialSystem {
 }
 
 impl FinancialSystem {
+    /// Moves the curve to `date` from the Treasury order books: each quoted tenor's yield is the yield
+    /// to maturity of its benchmark issue at the mid of the best clean-price quotes. Tenors without a
+    /// two-sided market keep their last yield.
     pub fn update_yield_curve(&mut self, date: chrono::NaiveDate) {
         let mut yields = HashMap::new();
         for (market_id, market) in &self.exchange.financial_markets {
-            if let FinancialMarketId::Treasury { tenor } = market_id {
-                if let (Some(bid), Some(ask)) = (market.order_book.best_bid(), market.order_book.best_ask()) {
-                    let price = (bid.price + ask.price) / 2.0;
-                    let daily_rate = market_id.price_to_daily_rate(price);
-                    let annual_rate = (1.0 + daily_rate).powf(365.0) - 1.0;
-                    yields.insert(*tenor, annual_rate);
+            if let FinancialMarketId::Treasury { tenor } = market_id
+                && let (Some(bid), Some(ask)) = (market.order_book.best_bid(), market.order_book.best_ask())
+            {
+                let mid = (bid.price + ask.price) / 2.0;
+                if let Some(ytm) = BondDetails::benchmark(*tenor, date).pricer(date).yield_to_maturity(mid) {
+                    yields.insert(*tenor, ytm);
+                }
+            }
+        }
+        self.yield_curve.update(date, &yields);
+    }
+
+    /// Marks a bond holding to market: the clean price of the bond at the risk-free yield for its
+    /// tenor, plus its issuer's current spread for a corporate bond, times the bonds held. `None` for
+    /// anything that is not a bond.
+    pub fn bond_market_value(&self, instrument: &FinancialInstrument, date: chrono::NaiveDate) -> Option<f64> {
+        let bond = instrument.details.as_any().downcast_ref::<BondDetails>()?;
+        let spread = match bond.bond_type {
+            BondType::Corporate { .. } => self.credit.rating_or_unrated(&instrument.debtor).spread(),
+            BondType::Government => 0.0,
+        };
+        let ytm = self.risk_free_rate(&bond.tenor) + spread;
+        Some(bond.pricer(date).clean_price(ytm) * bond.quantity as f64)
+    }
+
+    /// The liabilities `bank` must hold reserves against: the demand and savings deposits it owes.
+    /// Interbank and facility borrowing, bonds and equity are not reservable.
+    pub fn reservable_liabilities(&self, bank: &AgentId) -> f64 {
+        self.get_bs_by_id(bank).map_or(0.0, |bs| {
+            bs.liabilities
+                .values()
+                .filter(|inst| {
+                    let details = inst.details.as_any();
+                    details.is::<DemandDepositDetails>() || details.is::<SavingsDepositDetails>()
+                })
+                .map(|inst| inst.principal)
+                .sum::<f64>()
+        })
+    }
+
+    /// Reserves `bank` must hold against its reservable liabilities.
+    pub fn required_reserves(&self, bank: &AgentId) -> f64 {
+        self.reservable_liabilities(bank) * self.central_bank.reserve_requirement
+    }
+
+    /// Reserves above the requirement, on which the central bank pays interest.
+    pub fn excess_reserves(&self, bank: &AgentId) -> f64 {
+        (self.get_bank_reserves(bank).unwrap_or(0.0) - self.required_reserves(bank)).max(0.0)
+    }
+
+    /// What the lending facility will still lend `bank`: the market value of its Treasuries after the
+    /// haircut, less the collateral already pledged to its outstanding facility loans.
+    pub fn facility_borrowing_capacity(&self, bank: &AgentId, date: chrono::NaiveDate) -> f64 {
+        let Some(bs) = self.get_bs_by_id(bank) else {
+            return 0.0;
+        };
+        let treasuries: f64 = bs
+            .assets
+            .values()
+            .filter(|inst| {
+                let bond = inst.details.as_any().downcast_ref::<BondDetails>();
+                bond.is_some_and(|bond| bond.bond_type == BondType::Government)
+            })
+            .filter_map(|inst| self.bond_market_value(inst, date))
+            .sum();
+        let pledged: f64 = bs
+            .liabilities
+            .values()
+            .filter_map(|inst| inst.details.as_any().downcast_ref::<LoanDetails>())
+            .filter(|loan| loan.loan_type == LoanType::StandingFacility)
+            .filter_map(|loan| loan.collateral.as_ref().map(|collateral| collateral.value))
+            .sum();
+        (treasuries * (1.0 - self.central_bank.facilities.collateral_haircut) - pledged).max(0.0)
+    }
+
+    /// Every holding of `firm`'s shares as `(holding, shareholder, shares)`, in a stable order.
+    pub fn shareholders(&self, firm: &AgentId) -> Vec<(InstrumentId, AgentId, u64)> {
+        let mut holdings: Vec<_> = self
+            .instruments
+            .values()
+            .filter(|inst| inst.is_equity_of(firm))
+            .filter_map(|inst| inst.quantity().map(|shares| (inst.id, inst.creditor, shares)))
+            .collect();
+        holdings.sort_by_key(|(id, ..)| id.0);
+        holdings
+    }
+
+    pub fn shares_held(&self, agent_id: &AgentId, firm: &AgentId) -> u64 {
+        self.get_bs_by_id(agent_id).map_or(0, |bs| {
+            bs.assets.values().filter(|inst| inst.is_equity_of(firm)).filter_map(|inst| inst.quantity()).sum()
+        })
+    }
+
+    pub fn shares_outstanding(&self, firm: &AgentId) -> u64 {
+        self.shareholders(firm).iter().map(|(.., shares)| shares).sum()
+    }
+
+    /// Risk-free rate for `tenor`: the Treasury yield on the curve, otherwise the policy rate.
+    pub fn risk_free_rate(&self, tenor: &Tenor) -> f64 {
+        self.risk_free_rate_for_years(tenor.years())
+    }
+
+    /// Risk-free par yield for a maturity of `years`, interpolated along the curve. The policy rate
+    /// until the Treasury markets have quoted a tenor.
+    pub fn risk_free_rate_for_years(&self, years: f64) -> f64 {
+        self.yield_curve.par_yield(years).unwrap_or(self.central_bank.policy_rate)
+    }
+
+    /// The rating bucket a corporate bond trades in, which follows its issuer's current rating. `None`
+    /// for anything that is not a corporate bond.
+    pub fn corporate_bond_rating(&self, instrument: &FinancialInstrument) -> Option<CreditRating> {
+        let bond = instrument.details.as_any().downcast_ref::<BondDetails>()?;
+        matches!(bond.bond_type, BondType::Corporate { .. })
+            .then(|| self.credit.rating_or_unrated(&instrument.debtor))
+    }
+
+    /// Corporate bonds `agent_id` holds in the `rating` bucket.
+    pub fn corporate_bonds_held(&self, agent_id: &AgentId, rating: &CreditRating) -> u64 {
+        self.get_bs_by_id(agent_id).map_or(0, |bs| {
+            bs.assets
+                .values()
+                .filter(|inst| self.corporate_bond_rating(inst).as_ref() == Some(rating))
+                .filter_map(|inst| inst.quantity())
+                .sum()
+        })
+    }
+    /// `agent_id`'s holdings of Treasuries of `tenor` as `(holding, bonds)`, in a stable order.
+    pub fn treasury_lots(&self, agent_id: &AgentId, tenor: &Tenor) -> Vec<(InstrumentId, u64)> {
+        let mut lots: Vec<_> = self.get_bs_by_id(agent_id).map_or(vec![], |bs| {
+            bs.assets
+                .values()
+                .filter(|inst| {
+                    inst.details
+                        .as_any()
+                        .downcast_ref::<BondDetails>()
+                        .is_some_and(|bond| bond.bond_type == BondType::Government && bond.tenor == *tenor)
+                })
+                .filter_map(|inst| inst.quantity().map(|bonds| (inst.id, bonds)))
+                .collect()
+        });
+        lots.sort_by_key(|(id, _)| id.0);
+        lots
+    }
+
+    pub fn treasuries_held(&self, agent_id: &AgentId, tenor: &Tenor) -> u64 {
+        self.treasury_lots(agent_id, tenor).iter().map(|(_, bonds)| bonds).sum()
+    }
+
+    /// Face value of the Treasuries outstanding.
+    pub fn government_debt(&self) -> f64 {
+        self.instruments
+            .values()
+            .filter(|inst| inst.debtor == self.government.id)
+            .filter_map(|inst| inst.details.as_any().downcast_ref::<BondDetails>())
+            .map(|bond| bond.face_value * bond.quantity as f64)
+            .sum()
+    }
+
+    /// Face value of the Treasuries announced for auction and not yet sold.
+    pub fn government_debt_announced(&self) -> f64 {
+        self.treasury_auctions.iter().map(|auction| auction.offering as f64 * BENCHMARK_FACE_VALUE).sum()
+    }
+
+    pub fn treasury_auction(&self, id: &AuctionId) -> Option<&TreasuryAuction> {
+        self.treasury_auctions.iter().find(|auction| auction.id == *id)
+    }
+
+    /// The market a bond or share holding trades in: Treasuries by tenor, corporate bonds by their
+    /// issuer's rating and shares by firm.
+    pub fn holding_market(&self, instrument: &FinancialInstrument) -> Option<MarketId> {
+        let details = instrument.details.as_any();
+        let market_id = match details.downcast_ref::<BondDetails>() {
+            Some(bond) if bond.bond_type == BondType::Government => FinancialMarketId::Treasury { tenor: bond.tenor },
+            Some(_) => FinancialMarketId::CorporateBond { rating: self.corporate_bond_rating(instrument)? },
+            None if details.is::<EquityDetails>() => FinancialMarketId::Equity { firm: instrument.debtor },
+            None => return None,
+        };
+        Some(MarketId::Financial(market_id))
+    }
+
+    /// Average price `holder` paid per unit of what it holds in `market_id`; `None` if it has no
+    /// recorded purchases there.
+    pub fn average_cost(&self, holder: &AgentId, market_id: &MarketId) -> Option<f64> {
+        self.cost_basis.get(holder)?.get(market_id)?.average_cost()
+    }
+
+    /// The central bank's balance sheet by line on `date`.
+    pub fn central_bank_balance_sheet(&self, date: chrono::NaiveDate) -> CentralBankBalanceSheet {
+        let mut report = CentralBankBalanceSheet { date, ..Default::default() };
+        let Some(bs) = self.get_bs_by_id(&self.central_bank.id) else {
+            return report;
+        };
+        for inst in bs.assets.values() {
+            let details = inst.details.as_any();
+            if let Some(bond) = details.downcast_ref::<BondDetails>()
+                && bond.bond_type == BondType::Government
+            {
+                let market_value = self.bond_market_value(inst, date).unwrap_or(inst.principal);
+                match report.treasuries.iter_mut().find(|holding| holding.tenor == bond.tenor) {
+                    Some(holding) => {
+                        holding.quantity += bond.quantity;
+                        holding.market_value += market_value;
+                    }
+                    None => {
+                        let holding = TreasuryHolding { tenor: bond.tenor, quantity: bond.quantity, market_value };
+                        report.treasuries.push(holding);
+                    }
                 }
+            } else if details
+                .downcast_ref::<LoanDetails>()
+                .is_some_and(|loan| loan.loan_type == LoanType::StandingFacility)
+            {
+                report.facility_lending += inst.principal;
+            } else {
+                report.other_assets += inst.principal;
+            }
+        }
+        for inst in bs.liabilities.values() {
+            let details = inst.details.as_any();
+            if details.is::<CentralBankReservesDetails>() {
+                report.reserves += inst.principal;
+            } else if details
+                .downcast_ref::<LoanDetails>()
+                .is_some_and(|loan| loan.loan_type == LoanType::DepositFacility)
+            {
+                report.deposit_facility += inst.principal;
+            } else if details.is::<CashDetails>() {
+                report.currency += inst.principal;
+            } else {
+                report.other_liabilities += inst.principal;
             }
         }
-        self.yield_curve = YieldCurve { date, yields };
+        report.treasuries.sort_by_key(|holding| holding.tenor.to_days());
+        report
     }
 }
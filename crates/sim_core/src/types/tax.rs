@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+
+/// What a holder paid for the units it holds in one market. Capital gains are worked out against the
+/// average cost.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct CostBasis {
+    pub units: u64,
+    pub cost: f64,
+}
+
+impl CostBasis {
+    /// `None` once every unit has been sold.
+    pub fn average_cost(&self) -> Option<f64> {
+        (self.units > 0).then(|| self.cost / self.units as f64)
+    }
+
+    pub fn add(&mut self, units: u64, cost: f64) {
+        self.units += units;
+        self.cost += cost;
+    }
+
+    /// Takes `units` out at the average cost, or everything if fewer are held.
+    pub fn remove(&mut self, units: u64) {
+        let removed = units.min(self.units);
+        self.cost -= self.average_cost().unwrap_or(0.0) * removed as f64;
+        self.units -= removed;
+        if self.units == 0 {
+            self.cost = 0.0;
+        }
+    }
+}
@@ -1,42 +1,89 @@
-use chrono::{NaiveDate, Datelike, Duration};
+use chrono::{NaiveDate, Datelike, Duration, Months};
 use serde::{Deserialize, Serialize};
+use crate::*;
 
 pub fn year_fraction(start: NaiveDate, end: NaiveDate) -> f64 {
-    (end - start).num_days() as f64 / 365.0
+    DayCount::Act365Fixed.year_fraction(start, end)
 }
 
 pub fn year_fraction_360(start: NaiveDate, end: NaiveDate) -> f64 {
-    (end - start).num_days() as f64 / 360.0
+    DayCount::Act360.year_fraction(start, end)
+}
+
+/// How an instrument counts the days between two dates when it accrues interest.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+pub enum DayCount {
+    /// Actual days over 360, the money-market basis.
+    Act360,
+    /// Actual days over a fixed 365-day year.
+    #[default]
+    Act365Fixed,
+    /// US bond basis: every month has 30 days and the year 360.
+    Thirty360,
+    /// ISDA actual/actual: the days falling in each calendar year over that year's length.
+    ActAct,
+}
+
+impl DayCount {
+    pub fn year_fraction(&self, start: NaiveDate, end: NaiveDate) -> f64 {
+        if end < start {
+            return -self.year_fraction(end, start);
+        }
+        match self {
+            DayCount::Act360 => (end - start).num_days() as f64 / 360.0,
+            DayCount::Act365Fixed => (end - start).num_days() as f64 / 365.0,
+            DayCount::Thirty360 => {
+                let d1 = start.day().min(30);
+                let d2 = if d1 == 30 { end.day().min(30) } else { end.day() };
+                let days = 360 * (end.year() - start.year())
+                    + 30 * (end.month() as i32 - start.month() as i32)
+                    + (d2 as i32 - d1 as i32);
+                days as f64 / 360.0
+            },
+            DayCount::ActAct => {
+                let mut fraction = 0.0;
+                let mut from = start;
+                while from < end {
+                    let next_year = NaiveDate::from_ymd_opt(from.year() + 1, 1, 1).unwrap_or(end);
+                    let to = next_year.min(end);
+                    let year_length = if is_leap_year(from.year()) { 366.0 } else { 365.0 };
+                    fraction += (to - from).num_days() as f64 / year_length;
+                    from = to;
+                }
+                fraction
+            },
+        }
+    }
 }
 
 pub fn is_weekend(date: NaiveDate) -> bool {
     matches!(date.weekday(), chrono::Weekday::Sat | chrono::Weekday::Sun)
 }
 
-pub fn next_business_day(date: NaiveDate) -> NaiveDate {
+pub fn next_business_day(date: NaiveDate, calendar: &dyn HolidayCalendar) -> NaiveDate {
     let mut next = date + Duration::days(1);
-    while is_weekend(next) {
+    while !calendar.is_business_day(next) {
         next = next + Duration::days(1);
     }
     next
 }
 
-pub fn previous_business_day(date: NaiveDate) -> NaiveDate {
+pub fn previous_business_day(date: NaiveDate, calendar: &dyn HolidayCalendar) -> NaiveDate {
     let mut prev = date - Duration::days(1);
-    while is_weekend(prev) {
+    while !calendar.is_business_day(prev) {
         prev = prev - Duration::days(1);
     }
     prev
 }
 
-pub fn add_business_days(date: NaiveDate, days: i32) -> NaiveDate {
+pub fn add_business_days(date: NaiveDate, days: i32, calendar: &dyn HolidayCalendar) -> NaiveDate {
     let mut current = date;
     let mut remaining = days.abs();
     let step = if days >= 0 { 1 } else { -1 };
     
     while remaining > 0 {
         current = current + Duration::days(step as i64);
-        if !is_weekend(current) {
+        if calendar.is_business_day(current) {
             remaining -= 1;
         }
     }
@@ -58,6 +105,35 @@ pub enum TimePeriod {
 }
 
 impl TimePeriod {
+    /// The period between payments made `per_year` times a year.
+    pub fn from_frequency(per_year: usize) -> Self {
+        match per_year {
+            0 | 1 => TimePeriod::Annual,
+            2 => TimePeriod::SemiAnnual,
+            4 => TimePeriod::Quarterly,
+            12 => TimePeriod::Monthly,
+            n => TimePeriod::Months((12 / n.min(12)) as u32),
+        }
+    }
+
+    /// The date `n` whole periods before `date`. Month-based periods keep the day of month where it
+    /// exists and fall back to the month's last day where it does not.
+    pub fn sub_periods(&self, date: NaiveDate, n: u32) -> NaiveDate {
+        let months = |m: u32| date.checked_sub_months(Months::new(m * n)).unwrap_or(NaiveDate::MIN);
+        match self {
+            TimePeriod::Days(d) => date - Duration::days((*d * n) as i64),
+            TimePeriod::Weeks(w) => date - Duration::weeks((*w * n) as i64),
+            TimePeriod::Months(m) => months(*m),
+            TimePeriod::Years(y) => months(*y * 12),
+            TimePeriod::Overnight => date - Duration::days(n as i64),
+            TimePeriod::Weekly => date - Duration::weeks(n as i64),
+            TimePeriod::Monthly => months(1),
+            TimePeriod::Quarterly => months(3),
+            TimePeriod::SemiAnnual => months(6),
+            TimePeriod::Annual => months(12),
+        }
+    }
+
     pub fn to_days(&self) -> u32 {
         match self {
             TimePeriod::Days(d) => *d,
@@ -141,54 +217,60 @@ pub enum BusinessDayConvention {
 }
 
 impl BusinessDayConvention {
-    pub fn adjust(&self, date: NaiveDate) -> NaiveDate {
+    /// Moves `date` to a business day of `calendar` if it is not one already.
+    pub fn adjust(&self, date: NaiveDate, calendar: &dyn HolidayCalendar) -> NaiveDate {
+        if calendar.is_business_day(date) {
+            return date;
+        }
         match self {
             BusinessDayConvention::None => date,
-            BusinessDayConvention::Following => {
-                if is_weekend(date) {
-                    next_business_day(date)
-                } else {
-                    date
-                }
-            },
-            BusinessDayConvention::Preceding => {
-                if is_weekend(date) {
-                    previous_business_day(date)
-                } else {
-                    date
-                }
-            },
+            BusinessDayConvention::Following => next_business_day(date, calendar),
+            BusinessDayConvention::Preceding => previous_business_day(date, calendar),
             BusinessDayConvention::ModifiedFollowing => {
-                if is_weekend(date) {
-                    let next = next_business_day(date);
-                    if next.month() != date.month() {
-                        previous_business_day(date)
-                    } else {
-                        next
-                    }
+                let next = next_business_day(date, calendar);
+                if next.month() != date.month() {
+                    previous_business_day(date, calendar)
                 } else {
-                    date
+                    next
                 }
             },
             BusinessDayConvention::ModifiedPreceding => {
-                if is_weekend(date) {
-                    let prev = previous_business_day(date);
-                    if prev.month() != date.month() {
-                        next_business_day(date)
-                    } else {
-                        prev
-                    }
+                let prev = previous_business_day(date, calendar);
+                if prev.month() != date.month() {
+                    next_business_day(date, calendar)
                 } else {
-                    date
+                    prev
                 }
             },
         }
     }
 }
 
+/// Payment dates of an instrument running from `start` to `end` that pays every `period`. Dates roll
+/// back from `end` in whole periods, so a short stub falls at the start, and each date is then moved
+/// to a business day of `calendar` by `convention`. `end` itself is always the last payment.
+pub fn payment_schedule(
+    start: NaiveDate, end: NaiveDate, period: &TimePeriod, convention: &BusinessDayConvention,
+    calendar: &dyn HolidayCalendar,
+) -> Vec<NaiveDate> {
+    let mut dates = Vec::new();
+    let mut n = 0;
+    loop {
+        let date = period.sub_periods(end, n);
+        if date <= start || (n > 0 && date >= end) {
+            break;
+        }
+        dates.push(convention.adjust(date, calendar));
+        n += 1;
+    }
+    dates.reverse();
+    dates
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{AgentId, LoanType};
     use chrono::NaiveDate;
     
     #[test]
@@ -203,7 +285,7 @@ mod tests {
     #[test]
     fn test_business_days() {
         let friday = NaiveDate::from_ymd_opt(2024, 1, 5).unwrap();
-        let next = next_business_day(friday);
+        let next = next_business_day(friday, &RuleCalendar::weekends_only());
         let monday = NaiveDate::from_ymd_opt(2024, 1, 8).unwrap();
         
         assert_eq!(next, monday);
@@ -217,4 +299,67 @@ mod tests {
         
         assert_eq!(result, feb_29);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_day_counts() {
+        let start = NaiveDate::from_ymd_opt(2023, 7, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2024, 7, 1).unwrap();
+
+        assert!((DayCount::Act360.year_fraction(start, end) - 366.0 / 360.0).abs() < 1e-12);
+        assert!((DayCount::Act365Fixed.year_fraction(start, end) - 366.0 / 365.0).abs() < 1e-12);
+        assert!((DayCount::Thirty360.year_fraction(start, end) - 1.0).abs() < 1e-12);
+        assert!((DayCount::ActAct.year_fraction(start, end) - (184.0 / 365.0 + 182.0 / 366.0)).abs() < 1e-12);
+
+        let jan_31 = NaiveDate::from_ymd_opt(2024, 1, 31).unwrap();
+        let mar_31 = NaiveDate::from_ymd_opt(2024, 3, 31).unwrap();
+        assert!((DayCount::Thirty360.year_fraction(jan_31, mar_31) - 60.0 / 360.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_payment_schedule_rolls_back_from_maturity() {
+        let start = NaiveDate::from_ymd_opt(2026, 1, 15).unwrap();
+        let end = NaiveDate::from_ymd_opt(2027, 8, 31).unwrap();
+
+        let weekends = RuleCalendar::weekends_only();
+        let schedule = payment_schedule(start, end, &TimePeriod::SemiAnnual, &BusinessDayConvention::None, &weekends);
+        let expected: Vec<NaiveDate> = [(2026, 2, 28), (2026, 8, 31), (2027, 2, 28), (2027, 8, 31)]
+            .iter()
+            .map(|(y, m, d)| NaiveDate::from_ymd_opt(*y, *m, *d).unwrap())
+            .collect();
+        assert_eq!(schedule, expected);
+
+        // 28 February falls on a Saturday in 2026 and a Sunday in 2027
+        let adjusted =
+            payment_schedule(start, end, &TimePeriod::SemiAnnual, &BusinessDayConvention::Following, &weekends);
+        assert_eq!(adjusted[0], NaiveDate::from_ymd_opt(2026, 3, 2).unwrap());
+        assert_eq!(adjusted[2], NaiveDate::from_ymd_opt(2027, 3, 1).unwrap());
+        assert_eq!((adjusted[1], adjusted[3]), (expected[1], expected[3]));
+
+        // Modified following keeps 31 August 2026 in August when it is a holiday
+        let august_holiday = RuleCalendar {
+            name: "Test".to_string(),
+            rules: vec![HolidayRule::Fixed { month: 8, day: 31, observed: false }],
+        };
+        let convention = BusinessDayConvention::ModifiedFollowing;
+        let modified = payment_schedule(start, end, &TimePeriod::SemiAnnual, &convention, &august_holiday);
+        assert_eq!(modified[1], NaiveDate::from_ymd_opt(2026, 8, 28).unwrap());
+    }
+
+    #[test]
+    fn test_loans_pay_interest_monthly_on_business_days() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        let (lender, borrower) = (AgentId::default(), AgentId::default());
+        let maturity = date(2027, 1, 15);
+        let loan = crate::loan!(lender, borrower, 1_000.0, LoanType::Personal, 0.05, maturity, date(2026, 1, 15));
+        assert_eq!(loan.payment_schedule.len(), 12);
+        assert!(loan.payment_schedule.iter().all(|day| !is_weekend(*day)));
+        // 15 February 2026 is a Sunday
+        assert_eq!(loan.payment_schedule[0], date(2026, 2, 16));
+        assert_eq!(loan.payment_schedule[11], maturity);
+
+        // Lent on a Friday, an overnight loan pays on the Monday
+        let (friday, saturday) = (date(2026, 1, 16), date(2026, 1, 17));
+        let overnight = crate::loan!(lender, borrower, 1_000.0, LoanType::Interbank, 0.05, saturday, friday);
+        assert_eq!(overnight.payment_schedule, vec![date(2026, 1, 19)]);
+    }
+}
@@ -0,0 +1,283 @@
+use crate::*;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// One benchmark tenor on the curve. `observed` is the last date the Treasury market for the tenor
+/// had a two-sided quote; a tenor without a fresh quote keeps its last yield and `age_days` says how
+/// old it is. Rates compound semi-annually, like the benchmark coupons.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CurvePoint {
+    pub tenor: Tenor,
+    pub years: f64,
+    pub yield_to_maturity: f64,
+    pub zero_rate: f64,
+    pub observed: NaiveDate,
+    pub age_days: i64,
+}
+
+impl CurvePoint {
+    pub fn is_stale(&self) -> bool {
+        self.age_days > 0
+    }
+}
+
+/// Nelson–Siegel–Svensson zero curve. With fewer than four points to fit, the later terms are left at
+/// zero, falling back to Nelson–Siegel and then to a flat or sloped curve.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct NelsonSiegelSvensson {
+    pub beta0: f64,
+    pub beta1: f64,
+    pub beta2: f64,
+    pub beta3: f64,
+    pub tau1: f64,
+    pub tau2: f64,
+}
+
+impl NelsonSiegelSvensson {
+    const TAU1_GRID: [f64; 8] = [0.5, 1.0, 1.5, 2.0, 3.0, 4.0, 5.0, 7.0];
+    const TAU2_GRID: [f64; 6] = [5.0, 8.0, 10.0, 15.0, 20.0, 30.0];
+    /// Ridge penalty that keeps the betas finite when the fit is exactly determined.
+    const RIDGE: f64 = 1e-8;
+
+    fn loadings(years: f64, tau1: f64, tau2: f64) -> [f64; 4] {
+        let t = years.max(1e-6);
+        let (x1, x2) = (t / tau1, t / tau2);
+        let slope = (1.0 - (-x1).exp()) / x1;
+        [1.0, slope, slope - (-x1).exp(), (1.0 - (-x2).exp()) / x2 - (-x2).exp()]
+    }
+
+    pub fn zero_rate(&self, years: f64) -> f64 {
+        let l = Self::loadings(years, self.tau1, self.tau2);
+        self.beta0 * l[0] + self.beta1 * l[1] + self.beta2 * l[2] + self.beta3 * l[3]
+    }
+
+    /// Least-squares fit to `(years, zero rate)` points, choosing the decay parameters from a grid.
+    pub fn fit(points: &[(f64, f64)]) -> Option<Self> {
+        let terms = points.len().min(4);
+        if terms == 0 {
+            return None;
+        }
+        let mut best: Option<(f64, Self)> = None;
+        for tau1 in Self::TAU1_GRID {
+            for tau2 in Self::TAU2_GRID.into_iter().filter(|tau2| *tau2 > tau1) {
+                let rows: Vec<[f64; 4]> = points.iter().map(|(t, _)| Self::loadings(*t, tau1, tau2)).collect();
+                let mut normal = vec![vec![0.0; terms]; terms];
+                let mut rhs = vec![0.0; terms];
+                for (row, (_, rate)) in rows.iter().zip(points) {
+                    for i in 0..terms {
+                        rhs[i] += row[i] * rate;
+                        for j in 0..terms {
+                            normal[i][j] += row[i] * row[j];
+                        }
+                    }
+                }
+                for (i, row) in normal.iter_mut().enumerate() {
+                    row[i] += Self::RIDGE;
+                }
+                let Some(betas) = solve_linear(normal, rhs) else {
+                    continue;
+                };
+                let beta = |i: usize| betas.get(i).copied().unwrap_or(0.0);
+                let curve = Self { beta0: beta(0), beta1: beta(1), beta2: beta(2), beta3: beta(3), tau1, tau2 };
+                let error: f64 = points.iter().map(|(t, rate)| (curve.zero_rate(*t) - rate).powi(2)).sum();
+                if best.as_ref().is_none_or(|(best_error, _)| error < *best_error - 1e-15) {
+                    best = Some((error, curve));
+                }
+            }
+        }
+        best.map(|(_, curve)| curve)
+    }
+}
+
+/// Gaussian elimination with partial pivoting. `None` if the system is singular.
+fn solve_linear(mut a: Vec<Vec<f64>>, mut b: Vec<f64>) -> Option<Vec<f64>> {
+    let n = b.len();
+    for col in 0..n {
+        let pivot = (col..n).max_by(|x, y| a[*x][col].abs().total_cmp(&a[*y][col].abs()))?;
+        if a[pivot][col].abs() < 1e-14 {
+            return None;
+        }
+        a.swap(col, pivot);
+        b.swap(col, pivot);
+        let (upper, lower) = a.split_at_mut(col + 1);
+        let pivot_row = &upper[col];
+        for (offset, row) in lower.iter_mut().enumerate() {
+            let factor = row[col] / pivot_row[col];
+            for (value, pivot_value) in row.iter_mut().zip(pivot_row).skip(col) {
+                *value -= factor * pivot_value;
+            }
+            b[col + 1 + offset] -= factor * b[col];
+        }
+    }
+    let mut x = vec![0.0; n];
+    for row in (0..n).rev() {
+        let tail: f64 = (row + 1..n).map(|k| a[row][k] * x[k]).sum();
+        x[row] = (b[row] - tail) / a[row][row];
+    }
+    Some(x)
+}
+
+/// The Treasury curve. Benchmark yields come from the Treasury markets; zero rates are bootstrapped
+/// from the benchmark bonds priced at those yields, and a Nelson–Siegel–Svensson curve is fitted to
+/// the zero rates. Between benchmark tenors rates are interpolated linearly, and beyond the ends they
+/// are held flat.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct YieldCurve {
+    pub date: NaiveDate,
+    pub points: Vec<CurvePoint>,
+    pub fitted: Option<NelsonSiegelSvensson>,
+}
+
+impl YieldCurve {
+    pub fn new(date: NaiveDate) -> Self {
+        Self { date, points: Vec::new(), fitted: None }
+    }
+
+    pub fn point(&self, tenor: &Tenor) -> Option<&CurvePoint> {
+        self.points.iter().find(|point| point.tenor == *tenor)
+    }
+
+    /// Moves the curve to `date` with the benchmark yields quoted today. Tenors without a quote keep
+    /// their last yield and age; the zero rates and the fit are then rebuilt from every point.
+    pub fn update(&mut self, date: NaiveDate, quoted: &HashMap<Tenor, f64>) {
+        let mut yields: HashMap<Tenor, (f64, NaiveDate)> =
+            self.points.iter().map(|point| (point.tenor, (point.yield_to_maturity, point.observed))).collect();
+        for (tenor, ytm) in quoted {
+            yields.insert(*tenor, (*ytm, date));
+        }
+
+        let mut points: Vec<CurvePoint> = yields
+            .into_iter()
+            .map(|(tenor, (yield_to_maturity, observed))| CurvePoint {
+                tenor,
+                years: tenor.years(),
+                yield_to_maturity,
+                zero_rate: yield_to_maturity,
+                observed,
+                age_days: (date - observed).num_days(),
+            })
+            .collect();
+        points.sort_by(|a, b| a.years.total_cmp(&b.years));
+
+        self.date = date;
+        self.points = points;
+        self.bootstrap();
+        let zeros: Vec<(f64, f64)> = self.points.iter().map(|point| (point.years, point.zero_rate)).collect();
+        self.fitted = NelsonSiegelSvensson::fit(&zeros);
+    }
+
+    /// Solves each tenor's zero rate in turn so that discounting its benchmark bond's cash flows on
+    /// the zero curve built so far reproduces the bond's price at its quoted yield.
+    fn bootstrap(&mut self) {
+        let mut knots: Vec<(f64, f64)> = Vec::new();
+        for point in &mut self.points {
+            let bond = BondDetails::benchmark(point.tenor, self.date);
+            let pricer = bond.pricer(self.date);
+            let target = pricer.dirty_price(point.yield_to_maturity);
+            let flows = pricer.cash_flows_in_years();
+
+            let price_at = |zero: f64| {
+                let mut trial = knots.clone();
+                trial.push((point.years, zero));
+                flows.iter().map(|(t, amount)| amount * discount_factor(interpolate(&trial, *t), *t)).sum::<f64>()
+            };
+            let (mut low, mut high) = (-0.5, 1.0);
+            for _ in 0..100 {
+                let mid = (low + high) / 2.0;
+                if price_at(mid) > target { low = mid } else { high = mid }
+            }
+            point.zero_rate = (low + high) / 2.0;
+            knots.push((point.years, point.zero_rate));
+        }
+    }
+
+    /// Benchmark yield for a maturity of `years`.
+    pub fn par_yield(&self, years: f64) -> Option<f64> {
+        let knots: Vec<(f64, f64)> = self.points.iter().map(|p| (p.years, p.yield_to_maturity)).collect();
+        (!knots.is_empty()).then(|| interpolate(&knots, years))
+    }
+
+    /// Bootstrapped zero rate for a maturity of `years`.
+    pub fn zero_rate(&self, years: f64) -> Option<f64> {
+        let knots: Vec<(f64, f64)> = self.points.iter().map(|p| (p.years, p.zero_rate)).collect();
+        (!knots.is_empty()).then(|| interpolate(&knots, years))
+    }
+
+    /// Zero rate for a maturity of `years` on the fitted Nelson–Siegel–Svensson curve.
+    pub fn fitted_rate(&self, years: f64) -> Option<f64> {
+        self.fitted.as_ref().map(|curve| curve.zero_rate(years))
+    }
+
+    pub fn discount_factor(&self, years: f64) -> Option<f64> {
+        self.zero_rate(years).map(|zero| discount_factor(zero, years))
+    }
+}
+
+fn discount_factor(zero_rate: f64, years: f64) -> f64 {
+    (1.0 + zero_rate / BENCHMARK_FREQUENCY as f64).powf(-(BENCHMARK_FREQUENCY as f64) * years)
+}
+
+/// Linear interpolation over knots sorted by maturity, flat beyond either end.
+fn interpolate(knots: &[(f64, f64)], years: f64) -> f64 {
+    let (first, last) = (knots[0], knots[knots.len() - 1]);
+    if years <= first.0 {
+        return first.1;
+    }
+    if years >= last.0 {
+        return last.1;
+    }
+    let upper = knots.iter().position(|(t, _)| *t >= years).unwrap_or(knots.len() - 1);
+    let ((t0, r0), (t1, r1)) = (knots[upper - 1], knots[upper]);
+    r0 + (r1 - r0) * (years - t0) / (t1 - t0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_flat_curve_bootstraps_to_flat_zero_rates() {
+        let mut curve = YieldCurve::new(date(2026, 1, 1));
+        let quotes = HashMap::from([(Tenor::T2Y, 0.04), (Tenor::T10Y, 0.04), (Tenor::T30Y, 0.04)]);
+        curve.update(date(2026, 1, 1), &quotes);
+
+        for point in &curve.points {
+            assert!((point.zero_rate - 0.04).abs() < 5e-4, "{:?}", point);
+        }
+        assert!((curve.zero_rate(7.0).unwrap() - 0.04).abs() < 5e-4);
+        assert!((curve.fitted_rate(20.0).unwrap() - 0.04).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_curve_interpolates_and_carries_stale_points() {
+        let mut curve = YieldCurve::new(date(2026, 1, 1));
+        curve.update(date(2026, 1, 1), &HashMap::from([(Tenor::T2Y, 0.03), (Tenor::T10Y, 0.05)]));
+        let t2 = Tenor::T2Y.years();
+        let t10 = Tenor::T10Y.years();
+        let midpoint = (t2 + t10) / 2.0;
+        assert!((curve.par_yield(midpoint).unwrap() - 0.04).abs() < 1e-12);
+        assert_eq!(curve.par_yield(0.5), Some(0.03));
+        assert_eq!(curve.par_yield(40.0), Some(0.05));
+        assert!(curve.zero_rate(t10).unwrap() > 0.05, "An upward sloping par curve has zero rates above it");
+
+        curve.update(date(2026, 1, 4), &HashMap::from([(Tenor::T2Y, 0.031)]));
+        let stale = curve.point(&Tenor::T10Y).unwrap();
+        assert_eq!((stale.yield_to_maturity, stale.age_days, stale.is_stale()), (0.05, 3, true));
+        assert!(!curve.point(&Tenor::T2Y).unwrap().is_stale());
+    }
+
+    #[test]
+    fn test_nelson_siegel_svensson_fits_its_own_curve() {
+        let curve = NelsonSiegelSvensson { beta0: 0.05, beta1: -0.02, beta2: 0.01, beta3: 0.0, tau1: 2.0, tau2: 10.0 };
+        let points: Vec<(f64, f64)> = [0.5, 2.0, 5.0, 10.0, 30.0].iter().map(|t| (*t, curve.zero_rate(*t))).collect();
+        let fitted = NelsonSiegelSvensson::fit(&points).unwrap();
+        for t in [1.0, 3.0, 7.0, 20.0] {
+            assert!((fitted.zero_rate(t) - curve.zero_rate(t)).abs() < 1e-4, "at {} years", t);
+        }
+    }
+}